@@ -0,0 +1,95 @@
+//! Discovery and locale syncing of the config files that live next to Config.wtf:
+//! `WTF/Config-cache.wtf` and the account-level `WTF/Account/<ACCOUNT>/*.wtf` files.
+
+use crate::wtf::WtfDocument;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A related config file and the locale values it currently sets (if any).
+pub struct AccountConfig {
+    pub path: PathBuf,
+    pub audio_locale: Option<String>,
+    pub text_locale: Option<String>,
+}
+
+impl AccountConfig {
+    /// Path relative to the WTF directory, for display.
+    pub fn display_name(&self, config: &Path) -> String {
+        config
+            .parent()
+            .and_then(|wtf_dir| self.path.strip_prefix(wtf_dir).ok())
+            .unwrap_or(&self.path)
+            .display()
+            .to_string()
+    }
+}
+
+/// Find the config files related to `config` (the top-level Config.wtf) and read their locales.
+pub fn discover(config: &Path) -> Vec<AccountConfig> {
+    let Some(wtf_dir) = config.parent() else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = wtf_files(wtf_dir)
+        .into_iter()
+        .filter(|p| p.as_path() != config)
+        .collect();
+    if let Ok(accounts) = fs::read_dir(wtf_dir.join("Account")) {
+        let mut account_dirs: Vec<PathBuf> = accounts
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        account_dirs.sort();
+        for dir in account_dirs {
+            paths.extend(wtf_files(&dir));
+        }
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            let doc = read(&path).ok();
+            AccountConfig {
+                audio_locale: doc.as_ref().and_then(|d| d.get("audioLocale")),
+                text_locale: doc.as_ref().and_then(|d| d.get("textLocale")),
+                path,
+            }
+        })
+        .collect()
+}
+
+/// Rewrite the locale lines that `doc` already contains; a file without locale lines is left
+/// as it is.
+pub fn sync_locale(doc: &mut WtfDocument, audio: &str, text: &str) {
+    for (key, value) in [("audioLocale", audio), ("textLocale", text)] {
+        if doc.get(key).is_some_and(|v| v != value) {
+            doc.set(key, value);
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<WtfDocument, String> {
+    let meta = path.metadata().map_err(|e| e.to_string())?;
+    if meta.len() > crate::limits::get().max_config_bytes {
+        return Err("file is too large".into());
+    }
+    WtfDocument::parse(&fs::read(path).map_err(|e| e.to_string())?)
+}
+
+/// Config-style `.wtf` files directly inside `dir` (not SavedVariables or other data).
+fn wtf_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| crate::is_file_with_ext(p, "wtf"))
+        .filter(|p| {
+            p.file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.to_ascii_lowercase().starts_with("config"))
+        })
+        .collect();
+    files.sort();
+    files
+}
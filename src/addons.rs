@@ -0,0 +1,208 @@
+//! Protection of the per-character `WTF/Account/<ACCOUNT>/<Realm>/<Character>/AddOns.txt`
+//! files, which the client may reset after a locale switch, disabling every addon.
+//!
+//! The files are copied below the settings directory before a locale write; a file counts
+//! as blanked once none of the addons enabled in its copy is enabled any more.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ADDONS_FILE: &str = "AddOns.txt";
+
+/// An AddOns.txt that lost its enabled addons since it was saved.
+pub struct Blanked {
+    pub current: PathBuf,
+    pub saved: PathBuf,
+    /// `Realm/Character` (with the account when there are several), for display.
+    pub character: String,
+}
+
+/// Save a copy of every character's AddOns.txt next to the Config.wtf `config`. A file that
+/// already looks blanked keeps its earlier copy. Returns the number of files saved.
+pub fn snapshot(config: &Path) -> Result<usize, String> {
+    let store = store_dir(config).ok_or("cannot determine settings directory")?;
+    let mut saved = 0;
+    for (rel, current) in character_files(config) {
+        let copy = store.join(&rel);
+        if is_blanked(&copy, &current) {
+            continue;
+        }
+        if let Some(parent) = copy.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::copy(&current, &copy).map_err(|e| format!("{}: {}", current.display(), e))?;
+        saved += 1;
+    }
+    Ok(saved)
+}
+
+/// Characters whose AddOns.txt was blanked since the last `snapshot`.
+pub fn find_blanked(config: &Path) -> Vec<Blanked> {
+    let (Some(store), Some(account_dir)) = (store_dir(config), account_dir(config)) else {
+        return Vec::new();
+    };
+    let mut saved = Vec::new();
+    collect(&store, 3, &mut saved);
+    let several_accounts = saved
+        .iter()
+        .filter_map(|p| p.strip_prefix(&store).ok()?.components().next())
+        .collect::<HashSet<_>>()
+        .len()
+        > 1;
+    saved
+        .into_iter()
+        .filter_map(|saved| {
+            let rel = saved.strip_prefix(&store).ok()?.to_path_buf();
+            let current = account_dir.join(&rel);
+            if !is_blanked(&saved, &current) {
+                return None;
+            }
+            let parts: Vec<String> = rel
+                .parent()?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let skip = if several_accounts { 0 } else { 1 };
+            Some(Blanked {
+                current,
+                saved,
+                character: parts[skip..].join("/"),
+            })
+        })
+        .collect()
+}
+
+/// Put the saved copies of `blanked` back. Returns the number of files restored.
+pub fn restore(blanked: &[Blanked]) -> Result<usize, String> {
+    for b in blanked {
+        if let Some(parent) = b.current.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::copy(&b.saved, &b.current).map_err(|e| format!("{}: {}", b.current.display(), e))?;
+    }
+    Ok(blanked.len())
+}
+
+/// Forget the saved copies of `blanked`, accepting their current state.
+pub fn discard(blanked: &[Blanked]) {
+    for b in blanked {
+        let _ = fs::remove_file(&b.saved);
+    }
+}
+
+/// Whether `current` enables none of the addons its saved copy `saved` enables.
+fn is_blanked(saved: &Path, current: &Path) -> bool {
+    let Ok(saved) = fs::read(saved) else {
+        return false;
+    };
+    let saved = enabled(&String::from_utf8_lossy(&saved));
+    let current = fs::read(current).unwrap_or_default();
+    let current = enabled(&String::from_utf8_lossy(&current));
+    !saved.is_empty() && saved.is_disjoint(&current)
+}
+
+/// Names of the addons set to `enabled` in an AddOns.txt (`Name: enabled` lines).
+fn enabled(text: &str) -> HashSet<String> {
+    text.lines()
+        .filter_map(|line| line.rsplit_once(':'))
+        .filter(|(_, state)| state.trim().eq_ignore_ascii_case("enabled"))
+        .map(|(name, _)| name.trim().to_ascii_lowercase())
+        .collect()
+}
+
+/// `(path relative to WTF/Account, path)` of every character's AddOns.txt.
+fn character_files(config: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let Some(account_dir) = account_dir(config) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    collect(&account_dir, 3, &mut files);
+    files
+        .into_iter()
+        .filter_map(|p| Some((p.strip_prefix(&account_dir).ok()?.to_path_buf(), p)))
+        .collect()
+}
+
+/// AddOns.txt files exactly `depth` directories below `dir` (account/realm/character).
+fn collect(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if depth == 0 {
+            if path.is_file() && path.file_name().is_some_and(|n| n == ADDONS_FILE) {
+                files.push(path);
+            }
+        } else if path.is_dir() {
+            collect(&path, depth - 1, files);
+        }
+    }
+}
+
+fn account_dir(config: &Path) -> Option<PathBuf> {
+    Some(config.parent()?.join("Account"))
+}
+
+/// Where the copies of one installation's files are kept: a folder per WTF directory, so
+/// several installations don't mix.
+fn store_dir(config: &Path) -> Option<PathBuf> {
+    let wtf_dir = config.parent()?.to_string_lossy().to_lowercase();
+    let key = format!("{:08x}", crc32fast::hash(wtf_dir.as_bytes()));
+    Some(crate::settings_dir()?.join("addons").join(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENABLED: &str = "Bagnon: enabled\r\nDBM-Core: enabled\r\nDetails: disabled\r\n";
+
+    #[test]
+    fn snapshot_find_blanked_and_restore() {
+        crate::location::use_temp_dir();
+        let wtf = std::env::temp_dir().join(format!("entitan-addons-{}-wtf", std::process::id()));
+        let _ = fs::remove_dir_all(&wtf);
+        let config = wtf.join("Config.wtf");
+        let character = |name: &str| {
+            wtf.join("Account/ACCOUNT/Realm")
+                .join(name)
+                .join(ADDONS_FILE)
+        };
+        for name in ["Alice", "Bob"] {
+            fs::create_dir_all(character(name).parent().unwrap()).unwrap();
+            fs::write(character(name), ENABLED).unwrap();
+        }
+
+        assert_eq!(snapshot(&config), Ok(2));
+        assert!(find_blanked(&config).is_empty());
+
+        // The client reset one list: everything off, the names kept
+        fs::write(
+            character("Bob"),
+            "Bagnon: disabled\r\nDBM-Core: disabled\r\nDetails: disabled\r\n",
+        )
+        .unwrap();
+        let blanked = find_blanked(&config);
+        assert_eq!(blanked.len(), 1);
+        assert_eq!(blanked[0].character, "Realm/Bob");
+        assert_eq!(blanked[0].current, character("Bob"));
+        // A blanked file must not overwrite its good copy
+        assert_eq!(snapshot(&config), Ok(1));
+
+        assert_eq!(restore(&blanked), Ok(1));
+        assert_eq!(fs::read_to_string(character("Bob")).unwrap(), ENABLED);
+        assert!(find_blanked(&config).is_empty());
+        let _ = fs::remove_dir_all(&wtf);
+    }
+
+    #[test]
+    fn enabled_addons() {
+        let names = enabled(ENABLED);
+        assert_eq!(names.len(), 2);
+        assert!(names.contains("bagnon") && names.contains("dbm-core"));
+        assert!(enabled("Bagnon\nnot a line: maybe\n").is_empty());
+    }
+}
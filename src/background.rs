@@ -0,0 +1,144 @@
+//! The window's background image: which file, how it is faded and how it fills the window.
+//!
+//! Without a chosen file, a `background.png` (or .jpg/.jpeg/.webp) in the working directory,
+//! the settings directory or next to the exe is used, else the one built into the exe.
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
+
+// Embedded default background: compiled from `background.png` at the repository root.
+const DEFAULT_PNG: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/background.png"));
+
+/// How the image fills the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scaling {
+    /// Scaled to cover the whole window, the overflow cropped evenly.
+    #[default]
+    Cover,
+    /// Scaled to fit inside the window, centered.
+    Contain,
+    /// Repeated at its own size from the top left corner.
+    Tile,
+}
+
+impl Scaling {
+    pub const ALL: [Scaling; 3] = [Scaling::Cover, Scaling::Contain, Scaling::Tile];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cover => "Cover",
+            Self::Contain => "Contain",
+            Self::Tile => "Tile",
+        }
+    }
+}
+
+/// A loaded background: its texture and size in pixels.
+pub struct Background {
+    texture: egui::TextureHandle,
+    size: [usize; 2],
+}
+
+/// Decode `path` (the default image when empty), grayed if asked and scaled down to the
+/// largest texture the GPU takes, into a texture. Tiled images get a repeating texture. The
+/// second value names the file used, for the status line.
+pub fn load(
+    ctx: &egui::Context,
+    path: &str,
+    grayscale: bool,
+    scaling: Scaling,
+) -> Result<(Background, String), String> {
+    let (img, source) = if path.is_empty() {
+        match find_default() {
+            Some(found) => {
+                let img = image::open(&found).map_err(|e| format!("{}: {}", found.display(), e))?;
+                (img, found.display().to_string())
+            }
+            None => {
+                let img = image::load_from_memory(DEFAULT_PNG).map_err(|e| e.to_string())?;
+                (img, "the embedded default image".to_string())
+            }
+        }
+    } else {
+        let img = image::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        (img, path.to_string())
+    };
+    // Larger textures are rejected by the GPU backend; a camera photo or panorama easily is
+    let max_side = ctx.input(|i| i.max_texture_side) as u32;
+    let img = if img.width() > max_side || img.height() > max_side {
+        img.resize(max_side, max_side, image::imageops::FilterType::Triangle)
+    } else {
+        img
+    };
+    let img = img.to_rgba8();
+    let size = [img.width() as usize, img.height() as usize];
+    let mut pixels = img.into_vec();
+    if grayscale {
+        for chunk in pixels.chunks_exact_mut(4) {
+            // luminance per Rec. 601
+            let lum = (0.299 * chunk[0] as f32 + 0.587 * chunk[1] as f32 + 0.114 * chunk[2] as f32)
+                .round() as u8;
+            chunk[..3].fill(lum);
+        }
+    }
+    let options = if scaling == Scaling::Tile {
+        egui::TextureOptions::LINEAR_REPEAT
+    } else {
+        egui::TextureOptions::LINEAR
+    };
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
+    let texture = ctx.load_texture("background", color_image, options);
+    Ok((Background { texture, size }, source))
+}
+
+impl Background {
+    /// Draw the image into `rect` at `opacity` (0 to 1).
+    pub fn paint(&self, painter: &egui::Painter, rect: egui::Rect, scaling: Scaling, opacity: f32) {
+        let tint = egui::Color32::WHITE.gamma_multiply(opacity.clamp(0.0, 1.0));
+        let img = egui::vec2(self.size[0] as f32, self.size[1] as f32);
+        if img.x <= 0.0 || img.y <= 0.0 {
+            return;
+        }
+        let full = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        match scaling {
+            Scaling::Cover => {
+                // Scale so the image covers the rect; crop the overflow evenly
+                let scale = f32::max(rect.width() / img.x, rect.height() / img.y);
+                let visible = rect.size() / scale / img;
+                let min = (egui::vec2(1.0, 1.0) - visible) / 2.0;
+                let uv = egui::Rect::from_min_size(min.to_pos2(), visible);
+                painter.image(self.texture.id(), rect, uv, tint);
+            }
+            Scaling::Contain => {
+                let scale = f32::min(rect.width() / img.x, rect.height() / img.y);
+                let target = egui::Rect::from_center_size(rect.center(), img * scale);
+                painter.image(self.texture.id(), target, full, tint);
+            }
+            Scaling::Tile => {
+                // Points per image pixel are left to egui's scale, like any other image
+                let uv = egui::Rect::from_min_size(egui::Pos2::ZERO, rect.size() / img);
+                painter.image(self.texture.id(), rect, uv, tint);
+            }
+        }
+    }
+}
+
+/// A `background.*` file in the working directory, the settings directory or next to the exe.
+fn find_default() -> Option<PathBuf> {
+    let dirs = [
+        env::current_dir().ok(),
+        crate::settings_dir(),
+        env::current_exe()
+            .ok()
+            .and_then(|p| p.parent().map(Path::to_path_buf)),
+    ];
+    dirs.into_iter().flatten().find_map(|dir| {
+        ["png", "jpg", "jpeg", "webp"]
+            .iter()
+            .map(|ext| dir.join(format!("background.{}", ext)))
+            .find(|p| p.is_file())
+    })
+}
@@ -0,0 +1,411 @@
+//! "Backup enTitan": the whole settings directory (settings, profiles/snapshots and their
+//! notes, the event log) in one zip file, and restoring such a file.
+//!
+//! Only the subset of the zip format needed for that is implemented: deflated or stored
+//! entries, no zip64, no encryption.
+
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR: u32 = 0x0605_4b50;
+// Entry names are UTF-8 (general purpose flag bit 11)
+const FLAG_UTF8: u16 = 0x0800;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+const VERSION: u16 = 20;
+
+// Present in every backup; identifies an archive as one of ours
+const SETTINGS_FILE: &str = "settings.json";
+// An archive claiming more than this per entry or in total is refused before anything is
+// decompressed; settings directories are a few MB at most
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_TOTAL_BYTES: u64 = 256 * 1024 * 1024;
+
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+    time: u16,
+    date: u16,
+}
+
+/// A file to archive: relative name with `/` separators, contents and modification time
+/// in seconds since the Unix epoch.
+pub struct ZipFile {
+    pub name: String,
+    pub data: Vec<u8>,
+    pub modified: u64,
+}
+
+/// Write every file of the settings directory into the zip `out`. Returns the file count.
+pub fn create(out: &Path) -> Result<usize, String> {
+    let dir = crate::settings_dir().ok_or("cannot determine settings directory")?;
+    let mut names = Vec::new();
+    collect_files(&dir, &dir, &mut names).map_err(|e| e.to_string())?;
+    names.sort();
+    let files = names
+        .into_iter()
+        .map(|name| read_file(&dir, name))
+        .collect::<Result<Vec<_>, _>>()?;
+    write_zip(out, &files)
+}
+
+/// The file `name` below `dir`, for `write_zip`.
+pub fn read_file(dir: &Path, name: String) -> Result<ZipFile, String> {
+    let path = dir.join(&name);
+    let data = fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let modified = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs());
+    Ok(ZipFile {
+        name,
+        data,
+        modified,
+    })
+}
+
+/// Write `files` as the zip `out`. Returns the file count.
+pub fn write_zip(out: &Path, files: &[ZipFile]) -> Result<usize, String> {
+    let count = u16::try_from(files.len()).map_err(|_| "too many files for a zip file")?;
+    let mut zip = Vec::new();
+    let mut entries = Vec::new();
+    for file in files {
+        if u16::try_from(file.name.len()).is_err() {
+            return Err(format!("{}: name too long for a zip file", file.name));
+        }
+        let data = &file.data;
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).map_err(|e| e.to_string())?;
+        let compressed = encoder.finish().map_err(|e| e.to_string())?;
+        let (time, date) = dos_time(file.modified);
+        let entry = Entry {
+            name: file.name.clone(),
+            crc: crc32fast::hash(data),
+            compressed: u32_len(compressed.len())?,
+            size: u32_len(data.len())?,
+            offset: u32_len(zip.len())?,
+            time,
+            date,
+        };
+        put32(&mut zip, LOCAL_HEADER);
+        put16(&mut zip, VERSION);
+        put16(&mut zip, FLAG_UTF8);
+        put16(&mut zip, METHOD_DEFLATE);
+        put16(&mut zip, entry.time);
+        put16(&mut zip, entry.date);
+        put32(&mut zip, entry.crc);
+        put32(&mut zip, entry.compressed);
+        put32(&mut zip, entry.size);
+        put16(&mut zip, entry.name.len() as u16);
+        put16(&mut zip, 0);
+        zip.extend_from_slice(entry.name.as_bytes());
+        zip.extend_from_slice(&compressed);
+        entries.push(entry);
+    }
+
+    let central_start = u32_len(zip.len())?;
+    for entry in &entries {
+        put32(&mut zip, CENTRAL_HEADER);
+        put16(&mut zip, VERSION);
+        put16(&mut zip, VERSION);
+        put16(&mut zip, FLAG_UTF8);
+        put16(&mut zip, METHOD_DEFLATE);
+        put16(&mut zip, entry.time);
+        put16(&mut zip, entry.date);
+        put32(&mut zip, entry.crc);
+        put32(&mut zip, entry.compressed);
+        put32(&mut zip, entry.size);
+        put16(&mut zip, entry.name.len() as u16);
+        // extra field, comment, disk number, internal and external attributes
+        put16(&mut zip, 0);
+        put16(&mut zip, 0);
+        put16(&mut zip, 0);
+        put16(&mut zip, 0);
+        put32(&mut zip, 0);
+        put32(&mut zip, entry.offset);
+        zip.extend_from_slice(entry.name.as_bytes());
+    }
+    let central_size = u32_len(zip.len())? - central_start;
+    put32(&mut zip, END_OF_CENTRAL_DIR);
+    put16(&mut zip, 0);
+    put16(&mut zip, 0);
+    put16(&mut zip, count);
+    put16(&mut zip, count);
+    put32(&mut zip, central_size);
+    put32(&mut zip, central_start);
+    put16(&mut zip, 0);
+
+    fs::write(out, zip).map_err(|e| e.to_string())?;
+    Ok(entries.len())
+}
+
+/// Extract a backup made by `create` over the settings directory. The whole archive is
+/// checked before anything is written. Returns the file count.
+pub fn restore(archive: &Path) -> Result<usize, String> {
+    let files = read_zip(archive)?;
+    if !files.iter().any(|(p, _)| p == Path::new(SETTINGS_FILE)) {
+        return Err(format!("not an enTitan backup (no {})", SETTINGS_FILE));
+    }
+    write_files(&files)
+}
+
+/// Relative paths and contents of all entries of the zip `archive`, every one checked.
+pub fn read_zip(archive: &Path) -> Result<Vec<(PathBuf, Vec<u8>)>, String> {
+    let zip = fs::read(archive).map_err(|e| e.to_string())?;
+    let entries = read_central_dir(&zip)?;
+    let total: u64 = entries.iter().map(|e| u64::from(e.size)).sum();
+    if total > MAX_TOTAL_BYTES {
+        return Err("archive is too large to be an enTitan backup".into());
+    }
+    let mut files = Vec::new();
+    for entry in entries {
+        files.push((safe_path(&entry.name)?, read_entry(&zip, &entry)?));
+    }
+    Ok(files)
+}
+
+/// Write `files` below the settings directory. Returns the file count.
+pub fn write_files(files: &[(PathBuf, Vec<u8>)]) -> Result<usize, String> {
+    let dir = crate::settings_dir().ok_or("cannot determine settings directory")?;
+    for (rel, data) in files {
+        let path = dir.join(rel);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, data).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+    Ok(files.len())
+}
+
+/// Paths of all files below `dir`, relative to `root` with `/` separators.
+/// The lock file and the folder pointer (`location::is_local`) are left out.
+pub fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if path
+            .file_name()
+            .is_some_and(|n| !crate::location::is_local(n))
+            && let Ok(rel) = path.strip_prefix(root)
+        {
+            let parts: Vec<String> = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            files.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Relative path of an archive entry, refusing names that would escape the settings
+/// directory or replace the lock file.
+fn safe_path(name: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(name.replace('\\', "/"));
+    let normal = path.components().all(|c| matches!(c, Component::Normal(_)));
+    if !normal || name.is_empty() || path.file_name().is_some_and(crate::location::is_local) {
+        return Err(format!("refusing archive entry {:?}", name));
+    }
+    Ok(path)
+}
+
+fn read_central_dir(zip: &[u8]) -> Result<Vec<Entry>, String> {
+    const EOCD_LEN: usize = 22;
+    let corrupt = || "archive is damaged or not a zip file".to_string();
+    // The end record sits at the very end, before a comment of at most 64 KiB
+    let eocd = (0..=zip.len().saturating_sub(EOCD_LEN))
+        .rev()
+        .take(EOCD_LEN + u16::MAX as usize)
+        .find(|&i| get32(zip, i) == Some(END_OF_CENTRAL_DIR))
+        .ok_or_else(corrupt)?;
+    let count = get16(zip, eocd + 10).ok_or_else(corrupt)?;
+    let mut pos = get32(zip, eocd + 16).ok_or_else(corrupt)? as usize;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if get32(zip, pos) != Some(CENTRAL_HEADER) {
+            return Err(corrupt());
+        }
+        let field16 = |at: usize| get16(zip, pos + at).ok_or_else(corrupt);
+        let field32 = |at: usize| get32(zip, pos + at).ok_or_else(corrupt);
+        let name_len = field16(28)? as usize;
+        let skip = name_len + field16(30)? as usize + field16(32)? as usize;
+        let name = zip.get(pos + 46..pos + 46 + name_len).ok_or_else(corrupt)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        let method = field16(10)?;
+        if method != METHOD_STORED && method != METHOD_DEFLATE {
+            return Err(format!("{}: unsupported compression method", name));
+        }
+        entries.push(Entry {
+            name,
+            crc: field32(16)?,
+            compressed: field32(20)?,
+            size: field32(24)?,
+            offset: field32(42)?,
+            time: field16(12)?,
+            date: field16(14)?,
+        });
+        pos += 46 + skip;
+    }
+    Ok(entries)
+}
+
+fn read_entry(zip: &[u8], entry: &Entry) -> Result<Vec<u8>, String> {
+    let corrupt = || format!("{}: archive entry is damaged", entry.name);
+    if u64::from(entry.size) > MAX_ENTRY_BYTES {
+        return Err(format!("{}: archive entry is too large", entry.name));
+    }
+    let at = entry.offset as usize;
+    if get32(zip, at) != Some(LOCAL_HEADER) {
+        return Err(corrupt());
+    }
+    let method = get16(zip, at + 8).ok_or_else(corrupt)?;
+    let start = at
+        + 30
+        + get16(zip, at + 26).ok_or_else(corrupt)? as usize
+        + get16(zip, at + 28).ok_or_else(corrupt)? as usize;
+    let raw = zip
+        .get(start..start + entry.compressed as usize)
+        .ok_or_else(corrupt)?;
+    let data = if method == METHOD_DEFLATE {
+        let mut data = Vec::with_capacity(entry.size as usize);
+        // One byte past the claimed size is enough to tell the claim was wrong
+        DeflateDecoder::new(raw)
+            .take(u64::from(entry.size) + 1)
+            .read_to_end(&mut data)
+            .map_err(|_| corrupt())?;
+        data
+    } else {
+        raw.to_vec()
+    };
+    if data.len() != entry.size as usize || crc32fast::hash(&data) != entry.crc {
+        return Err(corrupt());
+    }
+    Ok(data)
+}
+
+/// Unix time `secs` as MS-DOS (time, date) in UTC.
+fn dos_time(secs: u64) -> (u16, u16) {
+    let (y, m, d) = crate::eventlog::civil_from_days((secs / 86_400) as i64);
+    // DOS dates start in 1980
+    if y < 1980 {
+        return (0, (1 << 5) | 1);
+    }
+    let rem = secs % 86_400;
+    let time = ((rem / 3600) << 11) | ((rem % 3600 / 60) << 5) | (rem % 60 / 2);
+    let date = (((y - 1980) as u64) << 9) | (u64::from(m) << 5) | u64::from(d);
+    (time as u16, date as u16)
+}
+
+fn u32_len(len: usize) -> Result<u32, String> {
+    u32::try_from(len).map_err(|_| "backup is too large for a zip file".to_string())
+}
+
+fn put16(buf: &mut Vec<u8>, v: u16) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn get16(buf: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(buf.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn get32(buf: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(buf.get(at..at + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("entitan-backup-{}-{}", std::process::id(), name))
+    }
+
+    fn file(name: &str, data: &[u8]) -> ZipFile {
+        ZipFile {
+            name: name.into(),
+            data: data.to_vec(),
+            modified: 1_760_000_000,
+        }
+    }
+
+    #[test]
+    fn write_and_read_round_trip() {
+        let path = temp_path("round-trip.zip");
+        let files = [
+            file(SETTINGS_FILE, b"{\"config\": \"C:/WoW/WTF/Config.wtf\"}"),
+            file(
+                "snapshots/Raid night.wtf",
+                &b"SET textLocale \"deDE\"\r\n".repeat(500),
+            ),
+            file("snapshots/ünïcode.txt", b""),
+        ];
+        assert_eq!(write_zip(&path, &files).unwrap(), 3);
+        let read = read_zip(&path).unwrap();
+        assert_eq!(read.len(), files.len());
+        for ((rel, data), file) in read.iter().zip(&files) {
+            assert_eq!(rel, &PathBuf::from(&file.name));
+            assert_eq!(data, &file.data);
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn write_refuses_what_the_format_cannot_hold() {
+        let path = temp_path("limits.zip");
+        let long = file(&"a".repeat(u16::MAX as usize + 1), b"");
+        assert!(write_zip(&path, &[long]).is_err());
+        let many: Vec<ZipFile> = (0..=u16::MAX as usize)
+            .map(|i| file(&i.to_string(), b""))
+            .collect();
+        assert!(write_zip(&path, &many).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn read_refuses_oversized_and_damaged_entries() {
+        let path = temp_path("damaged.zip");
+        write_zip(&path, &[file(SETTINGS_FILE, &[b'x'; 1000])]).unwrap();
+        let zip = fs::read(&path).unwrap();
+        let central = zip.len() - 22 - 46 - SETTINGS_FILE.len();
+        let patched = |at: usize, size: u32| {
+            let mut zip = zip.clone();
+            zip[central + at..central + at + 4].copy_from_slice(&size.to_le_bytes());
+            fs::write(&path, zip).unwrap();
+            read_zip(&path)
+        };
+        // Claimed sizes beyond the caps, and a claim smaller than the real contents
+        assert!(patched(24, u32::MAX).unwrap_err().contains("too large"));
+        assert!(patched(24, 10).unwrap_err().contains("damaged"));
+        assert!(patched(16, 0).unwrap_err().contains("damaged"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn entry_names_must_stay_inside_the_directory() {
+        assert!(safe_path("snapshots/a.wtf").is_ok());
+        for name in [
+            "",
+            "../settings.json",
+            "/etc/passwd",
+            "a/../../b",
+            "entitan.lock",
+        ] {
+            assert!(safe_path(name).is_err(), "{:?}", name);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Locale keys of WoW products in Battle.net's own `Battle.net.config` (JSON).
+//!
+//! Battle.net keeps a per-game language under `Games.<product>` and writes it back into
+//! Config.wtf on launch, which is what keeps resetting the locale. Every string field of a
+//! `wow*` product whose name contains "Language" is treated as a locale key: speech/audio
+//! ones follow the preferred audio locale, the others the preferred text locale.
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `%APPDATA%\Battle.net\Battle.net.config`.
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|d| {
+        PathBuf::from(d)
+            .join("Battle.net")
+            .join("Battle.net.config")
+    })
+}
+
+/// One locale key, e.g. `Games.wow_classic_era.SelectedTextLanguage = "deDE"`.
+pub struct LocaleEntry {
+    pub product: String,
+    pub key: String,
+    pub audio: bool,
+    pub value: String,
+}
+
+impl LocaleEntry {
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.product, self.key)
+    }
+}
+
+/// The locale keys currently in `path`.
+pub fn read(path: &Path) -> Result<Vec<LocaleEntry>, String> {
+    let root = load(path)?;
+    let mut entries = Vec::new();
+    visit(&root, |product, key, value| {
+        if let Value::String(value) = value {
+            entries.push(LocaleEntry {
+                product: product.to_string(),
+                key: key.to_string(),
+                audio: is_audio_key(key),
+                value: value.clone(),
+            });
+        }
+    });
+    Ok(entries)
+}
+
+/// Set every locale key in `path` to the preferred locale; returns how many changed.
+/// Battle.net rewrites this file while it runs, so it should be closed for this to stick.
+pub fn sync(path: &Path, audio: &str, text: &str) -> Result<usize, String> {
+    let mut root = load(path)?;
+    let mut changed = 0;
+    if let Some(Value::Object(games)) = root.get_mut("Games") {
+        for (product, game) in games.iter_mut() {
+            let Value::Object(fields) = game else {
+                continue;
+            };
+            if !is_wow_product(product) {
+                continue;
+            }
+            for (key, value) in fields.iter_mut() {
+                if !is_locale_key(key) || !value.is_string() {
+                    continue;
+                }
+                let wanted = if is_audio_key(key) { audio } else { text };
+                if value.as_str() != Some(wanted) {
+                    *value = Value::String(wanted.to_string());
+                    changed += 1;
+                }
+            }
+        }
+    }
+    if changed > 0 {
+        // Battle.net formats the file with 4-space indentation
+        let mut out = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        serde::Serialize::serialize(&root, &mut serializer).map_err(|e| e.to_string())?;
+        crate::wtf::write_atomic(path, &out).map_err(|e| e.to_string())?;
+    }
+    Ok(changed)
+}
+
+fn load(path: &Path) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+fn visit(root: &Value, mut f: impl FnMut(&str, &str, &Value)) {
+    let Some(Value::Object(games)) = root.get("Games") else {
+        return;
+    };
+    for (product, game) in games {
+        let Value::Object(fields) = game else {
+            continue;
+        };
+        if !is_wow_product(product) {
+            continue;
+        }
+        for (key, value) in fields {
+            if is_locale_key(key) {
+                f(product, key, value);
+            }
+        }
+    }
+}
+
+fn is_wow_product(product: &str) -> bool {
+    product.to_ascii_lowercase().starts_with("wow")
+}
+
+fn is_locale_key(key: &str) -> bool {
+    key.to_ascii_lowercase().contains("language")
+}
+
+fn is_audio_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("speech") || key.contains("audio")
+}
@@ -0,0 +1,169 @@
+//! Settings bundle: settings.json and the profiles (Config.wtf snapshots and their notes) in
+//! one zip, to move a setup to another PC or share it with guildmates.
+//!
+//! Unlike a backup it leaves out the run history, the window position and the recent paths,
+//! and importing one adds its profiles to the existing ones rather than replacing the
+//! settings directory. Settings that run programs, elevate or send data elsewhere never
+//! travel in a bundle: a shared file must not be able to install them unseen.
+
+use crate::backup::{self, ZipFile};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SETTINGS_FILE: &str = "settings.json";
+const SNAPSHOTS_DIR: &str = "snapshots";
+// Window placement and the recently used paths belong to the PC, not the setup; the window
+// language to the person
+const LOCAL_KEYS: &[&str] = &["geometry", "recentPaths", "uiLanguage"];
+// Hooks and custom steps run arbitrary commands, elevation runs them as administrator and the
+// webhook posts to any URL; these stay whatever this PC has
+const TRUSTED_KEYS: &[&str] = &[
+    "preLaunchHook",
+    "preLaunchHookCritical",
+    "postLaunchHook",
+    "postLaunchHookCritical",
+    "useCustomSequence",
+    "customSteps",
+    "battleNetElevated",
+    "wowElevated",
+    "deElevateChildren",
+    "broadcastEnabled",
+    "broadcastUrl",
+    "broadcastTemplate",
+];
+
+/// Write `settings` and every profile file into the zip `out`. Returns the profile count.
+pub fn export(out: &Path, settings: &Value) -> Result<usize, String> {
+    let mut settings = settings.clone();
+    if let Some(map) = settings.as_object_mut() {
+        for key in LOCAL_KEYS.iter().chain(TRUSTED_KEYS) {
+            map.remove(*key);
+        }
+    }
+    let mut files = vec![ZipFile {
+        name: SETTINGS_FILE.into(),
+        data: serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?,
+        modified: crate::eventlog::now(),
+    }];
+    let dir = crate::settings_dir().ok_or("cannot determine settings directory")?;
+    let mut names: Vec<String> = fs::read_dir(dir.join(SNAPSHOTS_DIR))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_file())
+                .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    for name in names {
+        files.push(backup::read_file(
+            &dir,
+            format!("{}/{}", SNAPSHOTS_DIR, name),
+        )?);
+    }
+    let profiles = crate::snapshots::list().len();
+    backup::write_zip(out, &files)?;
+    Ok(profiles)
+}
+
+/// Profile names in the bundle `archive`, checking that it is one.
+pub fn profiles(archive: &Path) -> Result<Vec<String>, String> {
+    let files = read(archive)?;
+    Ok(files
+        .iter()
+        .filter(|(p, _)| p.extension().is_some_and(|e| e == "wtf"))
+        .filter_map(|(p, _)| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect())
+}
+
+/// Replace settings.json with the bundle's, keeping this PC's window position, hooks, custom
+/// steps, elevation and webhook, and write its profiles over any of the same name. Returns
+/// the profile count.
+pub fn import(archive: &Path) -> Result<usize, String> {
+    let mut files = read(archive)?;
+    let current: Option<Value> = crate::settings_file_path()
+        .and_then(|p| fs::read(p).ok())
+        .and_then(|b| serde_json::from_slice(&b).ok());
+    for (path, data) in &mut files {
+        if path != Path::new(SETTINGS_FILE) {
+            continue;
+        }
+        let mut settings: Value = serde_json::from_slice(data).map_err(|e| e.to_string())?;
+        keep_local(&mut settings, current.as_ref());
+        *data = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+    }
+    let profiles = files
+        .iter()
+        .filter(|(p, _)| p.extension().is_some_and(|e| e == "wtf"))
+        .count();
+    backup::write_files(&files)?;
+    Ok(profiles)
+}
+
+/// Put this PC's values of the keys that don't travel in a bundle into the incoming
+/// `settings`, dropping those `current` doesn't have.
+fn keep_local(settings: &mut Value, current: Option<&Value>) {
+    let Some(map) = settings.as_object_mut() else {
+        return;
+    };
+    for key in LOCAL_KEYS.iter().chain(TRUSTED_KEYS) {
+        match current.and_then(|c| c.get(*key)) {
+            Some(value) => map.insert(key.to_string(), value.clone()),
+            None => map.remove(*key),
+        };
+    }
+}
+
+/// The settings and profile entries of a bundle (anything else, e.g. the run history of a
+/// backup opened by mistake, is left out). Fails unless settings.json is valid JSON.
+fn read(archive: &Path) -> Result<Vec<(PathBuf, Vec<u8>)>, String> {
+    let files: Vec<(PathBuf, Vec<u8>)> = backup::read_zip(archive)?
+        .into_iter()
+        .filter(|(p, _)| p == Path::new(SETTINGS_FILE) || p.starts_with(SNAPSHOTS_DIR))
+        .collect();
+    let settings = files
+        .iter()
+        .find(|(p, _)| p == Path::new(SETTINGS_FILE))
+        .ok_or_else(|| format!("not an enTitan settings bundle (no {})", SETTINGS_FILE))?;
+    if !serde_json::from_slice::<Value>(&settings.1).is_ok_and(|v| v.is_object()) {
+        return Err(format!("{} in the bundle is damaged", SETTINGS_FILE));
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn import_keeps_local_hooks_elevation_and_webhook() {
+        let mut incoming = json!({
+            "config": "D:/WoW/WTF/Config.wtf",
+            "preLaunchHook": "evil.bat",
+            "customSteps": [{"program": "evil.exe"}],
+            "wowElevated": true,
+            "broadcastUrl": "https://example.org/collect",
+            "uiLanguage": "de",
+        });
+        let current = json!({
+            "config": "C:/WoW/WTF/Config.wtf",
+            "preLaunchHook": "mine.bat",
+            "uiLanguage": "en",
+        });
+        keep_local(&mut incoming, Some(&current));
+        assert_eq!(
+            incoming,
+            json!({
+                "config": "D:/WoW/WTF/Config.wtf",
+                "preLaunchHook": "mine.bat",
+                "uiLanguage": "en",
+            })
+        );
+        let mut incoming = json!({"postLaunchHook": "evil.bat", "wowArgs": "-d3d11"});
+        keep_local(&mut incoming, None);
+        assert_eq!(incoming, json!({"wowArgs": "-d3d11"}));
+    }
+}
@@ -0,0 +1,108 @@
+//! Opt-in system clock drift check against an NTP server (SNTP, RFC 4330).
+//!
+//! Private-server authentication can fail when the local clock is skewed, so enTitan warns
+//! before a launch cycle is wasted.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Seconds between the NTP epoch (1900) and the Unix epoch (1970)
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+
+const NTP_PORT: u16 = 123;
+
+/// Query `server` on a background thread; the receiver yields the local clock's offset.
+pub fn check_async(server: String) -> Receiver<Result<f64, String>> {
+    crate::repaint::spawn(move || query_offset(&server))
+}
+
+/// Offset in seconds to add to the local clock to match `server` (positive: local is behind).
+pub fn query_offset(server: &str) -> Result<f64, String> {
+    let socket = connect(server)?;
+    socket
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .map_err(|e| e.to_string())?;
+
+    // LI = 0, version 4, mode 3 (client)
+    let mut request = [0u8; 48];
+    request[0] = 0x23;
+    let t1 = unix_now();
+    socket.send(&request).map_err(|e| e.to_string())?;
+    let mut response = [0u8; 48];
+    let len = socket.recv(&mut response).map_err(|e| e.to_string())?;
+    let t4 = unix_now();
+    if len < 48 {
+        return Err("short NTP response".into());
+    }
+    let t2 = timestamp(&response[32..40]);
+    let t3 = timestamp(&response[40..48]);
+    if t3 <= 0.0 {
+        return Err("NTP server sent no time".into());
+    }
+    Ok(((t2 - t1) + (t3 - t4)) / 2.0)
+}
+
+/// A UDP socket connected to `server` (`host`, `host:port`, an IPv4 or IPv6 address, or
+/// `[v6]:port`), bound to the family of the address it resolved to. Addresses are tried in
+/// turn, so a name whose IPv6 address this PC has no route to still works over IPv4.
+fn connect(server: &str) -> Result<UdpSocket, String> {
+    let resolved = match server.parse::<IpAddr>() {
+        Ok(ip) => Ok(vec![SocketAddr::new(ip, NTP_PORT)]),
+        Err(_) if server.contains(':') => server.to_socket_addrs().map(Iterator::collect),
+        Err(_) => (server, NTP_PORT).to_socket_addrs().map(Iterator::collect),
+    };
+    let addrs: Vec<SocketAddr> = resolved.map_err(|e| e.to_string())?;
+    let mut last_error = format!("{} did not resolve to an address", server);
+    for addr in addrs {
+        let local: SocketAddr = if addr.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        match UdpSocket::bind(local).and_then(|socket| socket.connect(addr).map(|()| socket)) {
+            Ok(socket) => return Ok(socket),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+/// NTP 64-bit timestamp (seconds + fraction since 1900) as Unix seconds.
+fn timestamp(bytes: &[u8]) -> f64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let frac = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if secs == 0 {
+        return 0.0;
+    }
+    f64::from(secs) + f64::from(frac) / 4_294_967_296.0 - NTP_UNIX_OFFSET
+}
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_binds_the_family_of_the_server() {
+        let v4 = connect("127.0.0.1").unwrap();
+        assert!(v4.local_addr().unwrap().is_ipv4());
+        assert_eq!(v4.peer_addr().unwrap().port(), NTP_PORT);
+        let v4 = connect("127.0.0.1:1123").unwrap();
+        assert_eq!(v4.peer_addr().unwrap().port(), 1123);
+        // Hosts without IPv6 can't bind it; nothing to check there
+        if UdpSocket::bind("[::1]:0").is_ok() {
+            for server in ["::1", "[::1]:1123"] {
+                let v6 = connect(server).unwrap();
+                assert!(v6.local_addr().unwrap().is_ipv6(), "{}", server);
+            }
+        }
+        assert!(connect("[::1").is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Export of the full cvar set of Config.wtf to JSON or TOML, and merging such a file back in.
+//!
+//! The format is picked by file extension; both are a flat `cvar = "value"` map.
+
+use crate::wtf::{self, WtfDocument};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Write every cvar of `config` to `out`; returns how many were written.
+pub fn export(config: &Path, out: &Path) -> Result<usize, String> {
+    // Later duplicates overwrite earlier ones, matching what WoW applies
+    let cvars: BTreeMap<String, String> = read_config(config)?.entries().into_iter().collect();
+    let text = if is_toml(out) {
+        toml::to_string(&cvars).map_err(|e| e.to_string())?
+    } else {
+        serde_json::to_string_pretty(&cvars).map_err(|e| e.to_string())?
+    };
+    fs::write(out, text).map_err(|e| e.to_string())?;
+    Ok(cvars.len())
+}
+
+/// How many of `cvars` differ from the values in `config`. Configs beyond the size limit
+/// are scanned rather than loaded.
+pub fn count_changes(config: &Path, cvars: &BTreeMap<String, String>) -> Result<usize, String> {
+    let keys: Vec<&str> = cvars.keys().map(String::as_str).collect();
+    let meta = config.metadata().map_err(|e| e.to_string())?;
+    let current = if meta.len() > crate::limits::get().max_config_bytes {
+        wtf::scan_values(config, &keys).map_err(|e| e.to_string())?
+    } else {
+        let doc = WtfDocument::parse(&fs::read(config).map_err(|e| e.to_string())?)?;
+        keys.iter().map(|key| doc.get(key)).collect()
+    };
+    Ok(current
+        .iter()
+        .zip(cvars.values())
+        .filter(|(current, value)| current.as_deref() != Some(value.as_str()))
+        .count())
+}
+
+fn read_config(config: &Path) -> Result<WtfDocument, String> {
+    let meta = config.metadata().map_err(|e| e.to_string())?;
+    if meta.len() > crate::limits::get().max_config_bytes {
+        return Err("Config.wtf is too large".into());
+    }
+    WtfDocument::parse(&fs::read(config).map_err(|e| e.to_string())?)
+}
+
+/// Read the flat cvar map of a JSON or TOML `file`; numbers and booleans are accepted and
+/// taken as their text.
+pub fn read(file: &Path) -> Result<BTreeMap<String, String>, String> {
+    let text = fs::read_to_string(file).map_err(|e| e.to_string())?;
+    let values: BTreeMap<String, Option<String>> = if is_toml(file) {
+        let map: BTreeMap<String, toml::Value> =
+            toml::from_str(&text).map_err(|e| e.to_string())?;
+        map.into_iter()
+            .map(|(k, v)| {
+                let v = match v {
+                    toml::Value::String(s) => Some(s),
+                    toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                        Some(v.to_string())
+                    }
+                    _ => None,
+                };
+                (k, v)
+            })
+            .collect()
+    } else {
+        let map: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        map.into_iter()
+            .map(|(k, v)| {
+                let v = match v {
+                    serde_json::Value::String(s) => Some(s),
+                    serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                        Some(v.to_string())
+                    }
+                    _ => None,
+                };
+                (k, v)
+            })
+            .collect()
+    };
+    values
+        .into_iter()
+        .map(|(key, value)| {
+            let value = value.ok_or_else(|| format!("{}: value must be text or a number", key))?;
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(format!("{:?} is not a valid cvar name", key));
+            }
+            if value.contains(['"', '\r', '\n']) {
+                return Err(format!(
+                    "{}: value cannot contain quotes or line breaks",
+                    key
+                ));
+            }
+            Ok((key, value))
+        })
+        .collect()
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("toml"))
+}
@@ -0,0 +1,134 @@
+//! Distributor defaults read from `defaults.toml` next to the executable.
+//!
+//! Server owners can ship enTitan inside their client pack with paths relative to the exe,
+//! e.g. `launcher = "..\\Battle.net\\Battle.net.exe"`. The defaults only seed the settings on
+//! first run (no `settings.json` yet); the user's own choices always win afterwards.
+//!
+//! An optional `[branding]` table customizes the window on every run:
+//!
+//! ```toml
+//! [branding]
+//! title = "Titan Reforged Launcher"
+//! icon = "branding/icon.png"     # relative to the exe
+//! accent_color = "#c08a2b"
+//! news_url = "https://example.org/news"
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const FILE_NAME: &str = "defaults.toml";
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct Defaults {
+    pub launcher: Option<String>,
+    pub config: Option<String>,
+    pub wow_executable: Option<String>,
+    pub preferred_audio_locale: Option<String>,
+    pub preferred_text_locale: Option<String>,
+    pub branding: Branding,
+}
+
+/// Distributor overrides for the window's look.
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct Branding {
+    pub title: Option<String>,
+    pub icon: Option<String>,
+    pub accent_color: Option<String>,
+    pub news_url: Option<String>,
+}
+
+impl Branding {
+    /// Load the window icon (any format the image crate reads), relative to the exe directory.
+    pub fn load_icon(&self) -> Option<eframe::egui::IconData> {
+        let path = exe_dir().unwrap_or_default().join(self.icon.as_ref()?);
+        match image::open(&path) {
+            Ok(img) => {
+                let img = img.to_rgba8();
+                Some(eframe::egui::IconData {
+                    width: img.width(),
+                    height: img.height(),
+                    rgba: img.into_raw(),
+                })
+            }
+            Err(e) => {
+                eprintln!("Failed to load branding icon {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Parsed `accent_color` ("#rrggbb" or "#rrggbbaa").
+    pub fn accent_color(&self) -> Option<eframe::egui::Color32> {
+        let hex = self.accent_color.as_deref()?;
+        match eframe::egui::Color32::from_hex(hex) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                eprintln!("Ignoring invalid branding accent_color {:?}: {:?}", hex, e);
+                None
+            }
+        }
+    }
+}
+
+impl Defaults {
+    /// Read `defaults.toml` from the executable's directory; None if absent or unreadable.
+    pub fn load() -> Option<Self> {
+        let path = exe_dir()?.join(FILE_NAME);
+        let contents = fs::read_to_string(&path).ok()?;
+        match toml::from_str(&contents) {
+            Ok(d) => Some(d),
+            Err(e) => {
+                eprintln!("Ignoring invalid {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Fill the first-run settings, resolving relative paths against the exe directory.
+    pub fn apply(&self, settings: &mut crate::SettingsFile) {
+        let base = exe_dir().unwrap_or_default();
+        let resolve = |p: &str| normalize(&base.join(p)).display().to_string();
+        if let Some(p) = &self.launcher {
+            settings.launcher = resolve(p);
+        }
+        if let Some(p) = &self.config {
+            settings.config = resolve(p);
+        }
+        if let Some(p) = &self.wow_executable {
+            settings.wow_executable = resolve(p);
+        }
+        if let Some(l) = &self.preferred_audio_locale {
+            settings.preferred_audio_locale = l.clone();
+        }
+        if let Some(l) = &self.preferred_text_locale {
+            settings.preferred_text_locale = l.clone();
+        }
+    }
+}
+
+fn exe_dir() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+}
+
+/// Resolve `.` and `..` lexically so pack-relative paths display cleanly.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push(component);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
@@ -0,0 +1,210 @@
+//! Persistent log of runs and status messages (`events.jsonl` next to `settings.json`),
+//! with CSV export for people tracking launch reliability across machines.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Only the most recent entries are kept in memory and loaded at startup.
+const MAX_ENTRIES: usize = 5000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// Run sequence progress: start, launches, failures, completion
+    Run,
+    /// Any other status message shown in the window
+    Status,
+}
+
+impl Kind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Status => "status",
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Entry {
+    /// Seconds since the Unix epoch
+    pub time: u64,
+    pub kind: Kind,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct EventLog {
+    entries: Vec<Entry>,
+}
+
+impl EventLog {
+    /// Read the persisted log, skipping lines that don't parse.
+    pub fn load() -> Self {
+        let mut entries: Vec<Entry> = log_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .map(|text| {
+                text.lines()
+                    .filter_map(|l| serde_json::from_str(l).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..excess);
+        Self { entries }
+    }
+
+    /// Record a message now and append it to the log file (best-effort).
+    pub fn push(&mut self, kind: Kind, message: impl Into<String>) {
+        let entry = Entry {
+            time: now(),
+            kind,
+            message: message.into(),
+        };
+        if let Some(path) = log_path() {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let (Ok(mut file), Ok(line)) = (
+                fs::OpenOptions::new().create(true).append(true).open(&path),
+                serde_json::to_string(&entry),
+            ) {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+/// Which columns a CSV export contains.
+pub struct CsvColumns {
+    pub time: bool,
+    pub kind: bool,
+    pub message: bool,
+}
+
+/// Write the entries with `from <= time < to` (either bound optional) as CSV to `out`.
+/// Returns the number of rows written.
+pub fn export_csv(
+    entries: &[Entry],
+    out: &Path,
+    columns: &CsvColumns,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> Result<usize, String> {
+    if !(columns.time || columns.kind || columns.message) {
+        return Err("select at least one column".into());
+    }
+    let mut csv = String::new();
+    let header: Vec<&str> = [
+        (columns.time, "time_utc"),
+        (columns.kind, "kind"),
+        (columns.message, "message"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect();
+    csv.push_str(&header.join(","));
+    csv.push_str("\r\n");
+    let mut rows = 0;
+    for entry in entries {
+        if from.is_some_and(|f| entry.time < f) || to.is_some_and(|t| entry.time >= t) {
+            continue;
+        }
+        let mut fields = Vec::new();
+        if columns.time {
+            fields.push(format_time(entry.time));
+        }
+        if columns.kind {
+            fields.push(entry.kind.as_str().to_string());
+        }
+        if columns.message {
+            fields.push(csv_field(&entry.message));
+        }
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+        rows += 1;
+    }
+    fs::write(out, csv).map_err(|e| e.to_string())?;
+    Ok(rows)
+}
+
+/// `YYYY-MM-DD HH:MM:SS` (UTC) for a Unix timestamp.
+pub fn format_time(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        y,
+        m,
+        d,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Unix timestamp of midnight UTC at the start of a `YYYY-MM-DD` date.
+pub fn parse_date(text: &str) -> Option<u64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: u32 = parts.next()?.parse().ok()?;
+    let d: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    u64::try_from(days_from_civil(y, m, d) * 86_400).ok()
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn log_path() -> Option<PathBuf> {
+    crate::settings_dir().map(|d| d.join("events.jsonl"))
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// Gregorian calendar conversions after Howard Hinnant's `days_from_civil`/`civil_from_days`
+pub fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = i64::from((m + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+pub fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
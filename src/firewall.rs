@@ -0,0 +1,85 @@
+//! Windows Defender Firewall allow rules for the launched executables.
+//!
+//! Rules are created with `netsh advfirewall` (one inbound and one outbound rule per program,
+//! named `enTitan - <name>`). Creating rules needs administrator rights, so unless enTitan is
+//! already elevated the commands run through a single UAC prompt.
+
+use std::sync::mpsc::Receiver;
+
+#[cfg(target_os = "windows")]
+const RULE_PREFIX: &str = "enTitan - ";
+
+/// Whether enTitan's rules exist for every `(name, path)` program. None where it can't be
+/// determined (not Windows, or netsh unavailable).
+#[cfg(target_os = "windows")]
+fn rules_exist(programs: &[(&str, &str)]) -> Option<bool> {
+    use std::os::windows::process::CommandExt;
+    // Keep netsh's console window from flashing up
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    for (name, _) in programs {
+        let status = std::process::Command::new("netsh")
+            .args(["advfirewall", "firewall", "show", "rule"])
+            .arg(format!("name={}{}", RULE_PREFIX, name))
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?
+            .status;
+        if !status.success() {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn rules_exist(_programs: &[(&str, &str)]) -> Option<bool> {
+    None
+}
+
+/// `rules_exist` on a background thread (netsh takes a moment, and more when busy).
+pub fn rules_exist_async(programs: Vec<(String, String)>) -> Receiver<Option<bool>> {
+    crate::repaint::spawn(move || {
+        let programs: Vec<(&str, &str)> = programs
+            .iter()
+            .map(|(n, p)| (n.as_str(), p.as_str()))
+            .collect();
+        rules_exist(&programs)
+    })
+}
+
+/// Replace enTitan's rules for `(name, path)` programs on a background thread (the UAC
+/// prompt blocks until answered).
+pub fn allow_async(programs: Vec<(String, String)>) -> Receiver<Result<(), String>> {
+    crate::repaint::spawn(move || allow(&programs))
+}
+
+/// The `cmd /S /C` command line deleting and re-adding the rules, so repeated use doesn't
+/// pile up duplicates.
+#[cfg(target_os = "windows")]
+fn commands(programs: &[(String, String)]) -> String {
+    let mut cmds = Vec::new();
+    for (name, path) in programs {
+        let rule = format!("name=\"{}{}\"", RULE_PREFIX, name);
+        // A missing rule makes `delete` fail; `&` (not `&&`) carries on regardless
+        cmds.push(format!("netsh advfirewall firewall delete rule {}", rule));
+        for dir in ["in", "out"] {
+            cmds.push(format!(
+                "netsh advfirewall firewall add rule {} dir={} action=allow program=\"{}\" enable=yes",
+                rule, dir, path
+            ));
+        }
+    }
+    format!("/S /C \"{}\"", cmds.join(" & "))
+}
+
+#[cfg(target_os = "windows")]
+fn allow(programs: &[(String, String)]) -> Result<(), String> {
+    crate::launch::run_elevated("cmd.exe", &commands(programs))
+        .map_err(|e| e.replace("cmd.exe", "netsh"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn allow(_programs: &[(String, String)]) -> Result<(), String> {
+    Err("firewall rules can only be created on Windows".into())
+}
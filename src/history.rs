@@ -0,0 +1,131 @@
+//! Undo/redo for the edits enTitan makes to Config.wtf, kept in memory for the session.
+
+use std::path::{Path, PathBuf};
+
+/// Oldest edits are forgotten beyond this many.
+const MAX_EDITS: usize = 50;
+
+struct Edit {
+    path: PathBuf,
+    label: String,
+    before: Vec<u8>,
+    after: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl History {
+    /// Remember that `path` was rewritten from `before` to `after`. A new edit discards
+    /// everything that could have been redone.
+    pub fn record(
+        &mut self,
+        path: &Path,
+        label: impl Into<String>,
+        before: Vec<u8>,
+        after: Vec<u8>,
+    ) {
+        if before == after {
+            return;
+        }
+        self.undo.push(Edit {
+            path: path.to_path_buf(),
+            label: label.into(),
+            before,
+            after,
+        });
+        if self.undo.len() > MAX_EDITS {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|e| e.label.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|e| e.label.as_str())
+    }
+
+    /// Restore the file contents from before the most recent edit; returns its label.
+    /// `write(path, expected, contents)` does the write, `expected` being what the file
+    /// should hold right now.
+    pub fn undo(
+        &mut self,
+        write: impl FnOnce(&Path, &[u8], &[u8]) -> Result<(), String>,
+    ) -> Result<String, String> {
+        let edit = self.undo.pop().ok_or("nothing to undo")?;
+        match write(&edit.path, &edit.after, &edit.before) {
+            Ok(()) => {
+                let label = edit.label.clone();
+                self.redo.push(edit);
+                Ok(label)
+            }
+            Err(e) => {
+                self.undo.push(edit);
+                Err(e)
+            }
+        }
+    }
+
+    /// Re-apply the most recently undone edit; returns its label. See `undo` for `write`.
+    pub fn redo(
+        &mut self,
+        write: impl FnOnce(&Path, &[u8], &[u8]) -> Result<(), String>,
+    ) -> Result<String, String> {
+        let edit = self.redo.pop().ok_or("nothing to redo")?;
+        match write(&edit.path, &edit.before, &edit.after) {
+            Ok(()) => {
+                let label = edit.label.clone();
+                self.undo.push(edit);
+                Ok(label)
+            }
+            Err(e) => {
+                self.redo.push(edit);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_pass_expected_and_new_contents() {
+        let mut history = History::default();
+        let path = Path::new("Config.wtf");
+        history.record(path, "unchanged", b"a".to_vec(), b"a".to_vec());
+        assert_eq!(history.undo_label(), None);
+        history.record(path, "set b", b"a".to_vec(), b"b".to_vec());
+
+        let mut written = Vec::new();
+        let label = history.undo(|p, expected, contents| {
+            written.push((p.to_path_buf(), expected.to_vec(), contents.to_vec()));
+            Ok(())
+        });
+        assert_eq!(label.unwrap(), "set b");
+        assert_eq!(
+            written,
+            [(path.to_path_buf(), b"b".to_vec(), b"a".to_vec())]
+        );
+        assert_eq!(history.redo_label(), Some("set b"));
+
+        // A refused write keeps the edit where it was
+        assert!(history.redo(|_, _, _| Err("refused".into())).is_err());
+        assert_eq!(history.redo_label(), Some("set b"));
+        history
+            .redo(|_, expected, contents| {
+                assert_eq!((expected, contents), (&b"a"[..], &b"b"[..]));
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(history.undo_label(), Some("set b"));
+        assert_eq!(history.redo_label(), None);
+    }
+}
@@ -0,0 +1,334 @@
+//! Realm hostname diagnostics: private servers often ask players to add a hosts file entry
+//! redirecting the realm hostname, and a stale entry pointing at a dead server looks like a
+//! plain connection failure in the game.
+
+use std::fs;
+use std::io;
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+/// Port the WoW client connects to when `realmList` doesn't name one.
+pub const DEFAULT_PORT: u16 = 3724;
+
+// Prefix of hosts lines disabled by the guided fix
+const DISABLED_MARKER: &str = "# disabled by enTitan: ";
+
+// Copy of the hosts file from before the guided fix, in enTitan's settings folder
+const HOSTS_BACKUP: &str = "hosts.bak";
+
+/// Outcome of `diagnose`: one Ok/Err line per check (like `entitan validate`) and the
+/// hosts file lines (1-based) redirecting to an unreachable server.
+pub struct Diagnosis {
+    pub results: Vec<Result<String, String>>,
+    pub stale: Vec<usize>,
+}
+
+pub fn hosts_path() -> PathBuf {
+    if cfg!(target_os = "windows") {
+        let root = std::env::var_os("SystemRoot").unwrap_or_else(|| "C:\\Windows".into());
+        PathBuf::from(root).join("System32\\drivers\\etc\\hosts")
+    } else {
+        PathBuf::from("/etc/hosts")
+    }
+}
+
+/// Host and port of a `realmList` value: `logon.example.org`, `logon.example.org:3725` or a
+/// full `set realmlist logon.example.org` line pasted from a server's instructions.
+pub fn parse_realm_list(value: &str) -> Option<(String, u16)> {
+    let mut words = value.split_whitespace();
+    let mut first = words.next()?;
+    if first.eq_ignore_ascii_case("set") {
+        words.next();
+        first = words.next()?;
+    }
+    match first.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((first.to_string(), DEFAULT_PORT)),
+    }
+}
+
+/// Hosts file entries for `host`, as (1-based line number, address).
+fn entries_for(contents: &str, host: &str) -> Vec<(usize, IpAddr)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.split('#').next().unwrap_or("");
+            let mut words = line.split_whitespace();
+            let ip = words.next()?.parse().ok()?;
+            words
+                .any(|name| name.eq_ignore_ascii_case(host))
+                .then_some((i + 1, ip))
+        })
+        .collect()
+}
+
+/// Run `diagnose` on a background thread (name resolution and connects can take seconds).
+pub fn diagnose_async(host: String, port: u16) -> Receiver<Diagnosis> {
+    crate::repaint::spawn(move || diagnose(&host, port))
+}
+
+/// Compare how `host` resolves with the hosts file and check the server answers on `port`.
+pub fn diagnose(host: &str, port: u16) -> Diagnosis {
+    let mut results = Vec::new();
+    let mut stale = Vec::new();
+    let path = hosts_path();
+    let entries = match fs::read_to_string(&path) {
+        Ok(contents) => entries_for(&contents, host),
+        Err(e) => {
+            results.push(Err(format!("cannot read {}: {}", path.display(), e)));
+            Vec::new()
+        }
+    };
+    let resolved: Result<Vec<IpAddr>, String> = (host, port)
+        .to_socket_addrs()
+        .map(|addrs| addrs.map(|a| a.ip()).collect())
+        .map_err(|e| e.to_string());
+
+    let mut ips: Vec<IpAddr> = entries.iter().map(|(_, ip)| *ip).collect();
+    ips.sort();
+    ips.dedup();
+    if ips.len() > 1 {
+        let lines: Vec<String> = entries.iter().map(|(l, _)| l.to_string()).collect();
+        results.push(Err(format!(
+            "the hosts file redirects {} to {} different addresses (lines {})",
+            host,
+            ips.len(),
+            lines.join(", ")
+        )));
+    }
+
+    for (line, ip) in &entries {
+        results.push(Ok(format!(
+            "hosts line {} redirects {} to {}",
+            line, host, ip
+        )));
+        if let Ok(addrs) = &resolved
+            && !addrs.contains(ip)
+        {
+            results.push(Err(format!(
+                "{} resolves to {} instead of the hosts entry; flush the DNS cache (ipconfig /flushdns)",
+                host,
+                join(addrs)
+            )));
+        }
+        match TcpStream::connect_timeout(
+            &(*ip, port).into(),
+            crate::limits::get().connect_timeout(),
+        ) {
+            Ok(_) => results.push(Ok(format!("{}:{} answers", ip, port))),
+            Err(e) => {
+                results.push(Err(format!(
+                    "{}:{} does not answer ({}); the hosts entry on line {} may point to a server that no longer exists",
+                    ip, port, e, line
+                )));
+                stale.push(*line);
+            }
+        }
+    }
+
+    if entries.is_empty() {
+        match &resolved {
+            Ok(addrs) if !addrs.is_empty() => {
+                results.push(Ok(format!("{} resolves to {}", host, join(addrs))));
+                match TcpStream::connect_timeout(
+                    &(addrs[0], port).into(),
+                    crate::limits::get().connect_timeout(),
+                ) {
+                    Ok(_) => results.push(Ok(format!("{}:{} answers", addrs[0], port))),
+                    Err(e) => results.push(Err(format!(
+                        "{}:{} does not answer ({})",
+                        addrs[0], port, e
+                    ))),
+                }
+            }
+            Ok(_) => results.push(Err(format!("{} has no addresses", host))),
+            Err(e) => results.push(Err(format!(
+                "{} does not resolve ({}); the server may require a hosts file entry",
+                host, e
+            ))),
+        }
+    }
+    Diagnosis { results, stale }
+}
+
+/// Round-trip estimate to `host:port`: the time a TCP connect takes (unlike ICMP ping this
+/// needs no privileges and goes through the same firewall/VPN path as the game).
+pub fn measure_latency(host: &str, port: u16) -> Result<Duration, String> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} has no addresses", host))?;
+    let start = std::time::Instant::now();
+    TcpStream::connect_timeout(&addr, crate::limits::get().connect_timeout())
+        .map_err(|e| e.to_string())?;
+    Ok(start.elapsed())
+}
+
+fn join(addrs: &[IpAddr]) -> String {
+    addrs
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Comment out hosts file `lines` (1-based) on a background thread; writing the hosts file
+/// needs administrator rights, so this may show the UAC prompt. Returns where the previous
+/// file was saved.
+pub fn disable_lines_async(lines: Vec<usize>) -> Receiver<Result<PathBuf, String>> {
+    crate::repaint::spawn(move || {
+        let backup = crate::location::current()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(HOSTS_BACKUP);
+        disable_lines(&hosts_path(), &backup, &lines).map(|()| backup)
+    })
+}
+
+/// Copy `path` to `backup`, then replace it with `lines` commented out in one step, so a
+/// failure halfway can't leave a truncated hosts file.
+fn disable_lines(path: &Path, backup: &Path, lines: &[usize]) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if let Some(dir) = backup.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(backup, &contents)
+        .map_err(|e| format!("could not save a backup to {}: {}", backup.display(), e))?;
+    let updated = comment_out(&contents, lines);
+    match crate::wtf::write_atomic(path, updated.as_bytes()) {
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => write_elevated(path, &updated),
+        other => other.map_err(|e| e.to_string()),
+    }
+}
+
+/// `contents` with `lines` (1-based) prefixed by `DISABLED_MARKER`.
+fn comment_out(contents: &str, lines: &[usize]) -> String {
+    // split_inclusive keeps each line's own ending (hosts files are usually CRLF)
+    contents
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if lines.contains(&(i + 1)) {
+                format!("{}{}", DISABLED_MARKER, line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Put `contents` in place of `path` from an elevated `cmd`: copied next to it first, then
+/// renamed over it like `write_atomic` does.
+#[cfg(target_os = "windows")]
+fn write_elevated(path: &Path, contents: &str) -> Result<(), String> {
+    let temp = std::env::temp_dir().join("entitan-hosts.tmp");
+    fs::write(&temp, contents).map_err(|e| e.to_string())?;
+    let next_to = path.with_file_name("hosts.entitan-tmp");
+    let result = crate::launch::run_elevated(
+        "cmd.exe",
+        &format!(
+            "/S /C \"copy /Y \"{}\" \"{}\" && move /Y \"{}\" \"{}\"\"",
+            temp.display(),
+            next_to.display(),
+            next_to.display(),
+            path.display()
+        ),
+    );
+    let _ = fs::remove_file(&temp);
+    result
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_elevated(path: &Path, _contents: &str) -> Result<(), String> {
+    Err(format!("no permission to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSTS: &str = "# Copyright (c) 1993-2009 Microsoft Corp.\r\n\
+        127.0.0.1       localhost\r\n\
+        \r\n\
+        203.0.113.7     logon.example.org   # private server\r\n\
+        # 198.51.100.1  logon.example.org\r\n\
+        198.51.100.2\tother.example.org LOGON.EXAMPLE.ORG\r\n\
+        ::1             localhost\r\n";
+
+    #[test]
+    fn realm_list_forms() {
+        let host = |h: &str, p| Some((h.to_string(), p));
+        assert_eq!(
+            parse_realm_list("logon.example.org"),
+            host("logon.example.org", DEFAULT_PORT)
+        );
+        assert_eq!(
+            parse_realm_list("logon.example.org:3725"),
+            host("logon.example.org", 3725)
+        );
+        assert_eq!(
+            parse_realm_list("  SET realmlist logon.example.org  "),
+            host("logon.example.org", DEFAULT_PORT)
+        );
+        assert_eq!(
+            parse_realm_list("set realmlist logon.example.org:8085"),
+            host("logon.example.org", 8085)
+        );
+        assert_eq!(parse_realm_list(""), None);
+        assert_eq!(parse_realm_list("set realmlist"), None);
+        assert_eq!(parse_realm_list("logon.example.org:port"), None);
+    }
+
+    #[test]
+    fn entries_skip_comments_and_match_any_alias() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert_eq!(
+            entries_for(HOSTS, "logon.example.org"),
+            vec![(4, ip("203.0.113.7")), (6, ip("198.51.100.2"))]
+        );
+        assert_eq!(
+            entries_for(HOSTS, "localhost"),
+            vec![(2, ip("127.0.0.1")), (7, ip("::1"))]
+        );
+        assert_eq!(entries_for(HOSTS, "example.org"), vec![]);
+    }
+
+    #[test]
+    fn comment_out_keeps_line_endings() {
+        let updated = comment_out(HOSTS, &[4, 6]);
+        let lines: Vec<&str> = updated.split_inclusive('\n').collect();
+        assert_eq!(lines.len(), HOSTS.split_inclusive('\n').count());
+        assert_eq!(
+            lines[3],
+            format!(
+                "{}203.0.113.7     logon.example.org   # private server\r\n",
+                DISABLED_MARKER
+            )
+        );
+        assert!(lines[5].starts_with(DISABLED_MARKER));
+        assert!(entries_for(&updated, "logon.example.org").is_empty());
+        assert_eq!(comment_out(HOSTS, &[]), HOSTS);
+    }
+
+    #[test]
+    fn disable_lines_keeps_a_backup() {
+        let dir = std::env::temp_dir().join(format!("entitan-hosts-{}-backup", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hosts");
+        let backup = dir.join("settings").join(HOSTS_BACKUP);
+        fs::write(&path, HOSTS).unwrap();
+
+        disable_lines(&path, &backup, &[4]).unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), HOSTS);
+        assert_eq!(fs::read_to_string(&path).unwrap(), comment_out(HOSTS, &[4]));
+        // Nothing but the file and the backup folder is left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        assert!(disable_lines(&dir.join("missing"), &backup, &[4]).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,315 @@
+//! Languages of enTitan's own window (not the game's): labels, buttons and section titles.
+//!
+//! Text is looked up by its English wording, so anything without a translation (hover
+//! texts, status messages) stays English.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Language {
+    /// The Windows display language when it is one of the others, else English.
+    #[default]
+    #[serde(rename = "system")]
+    System,
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "ru")]
+    Russian,
+}
+
+impl Language {
+    pub const ALL: [Language; 7] = [
+        Language::System,
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Portuguese,
+        Language::Russian,
+    ];
+
+    /// The language's name for itself.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::System => "Follow Windows",
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::French => "Français",
+            Self::Spanish => "Español",
+            Self::Portuguese => "Português",
+            Self::Russian => "Русский",
+        }
+    }
+
+    /// Column of the language in `TRANSLATIONS`; System is resolved first.
+    fn column(self) -> usize {
+        match self {
+            Self::System => crate::locales::from_os()
+                .and_then(|code| {
+                    Self::ALL[2..]
+                        .iter()
+                        .find(|l| code.starts_with(l.code()))
+                        .map(|l| l.column())
+                })
+                .unwrap_or(0),
+            Self::English => 0,
+            Self::German => 1,
+            Self::French => 2,
+            Self::Spanish => 3,
+            Self::Portuguese => 4,
+            Self::Russian => 5,
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::System => "",
+            Self::English => "en",
+            Self::German => "de",
+            Self::French => "fr",
+            Self::Spanish => "es",
+            Self::Portuguese => "pt",
+            Self::Russian => "ru",
+        }
+    }
+}
+
+// Language last passed to `set` (its position in `Language::ALL`) and the column it uses
+static CHOSEN: AtomicU8 = AtomicU8::new(u8::MAX);
+static COLUMN: AtomicU8 = AtomicU8::new(0);
+
+/// Show the window in `language` from the next `tr` on.
+pub fn set(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|l| *l == language)
+        .unwrap_or(0) as u8;
+    if CHOSEN.swap(index, Ordering::Relaxed) != index {
+        COLUMN.store(language.column() as u8, Ordering::Relaxed);
+    }
+}
+
+/// `text` in the current language, or unchanged when it has no translation.
+pub fn tr(text: &'static str) -> &'static str {
+    let column = COLUMN.load(Ordering::Relaxed) as usize;
+    if column == 0 {
+        return text;
+    }
+    TRANSLATIONS
+        .iter()
+        .find(|row| row[0] == text)
+        .map_or(text, |row| row[column])
+}
+
+// English, German, French, Spanish, Portuguese (Brazil), Russian; one row per text
+#[rustfmt::skip]
+const TRANSLATIONS: &[[&str; 6]] = &[
+    ["(after restart)", "(nach Neustart)", "(après redémarrage)", "(tras reiniciar)", "(após reiniciar)", "(после перезапуска)"],
+    ["(enTitan is running elevated)", "(enTitan läuft mit Adminrechten)", "(enTitan s'exécute en administrateur)", "(enTitan se ejecuta como administrador)", "(enTitan está em execução como administrador)", "(enTitan запущен с правами администратора)"],
+    ["(none)", "(keiner)", "(aucune)", "(ninguno)", "(nenhum)", "(нет)"],
+    ["(not available)", "(nicht verfügbar)", "(non disponible)", "(no disponible)", "(não disponível)", "(недоступно)"],
+    ["Abort the launch", "Start abbrechen", "Annuler le lancement", "Cancelar el inicio", "Cancelar a inicialização", "Отменить запуск"],
+    ["Add step", "Schritt hinzufügen", "Ajouter une étape", "Añadir paso", "Adicionar etapa", "Добавить шаг"],
+    ["Addons:", "Addons:", "Addons :", "Addons:", "Addons:", "Аддоны:"],
+    ["After run:", "Nach dem Ablauf:", "Après la séquence :", "Después de la secuencia:", "Depois da sequência:", "После запуска:"],
+    ["All", "Alle", "Tous", "Todos", "Todos", "Все"],
+    ["All cores", "Alle Kerne", "Tous les cœurs", "Todos los núcleos", "Todos os núcleos", "Все ядра"],
+    ["Allow Battle.net and WoW…", "Battle.net und WoW zulassen…", "Autoriser Battle.net et WoW…", "Permitir Battle.net y WoW…", "Permitir Battle.net e WoW…", "Разрешить Battle.net и WoW…"],
+    ["Also update Battle.net.config", "Auch Battle.net.config aktualisieren", "Mettre aussi à jour Battle.net.config", "Actualizar también Battle.net.config", "Atualizar também o Battle.net.config", "Также обновлять Battle.net.config"],
+    ["and Battle.net shows its window", "und Battle.net sein Fenster zeigt", "et que Battle.net affiche sa fenêtre", "y Battle.net muestre su ventana", "e o Battle.net mostrar sua janela", "и Battle.net показал своё окно"],
+    ["Appearance", "Darstellung", "Apparence", "Apariencia", "Aparência", "Оформление"],
+    ["Apply", "Anwenden", "Appliquer", "Aplicar", "Aplicar", "Применить"],
+    ["Arguments:", "Argumente:", "Arguments :", "Argumentos:", "Argumentos:", "Аргументы:"],
+    ["Arm", "Aktivieren", "Armer", "Activar", "Ativar", "Включить"],
+    ["Background image:", "Hintergrundbild:", "Image de fond :", "Imagen de fondo:", "Imagem de fundo:", "Фоновое изображение:"],
+    ["Background tint:", "Hintergrundtönung:", "Teinte de fond :", "Tinte de fondo:", "Tom do fundo:", "Оттенок фона:"],
+    ["Backup enTitan…", "enTitan sichern…", "Sauvegarder enTitan…", "Copia de seguridad de enTitan…", "Fazer backup do enTitan…", "Резервная копия enTitan…"],
+    ["Backup:", "Sicherung:", "Sauvegarde :", "Copia de seguridad:", "Backup:", "Резервная копия:"],
+    ["Before Battle.net relaunch", "Vor Battle.net-Neustart", "Avant la relance de Battle.net", "Antes de reiniciar Battle.net", "Antes de reabrir o Battle.net", "Перед перезапуском Battle.net"],
+    ["Before run:", "Vor dem Ablauf:", "Avant la séquence :", "Antes de la secuencia:", "Antes da sequência:", "Перед запуском:"],
+    ["Before WoW", "Vor WoW", "Avant WoW", "Antes de WoW", "Antes do WoW", "Перед WoW"],
+    ["Before WoW:", "Vor WoW:", "Avant WoW :", "Antes de WoW:", "Antes do WoW:", "Перед WoW:"],
+    ["Browse", "Durchsuchen", "Parcourir", "Examinar", "Procurar", "Обзор"],
+    ["Cancel", "Abbrechen", "Annuler", "Cancelar", "Cancelar", "Отмена"],
+    ["Change…", "Ändern…", "Modifier…", "Cambiar…", "Alterar…", "Изменить…"],
+    ["Check against", "Abgleichen mit", "Comparer avec", "Comparar con", "Comparar com", "Сверять с"],
+    ["check failed", "Prüfung fehlgeschlagen", "échec de la vérification", "comprobación fallida", "falha na verificação", "проверка не удалась"],
+    ["Check hosts/DNS", "hosts/DNS prüfen", "Vérifier hosts/DNS", "Comprobar hosts/DNS", "Verificar hosts/DNS", "Проверить hosts/DNS"],
+    ["Check now", "Jetzt prüfen", "Vérifier maintenant", "Comprobar ahora", "Verificar agora", "Проверить сейчас"],
+    ["Check the locale the client loaded when WoW exits", "Beim Beenden von WoW die geladene Sprache prüfen", "Vérifier la langue chargée par le client à la fermeture de WoW", "Comprobar el idioma que cargó el cliente al cerrar WoW", "Verificar o idioma carregado pelo cliente ao fechar o WoW", "Проверять язык, загруженный клиентом, при выходе из WoW"],
+    ["Check the realm before launching; limit", "Realm vor dem Start prüfen; Grenze", "Tester le royaume avant le lancement ; limite", "Comprobar el reino antes de iniciar; límite", "Verificar o reino antes de iniciar; limite", "Проверять мир перед запуском; предел"],
+    ["Clear", "Leeren", "Effacer", "Borrar", "Limpar", "Очистить"],
+    ["Clear flag, update, restore", "Attribut entfernen, aktualisieren, wiederherstellen", "Retirer l'attribut, mettre à jour, rétablir", "Quitar atributo, actualizar, restaurar", "Remover atributo, atualizar, restaurar", "Снять атрибут, обновить, вернуть"],
+    ["Clock:", "Uhr:", "Horloge :", "Reloj:", "Relógio:", "Часы:"],
+    ["Close", "Schließen", "Fermer", "Cerrar", "Fechar", "Закрыть"],
+    ["Close Battle.net instead", "Stattdessen Battle.net schließen", "Fermer Battle.net à la place", "Cerrar Battle.net en su lugar", "Fechar o Battle.net em vez disso", "Вместо этого закрыть Battle.net"],
+    ["Config.wtf is read-only", "Config.wtf ist schreibgeschützt", "Config.wtf est en lecture seule", "Config.wtf es de solo lectura", "Config.wtf é somente leitura", "Config.wtf только для чтения"],
+    ["Contain", "Einpassen", "Contenir", "Contener", "Conter", "Вписать"],
+    ["Continue", "Fortsetzen", "Continuer", "Continuar", "Continuar", "Продолжить"],
+    ["Cores", "Kerne", "Cœurs", "Núcleos", "Núcleos", "Ядра"],
+    ["Cover", "Füllen", "Couvrir", "Cubrir", "Cobrir", "Заполнить"],
+    ["Critical", "Kritisch", "Critique", "Crítico", "Crítico", "Критично"],
+    ["Cvars (JSON/TOML)", "Cvars (JSON/TOML)", "Cvars (JSON/TOML)", "Cvars (JSON/TOML)", "Cvars (JSON/TOML)", "Cvars (JSON/TOML)"],
+    ["Dark", "Dunkel", "Sombre", "Oscuro", "Escuro", "Тёмная"],
+    ["Default", "Standard", "Par défaut", "Predeterminado", "Padrão", "По умолчанию"],
+    ["Delays:", "Verzögerungen:", "Délais :", "Retrasos:", "Atrasos:", "Задержки:"],
+    ["Delete", "Löschen", "Supprimer", "Eliminar", "Excluir", "Удалить"],
+    ["Disable animations", "Animationen deaktivieren", "Désactiver les animations", "Desactivar animaciones", "Desativar animações", "Отключить анимацию"],
+    ["Disarm", "Deaktivieren", "Désarmer", "Desactivar", "Desativar", "Выключить"],
+    ["Dismiss", "Ausblenden", "Ignorer", "Descartar", "Dispensar", "Скрыть"],
+    ["Don't start Battle.net or WoW again if already running", "Battle.net oder WoW nicht erneut starten, wenn sie schon laufen", "Ne pas relancer Battle.net ou WoW s'ils sont déjà ouverts", "No volver a iniciar Battle.net o WoW si ya se están ejecutando", "Não iniciar o Battle.net ou o WoW de novo se já estiverem abertos", "Не запускать Battle.net или WoW повторно, если они уже запущены"],
+    ["Draw over the image", "Über das Bild zeichnen", "Dessiner sur l'image", "Dibujar sobre la imagen", "Desenhar sobre a imagem", "Поверх изображения"],
+    ["Duplicates:", "Duplikate:", "Doublons :", "Duplicados:", "Duplicados:", "Дубликаты:"],
+    ["Elevation:", "Adminrechte:", "Élévation :", "Elevación:", "Elevação:", "Права:"],
+    ["Enforce:", "Erzwingen:", "Imposer :", "Forzar:", "Forçar:", "Закрепить:"],
+    ["Environment:", "Umgebung:", "Environnement :", "Entorno:", "Ambiente:", "Окружение:"],
+    ["Event log", "Ereignisprotokoll", "Journal des événements", "Registro de eventos", "Registro de eventos", "Журнал событий"],
+    ["Export settings…", "Einstellungen exportieren…", "Exporter les paramètres…", "Exportar ajustes…", "Exportar configurações…", "Экспорт настроек…"],
+    ["Export:", "Export:", "Exporter :", "Exportar:", "Exportar:", "Экспорт:"],
+    ["Firewall:", "Firewall:", "Pare-feu :", "Firewall:", "Firewall:", "Брандмауэр:"],
+    ["Folder…", "Ordner…", "Dossier…", "Carpeta…", "Pasta…", "Папка…"],
+    ["Follow Windows", "Wie Windows", "Comme Windows", "Como Windows", "Como o Windows", "Как в Windows"],
+    ["Graphics presets", "Grafikvorlagen", "Préréglages graphiques", "Ajustes gráficos", "Predefinições gráficas", "Графические пресеты"],
+    ["Grayscale", "Graustufen", "Niveaux de gris", "Escala de grises", "Tons de cinza", "Оттенки серого"],
+    ["Guild broadcast", "Gildenmeldung", "Annonce de guilde", "Anuncio de hermandad", "Anúncio da guilda", "Оповещение гильдии"],
+    ["High contrast", "Hoher Kontrast", "Contraste élevé", "Alto contraste", "Alto contraste", "Высокая контрастность"],
+    ["History:", "Verlauf:", "Historique :", "Historial:", "Histórico:", "История:"],
+    ["Hold to confirm writes", "Schreiben durch Halten bestätigen", "Maintenir pour confirmer les écritures", "Mantener pulsado para confirmar escrituras", "Segurar para confirmar gravações", "Удерживать для подтверждения записи"],
+    ["Import cvars…", "Cvars importieren…", "Importer des cvars…", "Importar cvars…", "Importar cvars…", "Импорт cvars…"],
+    ["Import other launcher…", "Anderen Launcher importieren…", "Importer un autre lanceur…", "Importar otro lanzador…", "Importar outro launcher…", "Импорт из другого лаунчера…"],
+    ["Import settings…", "Einstellungen importieren…", "Importer les paramètres…", "Importar ajustes…", "Importar configurações…", "Импорт настроек…"],
+    ["Installation:", "Installation:", "Installation :", "Instalación:", "Instalação:", "Установка:"],
+    ["Keep Config.wtf on the preferred locales", "Config.wtf auf den bevorzugten Sprachen halten", "Garder Config.wtf sur les langues préférées", "Mantener Config.wtf en los idiomas preferidos", "Manter o Config.wtf nos idiomas preferidos", "Держать в Config.wtf предпочтительные языки"],
+    ["Language:", "Sprache:", "Langue :", "Idioma:", "Idioma:", "Язык:"],
+    ["Latency:", "Latenz:", "Latence :", "Latencia:", "Latência:", "Задержка:"],
+    ["Leave", "Verlassen", "Quitter", "Salir", "Sair", "Выйти"],
+    ["Light", "Hell", "Clair", "Claro", "Claro", "Светлая"],
+    ["Launch Sequence", "Startablauf", "Séquence de lancement", "Secuencia de inicio", "Sequência de início", "Порядок запуска"],
+    ["Live reload:", "Live-Neuladen:", "Rechargement auto :", "Recarga automática:", "Recarga automática:", "Автоперезагрузка:"],
+    ["Locale & Config", "Sprache & Config", "Langue et config", "Idioma y config", "Idioma e config", "Язык и конфиг"],
+    ["Locale report (JSON)", "Sprachbericht (JSON)", "Rapport de langue (JSON)", "Informe de idioma (JSON)", "Relatório de idioma (JSON)", "Отчёт о языке (JSON)"],
+    ["Log/History", "Protokoll/Verlauf", "Journal/Historique", "Registro/Historial", "Registro/Histórico", "Журнал/История"],
+    ["Login:", "Anmeldung:", "Connexion :", "Inicio de sesión:", "Login:", "Вход:"],
+    ["No snapshots saved yet", "Noch keine Schnappschüsse gespeichert", "Aucun instantané enregistré", "Aún no hay instantáneas", "Nenhum snapshot salvo ainda", "Снимков пока нет"],
+    ["Notes:", "Notizen:", "Notes :", "Notas:", "Notas:", "Заметки:"],
+    ["Opacity:", "Deckkraft:", "Opacité :", "Opacidad:", "Opacidade:", "Непрозрачность:"],
+    ["Pause after starting Battle.net until I press Continue", "Nach dem Start von Battle.net warten, bis ich Fortsetzen drücke", "Attendre après le démarrage de Battle.net que j'appuie sur Continuer", "Pausar tras iniciar Battle.net hasta que pulse Continuar", "Pausar após iniciar o Battle.net até eu clicar em Continuar", "Пауза после запуска Battle.net, пока я не нажму «Продолжить»"],
+    ["Pause autoscroll", "Autoscroll anhalten", "Suspendre le défilement", "Pausar desplazamiento", "Pausar rolagem", "Остановить прокрутку"],
+    ["Paths", "Pfade", "Chemins", "Rutas", "Caminhos", "Пути"],
+    ["Player:", "Spieler:", "Joueur :", "Jugador:", "Jogador:", "Игрок:"],
+    ["Preferred Audio:", "Bevorzugter Ton:", "Audio préféré :", "Audio preferido:", "Áudio preferido:", "Язык озвучки:"],
+    ["Preferred Text:", "Bevorzugter Text:", "Texte préféré :", "Texto preferido:", "Texto preferido:", "Язык текста:"],
+    ["Priority", "Priorität", "Priorité", "Prioridad", "Prioridade", "Приоритет"],
+    ["Profiles (Config.wtf snapshots)", "Profile (Config.wtf-Schnappschüsse)", "Profils (instantanés de Config.wtf)", "Perfiles (instantáneas de Config.wtf)", "Perfis (snapshots do Config.wtf)", "Профили (снимки Config.wtf)"],
+    ["Read-only", "Schreibgeschützt", "Lecture seule", "Solo lectura", "Somente leitura", "Только чтение"],
+    ["Realm:", "Realm:", "Royaume :", "Reino:", "Reino:", "Игровой мир:"],
+    ["Redo", "Wiederholen", "Rétablir", "Rehacer", "Refazer", "Повторить"],
+    ["Relaunch:", "Neustart:", "Relance :", "Reinicio:", "Reabrir:", "Перезапуск:"],
+    ["Remove", "Entfernen", "Retirer", "Quitar", "Remover", "Убрать"],
+    ["Renderer:", "Renderer:", "Moteur de rendu :", "Renderizador:", "Renderizador:", "Рендерер:"],
+    ["Reset to defaults…", "Auf Standard zurücksetzen…", "Réinitialiser…", "Restablecer valores…", "Restaurar padrões…", "Сбросить настройки…"],
+    ["Restore", "Wiederherstellen", "Restaurer", "Restaurar", "Restaurar", "Восстановить"],
+    ["Restore original files", "Originaldateien wiederherstellen", "Restaurer les fichiers d'origine", "Restaurar archivos originales", "Restaurar arquivos originais", "Вернуть исходные файлы"],
+    ["Restore…", "Wiederherstellen…", "Restaurer…", "Restaurar…", "Restaurar…", "Восстановить…"],
+    ["Run", "Starten", "Lancer", "Ejecutar", "Executar", "Запуск"],
+    ["Run Battle.net as administrator", "Battle.net als Administrator ausführen", "Exécuter Battle.net en administrateur", "Ejecutar Battle.net como administrador", "Executar o Battle.net como administrador", "Запускать Battle.net от имени администратора"],
+    ["Run sequence", "Startablauf", "Séquence de lancement", "Secuencia de inicio", "Sequência de início", "Порядок запуска"],
+    ["Run WoW as administrator", "WoW als Administrator ausführen", "Exécuter WoW en administrateur", "Ejecutar WoW como administrador", "Executar o WoW como administrador", "Запускать WoW от имени администратора"],
+    ["Running game:", "Laufendes Spiel:", "Jeu en cours :", "Juego en ejecución:", "Jogo em execução:", "Запущенная игра:"],
+    ["Save", "Speichern", "Enregistrer", "Guardar", "Salvar", "Сохранить"],
+    ["Save AddOns.txt before writing the locale", "AddOns.txt vor dem Schreiben der Sprache sichern", "Sauvegarder AddOns.txt avant d'écrire la langue", "Guardar AddOns.txt antes de escribir el idioma", "Salvar o AddOns.txt antes de gravar o idioma", "Сохранять AddOns.txt перед записью языка"],
+    ["Save snapshot", "Schnappschuss speichern", "Enregistrer l'instantané", "Guardar instantánea", "Salvar snapshot", "Сохранить снимок"],
+    ["Scaling:", "Skalierung:", "Mise à l'échelle :", "Escalado:", "Escala:", "Масштабирование:"],
+    ["Schedule:", "Zeitplan:", "Planification :", "Programación:", "Agendamento:", "Расписание:"],
+    ["Screenshots:", "Screenshots:", "Captures d'écran :", "Capturas:", "Capturas de tela:", "Скриншоты:"],
+    ["Send test", "Test senden", "Envoyer un test", "Enviar prueba", "Enviar teste", "Отправить тест"],
+    ["Settings folder:", "Einstellungsordner:", "Dossier des paramètres :", "Carpeta de ajustes:", "Pasta de configurações:", "Папка настроек:"],
+    ["Share settings:", "Einstellungen teilen:", "Partager les paramètres :", "Compartir ajustes:", "Compartilhar configurações:", "Поделиться настройками:"],
+    ["Software rendering", "Software-Rendering", "Rendu logiciel", "Renderizado por software", "Renderização por software", "Программный рендеринг"],
+    ["Sort new screenshots into per-locale folders", "Neue Screenshots in Ordner je Sprache sortieren", "Trier les nouvelles captures par dossier de langue", "Ordenar las capturas nuevas en carpetas por idioma", "Organizar novas capturas em pastas por idioma", "Раскладывать новые скриншоты по папкам языков"],
+    ["Start games without admin rights", "Spiele ohne Adminrechte starten", "Lancer les jeux sans droits d'administrateur", "Iniciar los juegos sin derechos de administrador", "Iniciar os jogos sem direitos de administrador", "Запускать игры без прав администратора"],
+    ["Start over:", "Neu beginnen:", "Repartir de zéro :", "Empezar de nuevo:", "Recomeçar:", "Начать заново:"],
+    ["Start the delays once Battle.net / WoW are running", "Verzögerungen erst ab laufendem Battle.net / WoW zählen", "Démarrer les délais une fois Battle.net / WoW lancés", "Empezar las esperas cuando Battle.net / WoW estén en ejecución", "Iniciar as esperas quando o Battle.net / WoW estiverem abertos", "Отсчитывать задержки после запуска Battle.net / WoW"],
+    ["Test", "Testen", "Tester", "Probar", "Testar", "Тест"],
+    ["Text size:", "Textgröße:", "Taille du texte :", "Tamaño del texto:", "Tamanho do texto:", "Размер текста:"],
+    ["Theme:", "Design:", "Thème :", "Tema:", "Tema:", "Тема:"],
+    ["Tile", "Kacheln", "Mosaïque", "Mosaico", "Lado a lado", "Плитка"],
+    ["Undo", "Rückgängig", "Annuler", "Deshacer", "Desfazer", "Отменить"],
+    ["Unlink", "Trennen", "Dissocier", "Desvincular", "Desvincular", "Отвязать"],
+    ["Update", "Aktualisieren", "Mettre à jour", "Actualizar", "Atualizar", "Обновить"],
+    ["Use default", "Standard verwenden", "Par défaut", "Usar predeterminada", "Usar padrão", "По умолчанию"],
+    ["Verify:", "Prüfen:", "Vérifier :", "Verificar:", "Verificar:", "Проверка:"],
+    ["Viewer mode:", "Ansichtsmodus:", "Mode lecture :", "Modo visor:", "Modo visualização:", "Режим просмотра:"],
+    ["Wait until idle for", "Auf Leerlauf warten von", "Attendre une inactivité de", "Esperar inactividad de", "Esperar inatividade de", "Ждать бездействия"],
+    ["WoW closed right after launching", "WoW wurde direkt nach dem Start beendet", "WoW s'est fermé juste après le lancement", "WoW se cerró justo después de iniciarse", "O WoW fechou logo após iniciar", "WoW закрылся сразу после запуска"],
+    ["WoW CPU:", "WoW-CPU:", "CPU de WoW :", "CPU de WoW:", "CPU do WoW:", "ЦП WoW:"],
+    ["WoW Executable:", "WoW-Programm:", "Exécutable WoW :", "Ejecutable de WoW:", "Executável do WoW:", "Исполняемый файл WoW:"],
+    ["Write the preferred locale to Config.wtf right before starting WoW", "Bevorzugte Sprache direkt vor dem WoW-Start in Config.wtf schreiben", "Écrire la langue préférée dans Config.wtf juste avant de lancer WoW", "Escribir el idioma preferido en Config.wtf justo antes de iniciar WoW", "Gravar o idioma preferido no Config.wtf logo antes de iniciar o WoW", "Записывать предпочтительный язык в Config.wtf прямо перед запуском WoW"],
+    ["● Unsaved changes", "● Ungespeicherte Änderungen", "● Modifications non enregistrées", "● Cambios sin guardar", "● Alterações não salvas", "● Несохранённые изменения"],
+    ["✔ active", "✔ aktiv", "✔ actif", "✔ activo", "✔ ativo", "✔ активно"],
+    ["✖ inactive", "✖ inaktiv", "✖ inactif", "✖ inactivo", "✖ inativo", "✖ неактивно"],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    // Literal keys of the `tr` calls in the files under `dir`
+    fn tr_keys(dir: &Path, keys: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                tr_keys(&path, keys);
+                continue;
+            }
+            if path.extension().is_none_or(|e| e != "rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            for (at, _) in source.match_indices("tr(\"") {
+                let before = source[..at].chars().next_back();
+                if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let rest = &source[at + 4..];
+                keys.push(rest[..rest.find('"').unwrap()].to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn every_tr_key_has_a_row() {
+        let mut keys = Vec::new();
+        tr_keys(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut keys,
+        );
+        assert!(keys.len() > 100);
+        let missing: Vec<_> = keys
+            .iter()
+            .filter(|k| !TRANSLATIONS.iter().any(|row| row[0] == k.as_str()))
+            .collect();
+        assert!(missing.is_empty(), "no translation for {:?}", missing);
+    }
+
+    #[test]
+    fn rows_are_complete_and_unique() {
+        for (i, row) in TRANSLATIONS.iter().enumerate() {
+            assert!(row.iter().all(|text| !text.is_empty()), "{:?}", row);
+            assert!(
+                TRANSLATIONS[..i].iter().all(|other| other[0] != row[0]),
+                "duplicate row {:?}",
+                row[0]
+            );
+        }
+    }
+}
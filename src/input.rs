@@ -0,0 +1,52 @@
+//! Keyboard shortcuts of the main window: the bindings, their text for hover hints, and
+//! reading them from a frame's input.
+
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Start the run sequence (Ctrl+R).
+    Run,
+    /// Write the preferred locales to Config.wtf (Ctrl+U).
+    UpdateLocale,
+    /// Save the settings (Ctrl+S).
+    Save,
+    /// Cancel the running sequence (Esc).
+    Cancel,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [
+        Action::Run,
+        Action::UpdateLocale,
+        Action::Save,
+        Action::Cancel,
+    ];
+
+    fn shortcut(self) -> KeyboardShortcut {
+        match self {
+            Self::Run => KeyboardShortcut::new(Modifiers::COMMAND, Key::R),
+            Self::UpdateLocale => KeyboardShortcut::new(Modifiers::COMMAND, Key::U),
+            Self::Save => KeyboardShortcut::new(Modifiers::COMMAND, Key::S),
+            Self::Cancel => KeyboardShortcut::new(Modifiers::NONE, Key::Escape),
+        }
+    }
+
+    /// `text` followed by the binding, e.g. "Save the settings (Ctrl+S)", for a hover text.
+    pub fn hint(self, ctx: &egui::Context, text: &str) -> String {
+        format!("{} ({})", text, ctx.format_shortcut(&self.shortcut()))
+    }
+}
+
+/// The actions among `enabled` whose shortcut was pressed this frame. Their key presses are
+/// consumed, so a focused widget doesn't act on them too; those of other actions are left
+/// alone (Esc still closes a drop-down when no run is active).
+pub fn pressed(ctx: &egui::Context, enabled: &[Action]) -> Vec<Action> {
+    ctx.input_mut(|i| {
+        enabled
+            .iter()
+            .copied()
+            .filter(|action| i.consume_shortcut(&action.shortcut()))
+            .collect()
+    })
+}
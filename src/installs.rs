@@ -0,0 +1,38 @@
+//! Named installations: the paths, preferred locales and delays of one WoW install (e.g. a
+//! live and a test client), switched from the drop-down at the top of the window.
+//!
+//! The active installation's values are the ordinary settings fields; the list keeps a copy
+//! of each one, refreshed from those fields whenever settings are saved.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Installation {
+    pub name: String,
+    pub launcher: String,
+    pub config: String,
+    pub wow_executable: String,
+    pub preferred_audio_locale: String,
+    pub preferred_text_locale: String,
+    pub wow_launch_delay_secs: u64,
+    pub relaunch_delay_secs: u64,
+}
+
+/// Index of the installation called `name` (case-insensitive).
+pub fn find(list: &[Installation], name: &str) -> Option<usize> {
+    list.iter()
+        .position(|i| i.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// `name` trimmed, if it is usable for a new installation: not empty and not taken.
+pub fn check_new_name(list: &[Installation], name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Enter a name for the installation".into());
+    }
+    if find(list, name).is_some() {
+        return Err(format!("An installation named {:?} already exists", name));
+    }
+    Ok(name.to_string())
+}
@@ -0,0 +1,633 @@
+//! Spawning of the launched executables (Battle.net, WoW).
+
+use std::io::{self, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// How often a running hook command is checked for its exit, timeout and cancellation.
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Debug, Default)]
+pub struct LaunchOptions {
+    /// Priority class set right after the process started.
+    pub priority: Priority,
+    /// CPU affinity mask set right after the process started; 0 leaves all CPUs allowed.
+    pub affinity: u64,
+    /// When enTitan itself runs elevated, start the child with the desktop shell's
+    /// (non-elevated) token so it doesn't inherit admin rights.
+    pub de_elevate: bool,
+    /// Start the child as administrator (UAC prompt). Takes precedence over `de_elevate`.
+    pub elevate: bool,
+    /// Environment variables not passed on to the child: names, or prefixes ending in `*`
+    /// (`OPENSSL_*`), matched without regard to case. Not applied to elevated launches,
+    /// which get enTitan's environment through the shell.
+    pub env_remove: Vec<String>,
+}
+
+/// Default `env_remove` patterns: TLS library overrides and overlay/layer hooks that
+/// overlays and injectors pick up from an inherited environment.
+pub const DEFAULT_ENV_REMOVE: &str = "OPENSSL_* SSL_CERT_* VK_INSTANCE_LAYERS VK_LAYER_PATH ENABLE_VK_LAYER_* SteamOverlayGameId SteamGameId SteamAppId __COMPAT_LAYER";
+
+/// Patterns of a space or comma separated list like `DEFAULT_ENV_REMOVE`.
+pub fn split_env_patterns(list: &str) -> Vec<String> {
+    list.split([' ', ',', ';'])
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Whether the variable `name` matches one of `patterns` (see `LaunchOptions::env_remove`).
+fn env_removed(name: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|p| match p.strip_suffix('*') {
+        Some(prefix) => name
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix)),
+        None => name.eq_ignore_ascii_case(p),
+    })
+}
+
+/// `Command` for `path` without the variables of `env_remove`.
+fn command(path: &str, args: &[String], env_remove: &[String]) -> Command {
+    let mut command = Command::new(path);
+    command.args(args);
+    for (name, _) in std::env::vars_os() {
+        if env_removed(&name.to_string_lossy(), env_remove) {
+            command.env_remove(name);
+        }
+    }
+    command
+}
+
+/// Process priority class for a launched program (Windows only).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    BelowNormal,
+    #[default]
+    Normal,
+    AboveNormal,
+    High,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::BelowNormal,
+        Priority::Normal,
+        Priority::AboveNormal,
+        Priority::High,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::BelowNormal => "Below normal",
+            Self::Normal => "Normal",
+            Self::AboveNormal => "Above normal",
+            Self::High => "High",
+        }
+    }
+}
+
+/// A started process, kept so its exit can be noticed and reported.
+pub struct Child {
+    pid: u32,
+    inner: ChildInner,
+}
+
+enum ChildInner {
+    Std(std::process::Child),
+    // Process handle of a child started through the Win32 APIs directly
+    #[cfg(target_os = "windows")]
+    Handle(OwnedHandle),
+}
+
+impl Child {
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// `Some(exit code)` once the process has exited (the code is None where it can't be
+    /// read, e.g. killed by a signal), None while it still runs.
+    pub fn exited(&mut self) -> Option<Option<i32>> {
+        match &mut self.inner {
+            ChildInner::Std(child) => match child.try_wait() {
+                Ok(Some(status)) => Some(status.code()),
+                Ok(None) => None,
+                Err(_) => Some(None),
+            },
+            #[cfg(target_os = "windows")]
+            ChildInner::Handle(handle) => {
+                use windows_sys::Win32::Foundation::WAIT_TIMEOUT;
+                use windows_sys::Win32::System::Threading::{
+                    GetExitCodeProcess, WaitForSingleObject,
+                };
+                if unsafe { WaitForSingleObject(handle.0, 0) } == WAIT_TIMEOUT {
+                    return None;
+                }
+                let mut code = 0u32;
+                let ok = unsafe { GetExitCodeProcess(handle.0, &mut code) } != 0;
+                Some(ok.then_some(code as i32))
+            }
+        }
+    }
+}
+
+impl From<std::process::Child> for Child {
+    fn from(child: std::process::Child) -> Self {
+        Self {
+            pid: child.id(),
+            inner: ChildInner::Std(child),
+        }
+    }
+}
+
+/// Start `path` with `args` and the given options. With `elevate` this blocks until the UAC
+/// prompt is answered; `spawn_async` keeps the window responsive meanwhile.
+pub fn spawn(path: &str, args: &[String], options: &LaunchOptions) -> io::Result<Child> {
+    if options.elevate {
+        return spawn_elevated(path, args);
+    }
+    if options.de_elevate && is_elevated() {
+        return spawn_de_elevated(path, args, &options.env_remove);
+    }
+    command(path, args, &options.env_remove)
+        .spawn()
+        .map(Child::from)
+}
+
+/// `spawn` on a background thread; the receiver yields the started program.
+pub fn spawn_async(
+    path: String,
+    args: Vec<String>,
+    options: LaunchOptions,
+) -> Receiver<io::Result<Child>> {
+    crate::repaint::spawn(move || spawn(&path, &args, &options))
+}
+
+/// `with exit code …` for the exit report; Windows crash codes (NTSTATUS) read best in hex.
+pub fn describe_exit(code: Option<i32>) -> String {
+    match code {
+        Some(code) if code as u32 > 0xFFFF => format!("with exit code {:#010X}", code as u32),
+        Some(code) => format!("with exit code {}", code),
+        None => "without an exit code".into(),
+    }
+}
+
+/// Run a user-configured hook `command` through the shell (`cmd /C` on Windows) and wait for
+/// it. Returns its trimmed output, or an error including the output when it fails. The shell
+/// is killed when it runs longer than `timeout` or once the sending half of `running` is
+/// dropped (the run was cancelled).
+pub fn run_hook(
+    command: &str,
+    timeout: Duration,
+    running: &Receiver<()>,
+) -> Result<String, String> {
+    #[cfg(target_os = "windows")]
+    let mut shell = {
+        use std::os::windows::process::CommandExt;
+        // Keep the console window from flashing up
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        let mut shell = Command::new("cmd");
+        shell
+            .arg("/C")
+            .raw_arg(command)
+            .creation_flags(CREATE_NO_WINDOW);
+        shell
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+
+    let mut child = shell
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    // Read both pipes while waiting, so a chatty command can't fill one and stall
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        let cancelled = matches!(running.try_recv(), Err(TryRecvError::Disconnected));
+        if cancelled || started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(if cancelled {
+                "cancelled".into()
+            } else {
+                format!("timed out after {} s", timeout.as_secs())
+            });
+        }
+        std::thread::sleep(HOOK_POLL_INTERVAL);
+    };
+    // A program the command started in the background may hold the pipes open
+    let rest = timeout.saturating_sub(started.elapsed());
+    let text = format!(
+        "{}{}",
+        stdout.recv_timeout(rest).unwrap_or_default(),
+        stderr.recv_timeout(rest).unwrap_or_default()
+    )
+    .trim()
+    .to_string();
+    if status.success() {
+        Ok(text)
+    } else if text.is_empty() {
+        Err(status.to_string())
+    } else {
+        Err(format!("{}: {}", status, text))
+    }
+}
+
+/// The text of `pipe` once it is closed, read on a background thread.
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        let _ = tx.send(String::from_utf8_lossy(&bytes).into_owned());
+    });
+    rx
+}
+
+/// Split a user-entered argument string on whitespace. Double quotes group words
+/// (`--exec "say hi"`) and `\"` is a literal quote.
+pub fn split_args(s: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    // Whether `current` is an argument even if empty (`""`)
+    let mut started = false;
+    let mut quoted = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => {
+                quoted = !quoted;
+                started = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if started {
+                    args.push(std::mem::take(&mut current));
+                    started = false;
+                }
+            }
+            c => {
+                current.push(c);
+                started = true;
+            }
+        }
+    }
+    if started {
+        args.push(current);
+    }
+    args
+}
+
+/// Whether enTitan itself is running with an elevated (admin) token (Windows only).
+#[cfg(target_os = "windows")]
+pub fn is_elevated() -> bool {
+    use windows_sys::Win32::Security::{
+        GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation,
+    };
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let mut token = std::ptr::null_mut();
+    if unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) } == 0 {
+        return false;
+    }
+    let token = OwnedHandle(token);
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut len = 0u32;
+    let ok = unsafe {
+        GetTokenInformation(
+            token.0,
+            TokenElevation,
+            &mut elevation as *mut TOKEN_ELEVATION as *mut core::ffi::c_void,
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut len,
+        )
+    };
+    ok != 0 && elevation.TokenIsElevated != 0
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+/// Start `path` with a primary token duplicated from the desktop shell (Explorer), which runs
+/// with the user's normal, non-elevated rights.
+#[cfg(target_os = "windows")]
+fn spawn_de_elevated(path: &str, args: &[String], env_remove: &[String]) -> io::Result<Child> {
+    use windows_sys::Win32::Security::{
+        DuplicateTokenEx, SecurityImpersonation, TOKEN_ADJUST_DEFAULT, TOKEN_ADJUST_SESSIONID,
+        TOKEN_ASSIGN_PRIMARY, TOKEN_DUPLICATE, TOKEN_QUERY, TokenPrimary,
+    };
+    use windows_sys::Win32::System::Threading::{
+        CREATE_UNICODE_ENVIRONMENT, CreateProcessWithTokenW, OpenProcess, OpenProcessToken,
+        PROCESS_INFORMATION, PROCESS_QUERY_INFORMATION, STARTUPINFOW,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetShellWindow, GetWindowThreadProcessId};
+
+    let shell = unsafe { GetShellWindow() };
+    if shell.is_null() {
+        return Err(io::Error::other(
+            "no desktop shell window to take a non-elevated token from",
+        ));
+    }
+    let mut shell_pid = 0u32;
+    unsafe { GetWindowThreadProcessId(shell, &mut shell_pid) };
+    if shell_pid == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let process = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION, 0, shell_pid) };
+    if process.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let process = OwnedHandle(process);
+
+    let mut shell_token = std::ptr::null_mut();
+    if unsafe { OpenProcessToken(process.0, TOKEN_DUPLICATE, &mut shell_token) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let shell_token = OwnedHandle(shell_token);
+
+    let mut token = std::ptr::null_mut();
+    let access = TOKEN_QUERY
+        | TOKEN_ASSIGN_PRIMARY
+        | TOKEN_DUPLICATE
+        | TOKEN_ADJUST_DEFAULT
+        | TOKEN_ADJUST_SESSIONID;
+    let ok = unsafe {
+        DuplicateTokenEx(
+            shell_token.0,
+            access,
+            std::ptr::null(),
+            SecurityImpersonation,
+            TokenPrimary,
+            &mut token,
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let token = OwnedHandle(token);
+
+    let application = wide(path);
+    let command_line = std::iter::once(path)
+        .chain(args.iter().map(String::as_str))
+        .map(quote_arg)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut command_line = wide(&command_line);
+    let startup = STARTUPINFOW {
+        cb: std::mem::size_of::<STARTUPINFOW>() as u32,
+        ..Default::default()
+    };
+    // Without a block the child gets the user profile's environment; with variables to
+    // remove, pass enTitan's own environment without them instead
+    let environment = (!env_remove.is_empty()).then(|| {
+        let mut block: Vec<u16> = Vec::new();
+        for (name, value) in std::env::vars_os() {
+            let name = name.to_string_lossy();
+            if !env_removed(&name, env_remove) {
+                block.extend(format!("{}={}", name, value.to_string_lossy()).encode_utf16());
+                block.push(0);
+            }
+        }
+        block.push(0);
+        block
+    });
+    let (flags, environment_ptr) = match &environment {
+        Some(block) => (
+            CREATE_UNICODE_ENVIRONMENT,
+            block.as_ptr().cast::<std::ffi::c_void>(),
+        ),
+        None => (0, std::ptr::null()),
+    };
+    let mut info = PROCESS_INFORMATION::default();
+    let ok = unsafe {
+        CreateProcessWithTokenW(
+            token.0,
+            0,
+            application.as_ptr(),
+            command_line.as_mut_ptr(),
+            flags,
+            environment_ptr,
+            std::ptr::null(),
+            &startup,
+            &mut info,
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    drop(OwnedHandle(info.hThread));
+    Ok(Child {
+        pid: info.dwProcessId,
+        inner: ChildInner::Handle(OwnedHandle(info.hProcess)),
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn spawn_de_elevated(path: &str, args: &[String], env_remove: &[String]) -> io::Result<Child> {
+    command(path, args, env_remove).spawn().map(Child::from)
+}
+
+/// Start `path` through `ShellExecuteExW` with the `runas` verb, for programs installed where
+/// a plain spawn lacks the permissions. Doesn't wait for the program to exit, but does block
+/// until the UAC prompt is answered, so callers run it on a background thread.
+#[cfg(target_os = "windows")]
+fn spawn_elevated(path: &str, args: &[String]) -> io::Result<Child> {
+    use windows_sys::Win32::System::Threading::GetProcessId;
+    use windows_sys::Win32::UI::Shell::{
+        SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW, ShellExecuteExW,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let verb = wide("runas");
+    let file = wide(path);
+    let parameters = wide(
+        &args
+            .iter()
+            .map(|a| quote_arg(a))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    // The shell would otherwise start it in System32; use the program's own folder
+    let directory = std::path::Path::new(path)
+        .parent()
+        .map(|d| wide(&d.to_string_lossy()));
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC,
+        lpVerb: verb.as_ptr(),
+        lpFile: file.as_ptr(),
+        lpParameters: parameters.as_ptr(),
+        lpDirectory: directory.as_ref().map_or(std::ptr::null(), |d| d.as_ptr()),
+        nShow: SW_SHOWNORMAL,
+        ..Default::default()
+    };
+    if unsafe { ShellExecuteExW(&mut info) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if info.hProcess.is_null() {
+        return Err(io::Error::other(format!("{} did not start", path)));
+    }
+    let process = OwnedHandle(info.hProcess);
+    match unsafe { GetProcessId(process.0) } {
+        0 => Err(io::Error::last_os_error()),
+        pid => Ok(Child {
+            pid,
+            inner: ChildInner::Handle(process),
+        }),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn spawn_elevated(_path: &str, _args: &[String]) -> io::Result<Child> {
+    Err(io::Error::other(
+        "running as administrator is only supported on Windows",
+    ))
+}
+
+/// Quote one argument for a Windows command line the way the MSVC runtime parses it back
+/// (backslashes only need doubling in front of a quote).
+#[cfg(target_os = "windows")]
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            c => {
+                quoted.extend(std::iter::repeat_n('\\', backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.extend(std::iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Run `program` with `parameters` as administrator and wait for it to exit. Unless enTitan
+/// is already elevated this shows the UAC prompt; declining it is reported as an error.
+#[cfg(target_os = "windows")]
+pub fn run_elevated(program: &str, parameters: &str) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::ERROR_CANCELLED;
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, INFINITE, WaitForSingleObject,
+    };
+    use windows_sys::Win32::UI::Shell::{
+        SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW, ShellExecuteExW,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_HIDE;
+
+    // Already elevated: "open" runs without a prompt; otherwise "runas" asks via UAC
+    let verb = wide(if is_elevated() { "open" } else { "runas" });
+    let file = wide(program);
+    let parameters = wide(parameters);
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC,
+        lpVerb: verb.as_ptr(),
+        lpFile: file.as_ptr(),
+        lpParameters: parameters.as_ptr(),
+        nShow: SW_HIDE,
+        ..Default::default()
+    };
+    if unsafe { ShellExecuteExW(&mut info) } == 0 {
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(ERROR_CANCELLED as i32) {
+            return Err("administrator permission was declined".into());
+        }
+        return Err(err.to_string());
+    }
+    if info.hProcess.is_null() {
+        return Err(format!("{} did not start", program));
+    }
+    let process = OwnedHandle(info.hProcess);
+    unsafe { WaitForSingleObject(process.0, INFINITE) };
+    let mut code = 0u32;
+    if unsafe { GetExitCodeProcess(process.0, &mut code) } == 0 {
+        return Err(io::Error::last_os_error().to_string());
+    }
+    if code != 0 {
+        return Err(format!("{} failed (exit code {})", program, code));
+    }
+    Ok(())
+}
+
+/// Closes the wrapped Win32 handle when dropped.
+#[cfg(target_os = "windows")]
+pub(crate) struct OwnedHandle(pub(crate) windows_sys::Win32::Foundation::HANDLE);
+
+// A process handle may be used and closed from any thread
+#[cfg(target_os = "windows")]
+unsafe impl Send for OwnedHandle {}
+
+#[cfg(target_os = "windows")]
+impl Drop for OwnedHandle {
+    fn drop(&mut self) {
+        unsafe { windows_sys::Win32::Foundation::CloseHandle(self.0) };
+    }
+}
+
+/// NUL-terminated UTF-16 copy of `s` for Win32 APIs.
+#[cfg(target_os = "windows")]
+pub(crate) fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+// The hooks run through `sh` here
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_hook_returns_output_and_stops_at_timeout_or_cancel() {
+        let (running, keep_running) = mpsc::channel();
+        let timeout = Duration::from_secs(10);
+        assert_eq!(
+            run_hook("echo out; echo err >&2", timeout, &keep_running),
+            Ok("out\nerr".into())
+        );
+        assert_eq!(
+            run_hook("echo oops; exit 3", timeout, &keep_running),
+            Err("exit status: 3: oops".into())
+        );
+
+        let started = Instant::now();
+        let result = run_hook("sleep 30", Duration::from_millis(200), &keep_running);
+        assert_eq!(result, Err("timed out after 0 s".into()));
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        drop(running);
+        let result = run_hook("sleep 30", timeout, &keep_running);
+        assert_eq!(result, Err("cancelled".into()));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
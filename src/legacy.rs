@@ -0,0 +1,222 @@
+//! Import of settings from other community WoW launchers.
+//!
+//! Such launchers mostly keep a small `key=value` .ini file; field names differ between them,
+//! so values are recognized by what they look like (an .exe, a .wtf, a locale code) together
+//! with hints in the key name rather than by a fixed list of keys.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Settings found in another launcher's config file; None where it had no such field.
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub battle_net: Option<String>,
+    pub wow: Option<String>,
+    pub config_wtf: Option<String>,
+    pub wow_args: Option<String>,
+    pub realm_host: Option<String>,
+    pub locale: Option<&'static str>,
+}
+
+impl Imported {
+    pub fn is_empty(&self) -> bool {
+        self.battle_net.is_none()
+            && self.wow.is_none()
+            && self.config_wtf.is_none()
+            && self.wow_args.is_none()
+            && self.realm_host.is_none()
+            && self.locale.is_none()
+    }
+}
+
+/// Read an .ini style launcher config. Relative paths are taken relative to the file.
+pub fn read_ini(path: &Path) -> Result<Imported, String> {
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    let text = String::from_utf8_lossy(&bytes);
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut imported = Imported::default();
+    for line in text.lines() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.starts_with([';', '#', '[']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim().trim_matches('"').trim();
+        if !value.is_empty() {
+            apply(&mut imported, &key, value, base);
+        }
+    }
+    // Launchers that only know the game folder: Config.wtf sits in its WTF folder
+    if imported.config_wtf.is_none()
+        && let Some(wow) = &imported.wow
+        && let Some(dir) = Path::new(wow).parent()
+    {
+        let config = dir.join("WTF").join("Config.wtf");
+        if config.is_file() {
+            imported.config_wtf = Some(config.display().to_string());
+        }
+    }
+    Ok(imported)
+}
+
+fn apply(imported: &mut Imported, key: &str, value: &str, base: &Path) {
+    let lower = value.to_ascii_lowercase();
+    let is_battle_net = ["battle", "bnet", "launcher"]
+        .iter()
+        .any(|k| key.contains(k));
+    let is_game = ["wow", "game", "client"].iter().any(|k| key.contains(k));
+    // Either separator, whichever OS wrote the file
+    let file = lower.rsplit(['/', '\\']).next().unwrap_or_default();
+    if lower.ends_with(".wtf") {
+        // realmlist.wtf and other .wtf files are no Config.wtf
+        if file == "config.wtf" {
+            imported.config_wtf.get_or_insert(resolve(base, value));
+        }
+    } else if lower.ends_with(".exe") {
+        if file.starts_with("battle.net") || (is_battle_net && !file.starts_with("wow")) {
+            imported.battle_net.get_or_insert(resolve(base, value));
+        } else {
+            imported.wow.get_or_insert(resolve(base, value));
+        }
+    } else if key.contains("realm") {
+        imported.realm_host.get_or_insert(value.to_string());
+    } else if key.contains("locale") || key.contains("language") {
+        if let Some(code) = crate::locales::canonical(value) {
+            imported.locale.get_or_insert(code);
+        }
+    } else if ["arg", "param", "commandline"]
+        .iter()
+        .any(|k| key.contains(k))
+    {
+        imported.wow_args.get_or_insert(value.to_string());
+    } else if is_game && let Some(exe) = find_wow_exe(&PathBuf::from(resolve(base, value))) {
+        imported.wow.get_or_insert(exe);
+    }
+}
+
+fn resolve(base: &Path, value: &str) -> String {
+    let path = Path::new(value);
+    if path.is_absolute() {
+        value.to_string()
+    } else {
+        base.join(path).display().to_string()
+    }
+}
+
+/// The WoW executable inside a game folder.
+fn find_wow_exe(dir: &Path) -> Option<String> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case("wow.exe"))
+        })
+        .map(|p| p.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("entitan-legacy-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn values_are_recognized_by_shape_and_key() {
+        let dir = temp_dir("shape");
+        let ini = dir.join("launcher.ini");
+        fs::write(
+            &ini,
+            "\u{feff}[Paths]\r\n\
+             ; a comment\r\n\
+             BnetPath = \"Battle.net\\Battle.net Launcher.exe\"\r\n\
+             RealmListPath=Game\\realmlist.wtf\r\n\
+             GamePath=Game\\Wow.exe\r\n\
+             Config=Game\\WTF\\Config.wtf\r\n\
+             # realmlist=ignored.example.org\r\n\
+             RealmList = logon.example.org\r\n\
+             Language=dede\r\n\
+             LaunchArgs=-console\r\n\
+             Empty=\r\n\
+             no equals sign\r\n",
+        )
+        .unwrap();
+        let imported = read_ini(&ini).unwrap();
+        let path = |p: &str| Some(dir.join(p).display().to_string());
+        assert_eq!(
+            imported.battle_net,
+            path("Battle.net\\Battle.net Launcher.exe")
+        );
+        assert_eq!(imported.wow, path("Game\\Wow.exe"));
+        assert_eq!(imported.config_wtf, path("Game\\WTF\\Config.wtf"));
+        assert_eq!(imported.realm_host.as_deref(), Some("logon.example.org"));
+        assert_eq!(imported.locale, Some("deDE"));
+        assert_eq!(imported.wow_args.as_deref(), Some("-console"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn first_value_wins_and_unknown_locales_are_skipped() {
+        let dir = temp_dir("first");
+        let ini = dir.join("launcher.ini");
+        fs::write(
+            &ini,
+            "wow=first\\Wow.exe\nwow=second\\Wow.exe\nlocale=xxXX\nlocale=frFR\n",
+        )
+        .unwrap();
+        let imported = read_ini(&ini).unwrap();
+        assert_eq!(
+            imported.wow,
+            Some(dir.join("first\\Wow.exe").display().to_string())
+        );
+        assert_eq!(imported.locale, Some("frFR"));
+        assert!(imported.battle_net.is_none() && imported.config_wtf.is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn game_folder_finds_exe_and_config() {
+        let dir = temp_dir("folder");
+        fs::create_dir_all(dir.join("Game").join("WTF")).unwrap();
+        fs::write(dir.join("Game").join("WOW.EXE"), "").unwrap();
+        fs::write(dir.join("Game").join("WTF").join("Config.wtf"), "").unwrap();
+        let ini = dir.join("launcher.ini");
+        fs::write(&ini, "GameFolder=Game\n").unwrap();
+        let imported = read_ini(&ini).unwrap();
+        assert_eq!(
+            imported.wow,
+            Some(dir.join("Game").join("WOW.EXE").display().to_string())
+        );
+        assert_eq!(
+            imported.config_wtf,
+            Some(
+                dir.join("Game")
+                    .join("WTF")
+                    .join("Config.wtf")
+                    .display()
+                    .to_string()
+            )
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn empty_file_imports_nothing() {
+        let dir = temp_dir("empty");
+        let ini = dir.join("launcher.ini");
+        fs::write(&ini, "[Section]\n; nothing here\n").unwrap();
+        assert!(read_ini(&ini).unwrap().is_empty());
+        assert!(read_ini(&dir.join("missing.ini")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,176 @@
+//! Safety limits that advanced users may tune in the `limits` section of settings.json.
+//!
+//! The values are read once at startup; out-of-range values are clamped into their range
+//! and reported rather than rejected, so a typo can't leave enTitan unable to start.
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Limits {
+    /// Largest Config.wtf read or edited in full; bigger files use large-file mode.
+    pub max_config_bytes: u64,
+    /// Smallest window size in logical points.
+    pub min_window_width: f32,
+    pub min_window_height: f32,
+    /// Quiet time after a Config.wtf change before locale enforcement rewrites it.
+    pub enforce_debounce_ms: u64,
+    /// How long the run waits for Battle.net/WoW to appear before carrying on.
+    pub process_wait_timeout_secs: u64,
+    /// How long Battle.net gets to close on request before it is terminated.
+    pub close_timeout_secs: u64,
+    /// Connect timeout of the realm and latency checks.
+    pub connect_timeout_secs: u64,
+    /// A launched program exiting within this time counts as an early exit.
+    pub early_exit_secs: u64,
+    /// How long a before/after run command may take before it is stopped.
+    pub hook_timeout_secs: u64,
+    /// How many earlier values each path field's drop-down remembers.
+    pub recent_paths: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_config_bytes: crate::wtf::MAX_FILE_SIZE,
+            min_window_width: 600.0,
+            min_window_height: 400.0,
+            enforce_debounce_ms: 2000,
+            process_wait_timeout_secs: 120,
+            close_timeout_secs: 10,
+            connect_timeout_secs: 3,
+            early_exit_secs: 60,
+            hook_timeout_secs: 300,
+            recent_paths: 8,
+        }
+    }
+}
+
+static LIMITS: OnceLock<Limits> = OnceLock::new();
+
+/// Install the limits from the settings, clamped to their valid ranges. Returns a message
+/// per clamped value. Only the first call has an effect.
+pub fn init(limits: Limits) -> Vec<String> {
+    let (limits, problems) = limits.validated();
+    let _ = LIMITS.set(limits);
+    problems
+}
+
+/// The limits in effect (the defaults if `init` wasn't called).
+pub fn get() -> &'static Limits {
+    LIMITS.get_or_init(Limits::default)
+}
+
+impl Limits {
+    fn validated(mut self) -> (Self, Vec<String>) {
+        let mut problems = Vec::new();
+        clamp(
+            &mut self.max_config_bytes,
+            64 * 1024..=64 * 1024 * 1024,
+            "maxConfigBytes",
+            &mut problems,
+        );
+        clamp(
+            &mut self.min_window_width,
+            320.0..=1920.0,
+            "minWindowWidth",
+            &mut problems,
+        );
+        clamp(
+            &mut self.min_window_height,
+            240.0..=1080.0,
+            "minWindowHeight",
+            &mut problems,
+        );
+        clamp(
+            &mut self.enforce_debounce_ms,
+            100..=60_000,
+            "enforceDebounceMs",
+            &mut problems,
+        );
+        clamp(
+            &mut self.process_wait_timeout_secs,
+            5..=1800,
+            "processWaitTimeoutSecs",
+            &mut problems,
+        );
+        clamp(
+            &mut self.close_timeout_secs,
+            1..=300,
+            "closeTimeoutSecs",
+            &mut problems,
+        );
+        clamp(
+            &mut self.connect_timeout_secs,
+            1..=60,
+            "connectTimeoutSecs",
+            &mut problems,
+        );
+        clamp(
+            &mut self.early_exit_secs,
+            5..=3600,
+            "earlyExitSecs",
+            &mut problems,
+        );
+        clamp(
+            &mut self.hook_timeout_secs,
+            1..=86_400,
+            "hookTimeoutSecs",
+            &mut problems,
+        );
+        clamp(&mut self.recent_paths, 1..=50, "recentPaths", &mut problems);
+        (self, problems)
+    }
+
+    pub fn enforce_debounce(&self) -> Duration {
+        Duration::from_millis(self.enforce_debounce_ms)
+    }
+
+    pub fn process_wait_timeout(&self) -> Duration {
+        Duration::from_secs(self.process_wait_timeout_secs)
+    }
+
+    pub fn close_timeout(&self) -> Duration {
+        Duration::from_secs(self.close_timeout_secs)
+    }
+
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs)
+    }
+
+    pub fn early_exit(&self) -> Duration {
+        Duration::from_secs(self.early_exit_secs)
+    }
+
+    pub fn hook_timeout(&self) -> Duration {
+        Duration::from_secs(self.hook_timeout_secs)
+    }
+}
+
+fn clamp<T: PartialOrd + Copy + std::fmt::Display>(
+    value: &mut T,
+    range: RangeInclusive<T>,
+    name: &str,
+    problems: &mut Vec<String>,
+) {
+    // NaN compares false both ways; treat it as below the range
+    let clamped = if *value > *range.end() {
+        *range.end()
+    } else if *value >= *range.start() {
+        return;
+    } else {
+        *range.start()
+    };
+    problems.push(format!(
+        "limits.{} = {} is outside {}..={}; using {}",
+        name,
+        value,
+        range.start(),
+        range.end(),
+        clamped
+    ));
+    *value = clamped;
+}
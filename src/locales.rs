@@ -0,0 +1,139 @@
+//! WoW client locales known to enTitan.
+
+/// Locale code, English language name and the language's own name for itself, in picker
+/// order.
+pub const KNOWN_LOCALES: &[(&str, &str, &str)] = &[
+    ("enUS", "English (United States)", "English (US)"),
+    ("enGB", "English (United Kingdom)", "English (UK)"),
+    ("deDE", "German (Germany)", "Deutsch"),
+    ("frFR", "French (France)", "Français"),
+    ("esES", "Spanish (Spain)", "Español (España)"),
+    ("esMX", "Spanish (Mexico)", "Español (México)"),
+    ("ptBR", "Portuguese (Brazil)", "Português (Brasil)"),
+    ("itIT", "Italian (Italy)", "Italiano"),
+    ("ruRU", "Russian (Russia)", "Русский"),
+    ("koKR", "Korean (Korea)", "한국어"),
+    ("zhCN", "Simplified Chinese", "简体中文"),
+    ("zhTW", "Traditional Chinese", "繁體中文"),
+];
+
+pub const DEFAULT_LOCALE: &str = "enUS";
+
+/// The correctly-cased code for `code` if it is a known locale (case-insensitive).
+pub fn canonical(code: &str) -> Option<&'static str> {
+    KNOWN_LOCALES
+        .iter()
+        .find(|(c, ..)| c.eq_ignore_ascii_case(code))
+        .map(|(c, ..)| *c)
+}
+
+/// Language name for a known locale code.
+pub fn name(code: &str) -> Option<&'static str> {
+    KNOWN_LOCALES
+        .iter()
+        .find(|(c, ..)| c.eq_ignore_ascii_case(code))
+        .map(|(_, n, _)| *n)
+}
+
+/// The language's own name for a known locale code, e.g. "Deutsch" for deDE.
+pub fn native_name(code: &str) -> Option<&'static str> {
+    KNOWN_LOCALES
+        .iter()
+        .find(|(c, ..)| c.eq_ignore_ascii_case(code))
+        .map(|(.., n)| *n)
+}
+
+/// "deDE — German (Germany)" for known codes, the bare code otherwise.
+pub fn label(code: &str) -> String {
+    match name(code) {
+        Some(n) => format!("{} — {}", code, n),
+        None => code.to_string(),
+    }
+}
+
+/// Picker entry "deDE — German (Germany) · Deutsch" for known codes, so the language can be
+/// found by its own name too; the bare code otherwise.
+pub fn picker_label(code: &str) -> String {
+    match (name(code), native_name(code)) {
+        (Some(n), Some(native)) if native != n => format!("{} — {} · {}", code, n, native),
+        _ => label(code),
+    }
+}
+
+/// Locale forced by a `-locale xxXX` / `--locale=xxXX` style argument on a WoW command line;
+/// such an argument silently takes precedence over Config.wtf.
+pub fn from_command_line(cmdline: &str) -> Option<String> {
+    let mut args = cmdline.split_whitespace().map(|a| a.trim_matches('"'));
+    while let Some(arg) = args.next() {
+        let flag = arg.trim_start_matches(['-', '/']);
+        if flag.len() == arg.len() {
+            continue;
+        }
+        let (name, inline) = match flag.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (flag, None),
+        };
+        if name.eq_ignore_ascii_case("locale") {
+            return inline.or_else(|| args.next()).map(str::to_string);
+        }
+    }
+    None
+}
+
+/// The known locale closest to the OS display language, for the first-run default.
+pub fn from_os() -> Option<&'static str> {
+    closest(&os_language()?)
+}
+
+/// Known locale for a language tag such as `de-DE`, `pt_PT.UTF-8` or `zh-Hant-TW`: the exact
+/// match, else the game's variant for that language.
+fn closest(tag: &str) -> Option<&'static str> {
+    let tag = tag.split(['.', '@']).next()?.replace('_', "-");
+    let mut parts = tag.split('-');
+    let language = parts.next()?.to_ascii_lowercase();
+    let rest: Vec<String> = parts.map(|p| p.to_ascii_uppercase()).collect();
+    if let Some(region) = rest.last()
+        && let Some(code) = canonical(&format!("{}{}", language, region))
+    {
+        return Some(code);
+    }
+    let traditional = rest
+        .iter()
+        .any(|p| matches!(p.as_str(), "HANT" | "TW" | "HK" | "MO"));
+    Some(match language.as_str() {
+        "en" => "enUS",
+        "de" => "deDE",
+        "fr" => "frFR",
+        // Latin American Spanish is closer to the Mexican client
+        "es" => "esMX",
+        "pt" => "ptBR",
+        "it" => "itIT",
+        "ru" => "ruRU",
+        "ko" => "koKR",
+        "zh" if traditional => "zhTW",
+        "zh" => "zhCN",
+        _ => return None,
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn os_language() -> Option<String> {
+    use windows_sys::Win32::Globalization::{GetUserDefaultUILanguage, LCIDToLocaleName};
+    // LOCALE_NAME_MAX_LENGTH
+    let mut name = [0u16; 85];
+    // A LANGID with the default sort order is its LCID
+    let lcid = u32::from(unsafe { GetUserDefaultUILanguage() });
+    let len = unsafe { LCIDToLocaleName(lcid, name.as_mut_ptr(), name.len() as i32, 0) };
+    if len <= 1 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn os_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty() && v != "C" && v != "POSIX")
+}
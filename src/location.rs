@@ -0,0 +1,196 @@
+//! Where the settings directory is: `ENTITAN_CONFIG_DIR` if set, else the folder named in
+//! `location.txt` of the default directory (chosen in the window, e.g. a synced cloud
+//! folder), else the default `%APPDATA%\entitan` (`$XDG_CONFIG_HOME/entitan` or
+//! `~/.config/entitan` elsewhere). Without `APPDATA` (or `HOME`) the default is the
+//! `entitan-settings` folder next to the executable, so settings are still kept.
+//!
+//! The single-instance lock file lives in whichever directory is in use, so two instances
+//! sharing a folder still exclude each other.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+pub const ENV_VAR: &str = "ENTITAN_CONFIG_DIR";
+// Names the chosen folder; only read from the default directory
+const LOCATION_FILE: &str = "location.txt";
+// Default folder next to the executable when the user folder is unknown
+const EXE_DIR_FOLDER: &str = "entitan-settings";
+// Held by the running instance
+const LOCK_FILE: &str = "entitan.lock";
+
+// The directory in use, resolved on first use and changed by `relocate`
+static CURRENT: RwLock<Option<PathBuf>> = RwLock::new(None);
+// Locked handle of the lock file, kept open for as long as the directory is in use
+static LOCK: Mutex<Option<File>> = Mutex::new(None);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Environment,
+    Chosen,
+    Default,
+}
+
+/// The settings directory in use.
+pub fn current() -> Option<PathBuf> {
+    if let Some(dir) = CURRENT.read().ok()?.as_ref() {
+        return Some(dir.clone());
+    }
+    let dir = resolve()?;
+    let mut current = CURRENT.write().ok()?;
+    Some(current.get_or_insert(dir).clone())
+}
+
+/// How the directory in use was chosen.
+pub fn source() -> Source {
+    if env_dir().is_some() {
+        Source::Environment
+    } else if current().is_some_and(|dir| Some(dir) != default_dir()) {
+        Source::Chosen
+    } else {
+        Source::Default
+    }
+}
+
+pub fn default_dir() -> Option<PathBuf> {
+    user_dir().or_else(|| Some(env::current_exe().ok()?.parent()?.join(EXE_DIR_FOLDER)))
+}
+
+/// Whether the default folder is the one next to the executable, because the environment
+/// names no user folder.
+pub fn default_is_exe_dir() -> bool {
+    user_dir().is_none()
+}
+
+/// The per-user default folder, when the environment names the user's folders.
+fn user_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        env::var("APPDATA")
+            .ok()
+            .map(|a| PathBuf::from(a).join("entitan"))
+    } else if let Ok(xdg) = env::var("XDG_CONFIG_HOME") {
+        Some(PathBuf::from(xdg).join("entitan"))
+    } else if let Ok(home) = env::var("HOME") {
+        Some(PathBuf::from(home).join(".config").join("entitan"))
+    } else {
+        None
+    }
+}
+
+/// Whether `name` is one of the files that belong to this PC's use of the directory rather
+/// than to the settings (never archived, copied or restored).
+pub fn is_local(name: &OsStr) -> bool {
+    name == LOCK_FILE || name == LOCATION_FILE
+}
+
+/// Take the single-instance lock in the settings directory (the temp directory if there is
+/// none). `Ok(false)` when another instance holds it.
+pub fn lock() -> Result<bool, String> {
+    let dir = current().unwrap_or_else(env::temp_dir);
+    match try_lock(&dir)? {
+        Some(file) => {
+            if let Ok(mut lock) = LOCK.lock() {
+                *lock = Some(file);
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Make `dir` the settings directory, taking the lock there and remembering it in
+/// `location.txt` (removed when `dir` is the default). With `copy` the files of the current
+/// directory are copied over first, replacing those of the same name. Returns the number of
+/// files copied.
+pub fn relocate(dir: &Path, copy: bool) -> Result<usize, String> {
+    if env_dir().is_some() {
+        return Err(format!("the settings folder is set by {}", ENV_VAR));
+    }
+    let default = default_dir().ok_or("cannot determine the default settings folder")?;
+    let old = current();
+    if old.as_deref() == Some(dir) {
+        return Ok(0);
+    }
+    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let file = try_lock(dir)?.ok_or("another enTitan instance uses that folder")?;
+    let mut copied = 0;
+    if copy && let Some(old) = &old {
+        let mut names = Vec::new();
+        crate::backup::collect_files(old, old, &mut names).map_err(|e| e.to_string())?;
+        for name in names {
+            let target = dir.join(&name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::copy(old.join(&name), &target)
+                .map_err(|e| format!("{}: {}", target.display(), e))?;
+            copied += 1;
+        }
+    }
+    let pointer = default.join(LOCATION_FILE);
+    if dir == default {
+        match fs::remove_file(&pointer) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(format!("{}: {}", pointer.display(), e));
+            }
+            _ => {}
+        }
+    } else {
+        fs::create_dir_all(&default).map_err(|e| e.to_string())?;
+        fs::write(&pointer, dir.display().to_string())
+            .map_err(|e| format!("{}: {}", pointer.display(), e))?;
+    }
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(dir.to_path_buf());
+    }
+    // Dropping the old handle releases the old directory's lock
+    if let Ok(mut lock) = LOCK.lock() {
+        *lock = Some(file);
+    }
+    Ok(copied)
+}
+
+/// Point the settings directory at a fresh folder below the temp directory, so tests never
+/// touch the real one. Returns it; every test in the run shares it.
+#[cfg(test)]
+pub fn use_temp_dir() -> PathBuf {
+    let dir = env::temp_dir().join(format!("entitan-settings-{}", std::process::id()));
+    let mut current = CURRENT.write().unwrap();
+    current.get_or_insert(dir).clone()
+}
+
+fn resolve() -> Option<PathBuf> {
+    if let Some(dir) = env_dir() {
+        return Some(dir);
+    }
+    let default = default_dir()?;
+    let chosen = fs::read_to_string(default.join(LOCATION_FILE))
+        .ok()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    Some(chosen.map(PathBuf::from).unwrap_or(default))
+}
+
+fn env_dir() -> Option<PathBuf> {
+    env::var_os(ENV_VAR)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The locked lock file of `dir`, or None when another instance holds it.
+fn try_lock(dir: &Path) -> Result<Option<File>, String> {
+    use fs2::FileExt;
+
+    let path = dir.join(LOCK_FILE);
+    let _ = fs::create_dir_all(dir);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(file.try_lock_exclusive().is_ok().then_some(file))
+}
@@ -3,49 +3,198 @@
     windows_subsystem = "windows"
 )]
 
+mod accounts;
+mod addons;
+mod background;
+mod backup;
+mod bnetconfig;
+mod bundle;
+mod clock;
+mod cvarfile;
+mod defaults;
+mod eventlog;
+mod firewall;
+mod history;
+mod hosts;
+mod i18n;
+mod input;
+mod installs;
+mod launch;
+mod legacy;
+mod limits;
+mod locales;
+mod location;
+mod migrate;
+mod monitors;
+mod presets;
+mod processes;
+mod recent;
+mod recovery;
+mod renderer;
+mod repaint;
+mod report;
+mod schedule;
+mod screenshots;
+mod script;
+mod sequence;
+mod servers;
+mod snapshots;
+mod tabs;
+mod theme;
+mod timeline;
+mod validate;
+mod verify;
+mod webhook;
+mod wtf;
+
 use eframe::egui;
+use i18n::tr;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher, recommended_watcher};
 use rfd::FileDialog;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use wtf::WtfDocument;
+
+const DEFAULT_WINDOW_TITLE: &str = "enTitan - Titan Reforged Locale Launcher";
+
+// How often a failed WTF directory watch registration is re-attempted
+const WATCH_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+// How often we look for a running WoW client to compare its locale with Config.wtf
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// How often the saved paths are re-validated while enTitan stays open
+const PATH_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Default waits of the run sequence: after starting Battle.net before WoW, and after WoW
+// before Battle.net is started again
+const DEFAULT_WOW_LAUNCH_DELAY_SECS: u64 = 10;
+const DEFAULT_RELAUNCH_DELAY_SECS: u64 = 60;
+
+// Guild broadcast payload (Discord-compatible); see `webhook::render_template`
+const DEFAULT_BROADCAST_TEMPLATE: &str = r#"{"content": "{player} launched WoW ({locale})"}"#;
+
+// NTP server and tolerated skew for the opt-in clock drift check
+const DEFAULT_NTP_SERVER: &str = "pool.ntp.org";
+const DEFAULT_CLOCK_DRIFT_THRESHOLD_SECS: u64 = 60;
+
+// Process-aware waits: Battle.net's main process (the launcher exe may be a stub that
+// starts it); see `limits` for how long to wait for it
+const BATTLE_NET_PROCESS: &str = "Battle.net.exe";
+
+// Hover text of the per-executable Test buttons
+const TEST_LAUNCH_HINT: &str = "Start only this program with its arguments (no sequence, no waits) and report whether it starts and how it exits";
+
+// Default latency limit of the pre-launch realm check
+const DEFAULT_LATENCY_THRESHOLD_MS: u64 = 250;
+
+// Copy of settings.json kept by each save, used when the file turns out damaged
+const SETTINGS_BACKUP: &str = "settings.json.bak";
+
+// Settings that change without being edited (window placement, the recent paths taken from
+// the path fields); they don't count as unsaved changes and are kept when those are discarded
+const UNTRACKED_KEYS: &[&str] = &["geometry", "recentPaths"];
+
+// Why a write or launch was refused in viewer mode
+const VIEWER_REFUSAL: &str = "viewer mode is on; nothing is written or launched";
+
+// Why a server folder switch was refused: the client would overwrite the result on exit
+const WOW_RUNNING_REFUSAL: &str = "close WoW first; it rewrites Config.wtf when it exits";
+
+// Where "Reset to defaults" moves the settings it clears
+const SETTINGS_BEFORE_RESET: &str = "settings.before-reset.json";
 
-// Embedded default background: compiled from `background.png` at the repository root.
-// This file is included at compile time using `include_bytes!`, causing a rebuild when the image changes.
-const DEFAULT_BACKGROUND_PNG: &[u8] =
-    include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/background.png"));
+// Default idle time required before the focus-stealing Battle.net relaunch (when enabled)
+const DEFAULT_RELAUNCH_IDLE_SECS: u64 = 5;
+
+// Text size range and default of the detached event log window, and how many entries it shows
+const DEFAULT_LOG_FONT_SIZE: f32 = 16.0;
+const LOG_FONT_MIN: f32 = 10.0;
+const LOG_FONT_MAX: f32 = 40.0;
+const DETACHED_LOG_ENTRIES: usize = 500;
 
 // The two entries in Config.wtf that set game language
 // SET audioLocale "enUS"
 // SET textLocale "enUS"
 
 fn main() {
-    // Load settings to read any saved window geometry (position & size)
-    let (_battle, _config, _wow, _preferred, geom) = load_settings_full();
-
-    // Single-instance enforcement: lock a file in the settings directory (or temp dir)
-    use fs2::FileExt;
-    use std::fs::OpenOptions;
-
-    let lock_path = settings_file_path()
-        .and_then(|p| p.parent().map(|d| d.join("entitan.lock")))
-        .unwrap_or_else(|| std::env::temp_dir().join("entitan.lock"));
-    if let Some(parent) = lock_path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
-    let lock_file = match OpenOptions::new()
-        .create(true)
-        .read(true)
-        .write(true)
-        .open(&lock_path)
+    // Headless subcommands run before any window or single-instance handling
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("validate") {
+        validate::attach_console();
+    }
+    // Settings first: their safety limits apply to the headless subcommands too
+    // Problems with them end up in the window's status line
+    let (settings, mut startup_problems) = load_settings_checked();
+    // A chosen folder on a drive that isn't there (e.g. a cloud folder not synced yet)
+    if location::source() != location::Source::Default
+        && let Some(dir) = settings_dir().filter(|d| !d.is_dir())
+    {
+        startup_problems.push(format!(
+            "Settings folder {} does not exist; starting with default settings",
+            dir.display()
+        ));
+    }
+    // Told once, when the folder next to the executable is first used
+    if location::default_is_exe_dir()
+        && location::source() == location::Source::Default
+        && let Some(file) = settings_file_path().filter(|f| !f.exists())
     {
-        Ok(f) => f,
+        let problem = format!(
+            "{} is not set; settings are kept next to enTitan in {}",
+            if cfg!(target_os = "windows") {
+                "APPDATA"
+            } else {
+                "HOME"
+            },
+            file.parent().unwrap_or(&file).display()
+        );
+        eprintln!("{}", problem);
+        startup_problems.push(problem);
+    }
+    let limit_problems = limits::init(settings.limits.clone());
+    for problem in &limit_problems {
+        eprintln!("{}", problem);
+    }
+    startup_problems.extend(limit_problems);
+    if args.first().map(String::as_str) == Some("validate") {
+        std::process::exit(validate::run(&args[1..]));
+    }
+    // `--tab <name>` opens the window with that section expanded (for shortcuts)
+    // `--viewer` opens a read-only window for this run, whatever the setting says
+    let viewer_forced = args.iter().any(|a| a == "--viewer");
+    let open_section = arg_value(&args, "--tab").and_then(|name| {
+        let section = Section::parse(name);
+        if section.is_none() {
+            eprintln!(
+                "Unknown --tab {:?}; expected one of: {}",
+                name,
+                Section::NAMES
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        section
+    });
+
+    // Saved window geometry (position & size)
+    let geom = settings.geometry;
+
+    // Single-instance enforcement: lock a file in the settings directory (or temp dir); the
+    // lock is held until exit or until the settings folder is moved
+    let locked = match location::lock() {
+        Ok(locked) => locked,
         Err(e) => {
-            eprintln!("Failed to create lock file {}: {}", lock_path.display(), e);
+            eprintln!("Failed to create lock file {}", e);
             return;
         }
     };
-    if let Err(_) = lock_file.try_lock_exclusive() {
+    if !locked {
         // Another instance is running — show a dialog and exit
         let _ = rfd::MessageDialog::new()
             .set_title("enTitan already running")
@@ -54,38 +203,100 @@ fn main() {
             .show();
         return;
     }
-    // Keep the lock file alive for the lifetime of main so the lock remains held
-    let _lock_file = lock_file;
-
     let mut options = eframe::NativeOptions::default();
     // Minimum window size (enforced where supported)
-    let min_size = egui::vec2(600.0, 400.0);
+    let min_size = egui::vec2(
+        limits::get().min_window_width,
+        limits::get().min_window_height,
+    );
     options.viewport.min_inner_size = Some(min_size);
 
+    // Renderer from settings; `--renderer glow|wgpu` and `--software` override it for this run
+    // so a machine whose GPU can't open the window can still start enTitan
+    let backend = match arg_value(&args, "--renderer") {
+        Some(name) => renderer::Backend::parse(name).unwrap_or_else(|| {
+            eprintln!("Unknown --renderer {:?}; expected glow or wgpu", name);
+            settings.renderer
+        }),
+        None => settings.renderer,
+    };
+    let software = settings.software_rendering || args.iter().any(|a| a == "--software");
+    renderer::apply(&mut options, backend, software);
+
     // Default initial size if no saved geometry
     let default_size = min_size;
 
     // Use ViewportBuilder but make sure to set min_inner_size on the builder so it isn't lost
-    let mut vp_builder = egui::viewport::ViewportBuilder::default().with_min_inner_size(min_size);
-    if let Some((x, y, w, h)) = geom {
+    // Distributor branding (title, window icon, accent color, news link) from defaults.toml
+    let branding = defaults::Defaults::load()
+        .map(|d| d.branding)
+        .unwrap_or_default();
+    let title = branding
+        .title
+        .clone()
+        .unwrap_or_else(|| DEFAULT_WINDOW_TITLE.to_string());
+
+    let mut vp_builder = egui::viewport::ViewportBuilder::default()
+        .with_min_inner_size(min_size)
+        .with_title(title.clone());
+    if let Some(icon) = branding.load_icon() {
+        vp_builder = vp_builder.with_icon(icon);
+    }
+    // Saved position in physical pixels; corrected on the first frame (see `restore_position`)
+    // unless the window opens maximized, where moving it would undo that
+    let mut restore_position = geom.as_ref().map(Geometry::physical_position);
+    if let Some(Geometry {
+        w,
+        h,
+        scale,
+        maximized,
+        ..
+    }) = geom
+    {
+        // Maximizes on the monitor the position below is on
+        vp_builder = vp_builder.with_maximized(maximized);
         // Clamp loaded window size to the minimum to avoid creating too-small windows
         let clamped_w = w.max(min_size.x);
         let clamped_h = h.max(min_size.y);
-        // Clamp loaded position to be non-negative so the window isn't placed off-screen
-        let clamped_x = x.max(0) as f32;
-        let clamped_y = y.max(0) as f32;
-        vp_builder = vp_builder
-            .with_inner_size(egui::vec2(clamped_w, clamped_h))
-            .with_position(egui::pos2(clamped_x, clamped_y));
+        vp_builder = vp_builder.with_inner_size(egui::vec2(clamped_w, clamped_h));
+        // The builder takes points; until the window exists its monitor's scale is unknown, so
+        // convert with the one saved, which is exact when the monitor's scale hasn't changed
+        if let Some(pos) = restore_position {
+            vp_builder = vp_builder.with_position(pos / scale.unwrap_or(1.0));
+        }
+        if maximized {
+            restore_position = None;
+        }
     } else {
         vp_builder = vp_builder.with_inner_size(default_size);
     }
     options.viewport = vp_builder;
 
+    let app_title = title.clone();
     let _ = eframe::run_native(
-        "enTitan - Titan Reforged Locale Launcher",
+        &title,
         options,
-        Box::new(|_cc| Ok(Box::new(EntitanApp::default()))),
+        Box::new(move |cc| {
+            // Before the app exists: its file watcher already sends through a repaint channel
+            repaint::init(&cc.egui_ctx);
+            if let Some(accent) = branding.accent_color() {
+                cc.egui_ctx.all_styles_mut(|style| {
+                    style.visuals.selection.bg_fill = accent;
+                    style.visuals.hyperlink_color = accent;
+                });
+            }
+            Ok(Box::new(EntitanApp {
+                news_url: branding.news_url,
+                window_title: app_title,
+                tab: open_section.map_or_else(tabs::Tab::default, Section::tab),
+                open_section,
+                viewer_forced,
+                restore_position,
+                // Report clamped limits where they are noticed
+                status: (!startup_problems.is_empty()).then(|| startup_problems.join("; ")),
+                ..Default::default()
+            }))
+        }),
     );
 }
 
@@ -93,81 +304,890 @@ struct EntitanApp {
     battle_net_path: String,
     config_wtf_path: String,
     wow_executable_path: String,
+    // Command-line arguments for Battle.net and WoW, split like a shell would (persisted)
+    battle_net_args: String,
+    wow_args: String,
+    // Shell commands run before and after the run sequence; a failing critical hook aborts
+    // the run (persisted)
+    pre_launch_hook: String,
+    pre_launch_hook_critical: bool,
+    post_launch_hook: String,
+    post_launch_hook_critical: bool,
+    // Buttons writing game files must be held to act (persisted)
+    confirm_writes: bool,
+    // The settings as last loaded or saved, and the keys changed since
+    saved_settings: serde_json::Value,
+    unsaved: Vec<String>,
+    // Answer to the unsaved-changes prompt on closing: save (true) or discard (false)
+    close_answered: Option<bool>,
+    // Read-only viewer mode: shows the setup but writes, launches and saves nothing
+    // (persisted), or forced for this run by `--viewer`
+    viewer_mode: bool,
+    viewer_forced: bool,
+    // Path fields locked against edits until explicitly unlocked (persisted)
+    battle_net_locked: bool,
+    config_wtf_locked: bool,
+    wow_locked: bool,
+    // Earlier values of the path fields, for their drop-downs (persisted)
+    recent_paths: recent::RecentPaths,
+    // Start these through the UAC prompt ("Run as administrator") (persisted)
+    battle_net_elevated: bool,
+    wow_elevated: bool,
+    // Priority class and CPU affinity mask (0 = all CPUs) applied to WoW after launch
+    // (persisted)
+    wow_priority: launch::Priority,
+    wow_affinity: u64,
+    // Local time of day (HH:MM) of the scheduled run (persisted) and, while armed, when it
+    // starts
+    scheduled_time: String,
+    scheduled_run: Option<SystemTime>,
     status: Option<String>,
-    // Preferred locale editable by the user (persisted)
-    preferred_locale: String,
+    // Preferred audio and text locales editable by the user (persisted)
+    preferred_audio_locale: String,
+    preferred_text_locale: String,
+    // Wait for Battle.net (optionally its window) and WoW to actually run (persisted)
+    process_aware_waits: bool,
+    wait_for_login_window: bool,
+    // User-defined run sequence replacing the built-in flow when enabled (persisted)
+    use_custom_sequence: bool,
+    custom_steps: Vec<script::ScriptStep>,
+    // Waits of the run sequence in seconds (persisted)
+    wow_launch_delay_secs: u64,
+    relaunch_delay_secs: u64,
+    // Don't start Battle.net/WoW again when they already run (persisted)
+    skip_running_programs: bool,
+    // Rewrite the preferred locales into Config.wtf right before WoW starts (persisted)
+    write_locale_before_wow: bool,
+    // Close Battle.net once WoW runs instead of relaunching it at the end (persisted)
+    close_battle_net_after_wow: bool,
+    // Optional idle wait before the final Battle.net relaunch (persisted)
+    relaunch_wait_for_idle: bool,
+    relaunch_idle_secs: u64,
+    // Pause for a manual Battle.net login (e.g. 2FA) before WoW is launched (persisted)
+    confirm_login: bool,
+    // Opt-in clock drift check at startup (persisted) and its pending result / measured offset
+    check_clock_drift: bool,
+    ntp_server: String,
+    clock_drift_threshold_secs: u64,
+    clock_rx: Option<std::sync::mpsc::Receiver<Result<f64, String>>>,
+    clock_offset: Option<Result<f64, String>>,
+    // Realm hostname to diagnose (persisted; empty uses Config.wtf's realmList, which is
+    // read with the locales), the running/finished hosts check and its guided fix
+    realm_host: String,
+    realm_list: Option<String>,
+    // Pre-launch latency check of the realm and whether exceeding the limit aborts (persisted)
+    latency_check: bool,
+    latency_threshold_ms: u64,
+    latency_abort: bool,
+    hosts_rx: Option<std::sync::mpsc::Receiver<hosts::Diagnosis>>,
+    hosts_diagnosis: Option<hosts::Diagnosis>,
+    hosts_fix_rx: Option<std::sync::mpsc::Receiver<Result<PathBuf, String>>>,
+    // Drop admin rights for spawned games when enTitan itself is elevated (persisted)
+    de_elevate_children: bool,
+    // Leave the listed environment variables out of launched programs (persisted)
+    sanitize_environment: bool,
+    environment_blocklist: String,
+    // Rewrite Config.wtf whenever its locales drift from the preferred ones (persisted)
+    enforce_locale: bool,
+    // Pending (debounced) enforcement rewrite and how often Battle.net reset the locale
+    enforce_due: Option<Instant>,
+    enforce_count: u32,
+    // Also write the preferred locale into Battle.net.config (persisted), and its current keys
+    sync_battle_net_config: bool,
+    bnet_locales: Vec<bnetconfig::LocaleEntry>,
+    // Copy the characters' AddOns.txt before locale writes (persisted), and those found
+    // blanked since; `blanked_rx` is a pending search for them
+    protect_addons: bool,
+    blanked_addons: Vec<addons::Blanked>,
+    blanked_rx: Option<std::sync::mpsc::Receiver<Vec<addons::Blanked>>>,
+    // Last run step failure with a known fix, offered above the Run button until dismissed
+    step_failure: Option<StepFailure>,
+    // The io error of the last failed Config.wtf write, for `recovery::for_write`
+    last_write_error: Option<std::io::Error>,
+    // Check which locale the client really ran in once WoW exits (persisted)
+    verify_locale: bool,
+    // Move new screenshots into per-locale subfolders of WoW's Screenshots directory (persisted)
+    organize_screenshots: bool,
+    screenshot_organizer: Option<screenshots::Organizer>,
     // Cached values parsed from the Config.wtf file (if available)
     audio_locale: Option<String>,
     text_locale: Option<String>,
     last_config_path: Option<String>,
+    // Size of a Config.wtf over `limits::get().max_config_bytes`, handled in large-file mode
+    large_config_size: Option<u64>,
+    // Undo/redo of our own Config.wtf edits
+    history: history::History,
+    // Problems found in Config.wtf by the lint pass (or why it couldn't be parsed)
+    config_findings: Vec<String>,
+    // Whether Config.wtf has the read-only attribute (users set it to stop Battle.net resets)
+    config_readonly: bool,
+    // Modification time of Config.wtf when the values above were read; a write first checks
+    // it to notice edits made since
+    config_read_mtime: Option<SystemTime>,
+    // Running WoW client (polled every `PROCESS_POLL_INTERVAL`)
+    running_game: Option<RunningGame>,
+    // Saved paths found invalid by the startup/daily check (shown as a banner)
+    invalid_paths: Vec<PathField>,
+    last_path_check: Option<Instant>,
+    last_process_check: Option<Instant>,
+    // When the run launched WoW and whether its process has been seen since, to notice an
+    // early exit; `firewall_hint` then offers the firewall helper, `firewall_rx` is its result
+    // and `firewall_check_rx` the check for existing rules after such an exit
+    wow_launch: Option<(Instant, bool)>,
+    // Programs started by runs, watched for their exit
+    children: Vec<LaunchedChild>,
+    // Pending test launch as administrator: program name, options and the started process
+    test_launch_rx: Option<
+        std::sync::mpsc::Receiver<(
+            &'static str,
+            launch::LaunchOptions,
+            std::io::Result<launch::Child>,
+        )>,
+    >,
+    firewall_hint: bool,
+    firewall_rx: Option<std::sync::mpsc::Receiver<Result<(), String>>>,
+    firewall_check_rx: Option<std::sync::mpsc::Receiver<Option<bool>>>,
+    // Config-cache.wtf and account-level configs found next to Config.wtf
+    account_configs: Vec<accounts::AccountConfig>,
     // File watcher (notify)
     watcher: Option<RecommendedWatcher>,
     watcher_rx: Option<std::sync::mpsc::Receiver<notify::Result<notify::Event>>>,
-    // Background image texture (loaded from ./background.png)
-    background_texture: Option<egui::TextureHandle>,
-    background_size: Option<[usize; 2]>,
-    background_load_attempted: bool,
-    // Cache of last seen inner size and window position (updated each frame)
+    // Path currently registered with the watcher (None while live reload is inactive)
+    watched_path: Option<PathBuf>,
+    last_watch_attempt: Option<Instant>,
+    // Background image texture, and the file, grayscale and tiling it was loaded with (the
+    // image is reloaded when they change)
+    background: Option<background::Background>,
+    background_loaded: Option<(String, bool, bool)>,
+    // Renderer used from the next start on (persisted, read before the window is created)
+    renderer: renderer::Backend,
+    software_rendering: bool,
+    // Tab shown in the window
+    tab: tabs::Tab,
+    // Light/dark theme (persisted)
+    theme: theme::Theme,
+    // Language of the window itself (persisted)
+    ui_language: i18n::Language,
+    // No UI animations (persisted); also off when Windows' reduced-motion setting is on
+    disable_animations: bool,
+    // Colorblind-friendly status colors, full-contrast text, no background image (persisted)
+    high_contrast: bool,
+    os_reduced_motion: bool,
+    // Background image file (empty: the default one), its opacity (0 to 1), grayscale and
+    // scaling (persisted)
+    background_image: String,
+    background_opacity: f32,
+    background_grayscale: bool,
+    background_scaling: background::Scaling,
+    // Tint color layered with the background image (persisted)
+    background_tint: [u8; 4],
+    tint_over_image: bool,
+    // Cache of last seen inner size and outer position in logical points, and the scale
+    // (pixels per point) they were measured at (updated each frame). While the window is
+    // maximized or minimized they keep its normal placement, and `last_maximized` is set.
     last_inner_size: Option<(f32, f32)>,
-    last_window_pos: Option<(i32, i32)>,
+    last_window_pos: Option<(f32, f32)>,
+    last_scale: Option<f32>,
+    last_maximized: bool,
+    // Saved window position in physical pixels, applied on the first frame once the scale
+    // of the monitor the window opened on is known
+    restore_position: Option<egui::Pos2>,
     // Run sequence state
-    run_active: bool,
-    run_tx: std::sync::mpsc::Sender<String>,
-    run_rx: std::sync::mpsc::Receiver<String>,
+    run: Option<sequence::Sequence>,
+    // Run history and status messages (persisted in events.jsonl); `logged_status` is the
+    // last status already recorded, so each message is logged once
+    event_log: eventlog::EventLog,
+    logged_status: Option<String>,
+    // Guild broadcast: POST a templated message to a webhook when a run completes (persisted)
+    broadcast_enabled: bool,
+    broadcast_url: String,
+    broadcast_template: String,
+    player_name: String,
+    broadcast_rx: Option<std::sync::mpsc::Receiver<Result<(), String>>>,
+    // CSV export options of the event log panel
+    csv_columns: eventlog::CsvColumns,
+    csv_from: String,
+    csv_to: String,
+    // Event log popped out into its own always-on-top window for screen sharing, its text
+    // size (persisted) and whether it stops following new entries
+    log_detached: bool,
+    log_font_size: f32,
+    log_autoscroll_paused: bool,
+    // Distributor news link shown at the bottom (from defaults.toml branding)
+    news_url: Option<String>,
+    // Named Config.wtf snapshots (profiles panel)
+    snapshot_names: Vec<String>,
+    // Server folders linked to snapshots, by snapshot name
+    snapshot_folders: HashMap<String, PathBuf>,
+    new_snapshot_name: String,
+    // Profile whose notes the launcher tab shows (persisted; set when a snapshot is saved or
+    // applied) and those notes
+    active_profile: Option<String>,
+    profile_notes: String,
+    // Section requested with `--tab`, expanded on the first frame
+    open_section: Option<Section>,
+    // Widget to focus on the next frame, after a native dialog took the focus away
+    pending_focus: Option<egui::Id>,
+    // Window title (from branding) and the title last sent to the window, which carries the
+    // run's countdown so it shows on the taskbar
+    window_title: String,
+    shown_title: Option<String>,
+    // Named installations (persisted; the active one's values are in the fields above) and
+    // the name typed for a new one
+    installations: Vec<installs::Installation>,
+    active_installation: Option<String>,
+    new_installation_name: String,
+}
+
+/// Run `write` on `path`, clearing its read-only flag first if `readonly` and restoring the
+/// flag afterwards, even if the write failed.
+fn with_writable(
+    path: &Path,
+    readonly: bool,
+    write: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    if readonly {
+        wtf::set_readonly(path, false).map_err(|e| e.to_string())?;
+    }
+    let written = write();
+    if readonly {
+        wtf::set_readonly(path, true)
+            .map_err(|e| format!("failed to restore read-only flag: {}", e))?;
+    }
+    written
+}
+
+/// Value following `flag` on the command line, e.g. `--tab profiles`.
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+/// A run step that failed in a way `recovery` has quick fixes for.
+struct StepFailure {
+    recovery: recovery::Recovery,
+    // The aborted run, resumed from the failed step; None for a failed locale write, after
+    // which the run went on
+    run: Option<sequence::Sequence>,
+}
+
+/// A program started by a run or a test launch, watched so its exit can be reported.
+struct LaunchedChild {
+    name: String,
+    child: launch::Child,
+    started: Instant,
+}
+
+/// A running WoW client and the locale its command line forces, if any.
+struct RunningGame {
+    pid: u32,
+    locale_arg: Option<String>,
+    // Process start time, else when enTitan first saw it
+    since: SystemTime,
+}
+
+/// The stored paths, for the validity check and its fixes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum PathField {
+    BattleNet,
+    ConfigWtf,
+    Wow,
+}
+
+impl PathField {
+    const ALL: [PathField; 3] = [Self::BattleNet, Self::ConfigWtf, Self::Wow];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::BattleNet => "Battle.net",
+            Self::ConfigWtf => "Config.wtf",
+            Self::Wow => "WoW Executable",
+        }
+    }
+
+    fn ext(self) -> &'static str {
+        match self {
+            Self::ConfigWtf => "wtf",
+            Self::BattleNet | Self::Wow => "exe",
+        }
+    }
+
+    /// Widget id of the field's text edit.
+    fn id(self) -> egui::Id {
+        egui::Id::new(("path_field", self))
+    }
+}
+
+/// Parts of the window that `--tab <name>` can open on.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Config,
+    Profiles,
+    Accounts,
+    Appearance,
+    // Whole tabs, with nothing to expand
+    Paths,
+    Launch,
+    Log,
+}
+
+impl Section {
+    // Accepted --tab names; shortcuts are typed by hand, so singular and plural both work
+    const NAMES: [(&str, Section); 14] = [
+        ("config", Self::Config),
+        ("locale", Self::Config),
+        ("profiles", Self::Profiles),
+        ("profile", Self::Profiles),
+        ("accounts", Self::Accounts),
+        ("account", Self::Accounts),
+        ("appearance", Self::Appearance),
+        ("paths", Self::Paths),
+        ("path", Self::Paths),
+        ("launch", Self::Launch),
+        ("sequence", Self::Launch),
+        ("log", Self::Log),
+        ("logs", Self::Log),
+        ("history", Self::Log),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, section)| section)
+    }
+
+    /// The tab the section is on.
+    fn tab(self) -> tabs::Tab {
+        match self {
+            Self::Config | Self::Profiles | Self::Accounts => tabs::Tab::Locale,
+            Self::Appearance | Self::Paths => tabs::Tab::Paths,
+            Self::Launch => tabs::Tab::Launch,
+            Self::Log => tabs::Tab::Log,
+        }
+    }
 }
 
 impl Default for EntitanApp {
     fn default() -> Self {
-        let (battle, config, wow, preferred, _geom) = load_settings_full();
-        let (tx, rx) = std::sync::mpsc::channel();
+        let settings = load_settings_full();
+        let (battle, config, wow) = (settings.launcher, settings.config, settings.wow_executable);
+        // Unknown codes (typos, garbage from hand edits) fall back to the default locale
+        let preferred = |value: &str| {
+            locales::canonical(value)
+                .unwrap_or(locales::DEFAULT_LOCALE)
+                .to_string()
+        };
+        let preferred_audio = preferred(&settings.preferred_audio_locale);
+        let preferred_text = preferred(&settings.preferred_text_locale);
 
         // Create file watcher (notify) to get OS-level notifications for Config.wtf changes
-        let (watch_tx, watch_rx) = std::sync::mpsc::channel();
+        let (watch_tx, watch_rx) = repaint::channel();
         let watcher = match recommended_watcher(move |res| {
             let _ = watch_tx.send(res);
         }) {
-            Ok(mut w) => {
-                if !config.is_empty() {
-                    if Path::new(&config).exists() {
-                        let _ = w.watch(Path::new(&config), RecursiveMode::NonRecursive);
-                    }
-                }
-                Some(w)
-            }
+            // The WTF directory watch itself is registered (and retried) by `ensure_watch`
+            Ok(w) => Some(w),
             Err(e) => {
                 eprintln!("Failed to create file watcher: {}", e);
                 None
             }
         };
 
-        Self {
+        let mut app = Self {
             battle_net_path: battle,
             config_wtf_path: config,
             wow_executable_path: wow,
+            battle_net_args: settings.launcher_args,
+            realm_host: settings.realm_host,
+            latency_check: settings.latency_check,
+            latency_threshold_ms: settings.latency_threshold_ms,
+            latency_abort: settings.latency_abort,
+            realm_list: None,
+            hosts_rx: None,
+            hosts_diagnosis: None,
+            hosts_fix_rx: None,
+            wow_args: settings.wow_args,
+            pre_launch_hook: settings.pre_launch_hook,
+            pre_launch_hook_critical: settings.pre_launch_hook_critical,
+            post_launch_hook: settings.post_launch_hook,
+            post_launch_hook_critical: settings.post_launch_hook_critical,
+            confirm_writes: settings.confirm_writes,
+            viewer_mode: settings.viewer_mode,
+            viewer_forced: false,
+            battle_net_locked: settings.battle_net_locked,
+            config_wtf_locked: settings.config_wtf_locked,
+            wow_locked: settings.wow_locked,
+            recent_paths: settings.recent_paths,
+            battle_net_elevated: settings.battle_net_elevated,
+            wow_elevated: settings.wow_elevated,
+            wow_priority: settings.wow_priority,
+            wow_affinity: settings.wow_affinity,
+            scheduled_time: settings.scheduled_time,
+            log_detached: false,
+            log_font_size: settings.log_font_size.clamp(LOG_FONT_MIN, LOG_FONT_MAX),
+            log_autoscroll_paused: false,
+            scheduled_run: None,
             status: None,
-            preferred_locale: if preferred.is_empty() {
-                "enUS".into()
-            } else {
-                preferred
-            },
+            preferred_audio_locale: preferred_audio,
+            preferred_text_locale: preferred_text,
+            skip_running_programs: settings.skip_running_programs,
+            write_locale_before_wow: settings.write_locale_before_wow,
+            close_battle_net_after_wow: settings.close_battle_net_after_wow,
+            relaunch_wait_for_idle: settings.relaunch_wait_for_idle,
+            relaunch_idle_secs: settings.relaunch_idle_secs,
+            confirm_login: settings.confirm_login,
+            process_aware_waits: settings.process_aware_waits,
+            wait_for_login_window: settings.wait_for_login_window,
+            use_custom_sequence: settings.use_custom_sequence,
+            custom_steps: settings.custom_steps,
+            wow_launch_delay_secs: settings.wow_launch_delay_secs,
+            relaunch_delay_secs: settings.relaunch_delay_secs,
+            enforce_locale: settings.enforce_locale,
+            clock_rx: settings
+                .check_clock_drift
+                .then(|| clock::check_async(settings.ntp_server.clone())),
+            check_clock_drift: settings.check_clock_drift,
+            ntp_server: settings.ntp_server,
+            clock_drift_threshold_secs: settings.clock_drift_threshold_secs,
+            clock_offset: None,
+            enforce_due: None,
+            enforce_count: 0,
+            sync_battle_net_config: settings.sync_battle_net_config,
+            protect_addons: settings.protect_addons,
+            verify_locale: settings.verify_locale,
+            blanked_addons: Vec::new(),
+            blanked_rx: None,
+            step_failure: None,
+            last_write_error: None,
+            bnet_locales: Vec::new(),
+            organize_screenshots: settings.organize_screenshots,
+            renderer: settings.renderer,
+            software_rendering: settings.software_rendering,
+            tab: tabs::Tab::default(),
+            theme: settings.theme,
+            ui_language: settings.ui_language,
+            disable_animations: settings.disable_animations,
+            high_contrast: settings.high_contrast,
+            os_reduced_motion: os_reduced_motion(),
+            screenshot_organizer: None,
+            de_elevate_children: settings.de_elevate_children,
+            sanitize_environment: settings.sanitize_environment,
+            environment_blocklist: settings.environment_blocklist,
             audio_locale: None,
             text_locale: None,
             last_config_path: None,
-            watcher: watcher,
+            large_config_size: None,
+            history: history::History::default(),
+            config_findings: Vec::new(),
+            config_readonly: false,
+            config_read_mtime: None,
+            running_game: None,
+            invalid_paths: Vec::new(),
+            last_path_check: None,
+            last_process_check: None,
+            wow_launch: None,
+            children: Vec::new(),
+            test_launch_rx: None,
+            firewall_hint: false,
+            firewall_rx: None,
+            firewall_check_rx: None,
+            account_configs: Vec::new(),
+            watcher,
             watcher_rx: Some(watch_rx),
-            background_texture: None,
-            background_size: None,
-            background_load_attempted: false,
-            last_inner_size: None,
-            last_window_pos: None,
-            run_active: false,
-            run_tx: tx,
-            run_rx: rx,
-        }
+            watched_path: None,
+            last_watch_attempt: None,
+            background: None,
+            background_loaded: None,
+            background_image: settings.background_image,
+            background_opacity: settings.background_opacity,
+            background_grayscale: settings.background_grayscale,
+            background_scaling: settings.background_scaling,
+            background_tint: settings.background_tint,
+            tint_over_image: settings.tint_over_image,
+            // Until the window reports its own, keep the saved placement
+            last_inner_size: settings.geometry.as_ref().map(|g| (g.w, g.h)),
+            last_window_pos: settings.geometry.as_ref().map(|g| (g.x, g.y)),
+            last_scale: settings.geometry.as_ref().and_then(|g| g.scale),
+            last_maximized: settings.geometry.as_ref().is_some_and(|g| g.maximized),
+            restore_position: None,
+            run: None,
+            news_url: None,
+            event_log: eventlog::EventLog::load(),
+            broadcast_enabled: settings.broadcast_enabled,
+            broadcast_url: settings.broadcast_url,
+            broadcast_template: settings.broadcast_template,
+            player_name: settings.player_name,
+            broadcast_rx: None,
+            logged_status: None,
+            csv_columns: eventlog::CsvColumns {
+                time: true,
+                kind: true,
+                message: true,
+            },
+            csv_from: String::new(),
+            csv_to: String::new(),
+            snapshot_names: Vec::new(),
+            snapshot_folders: HashMap::new(),
+            profile_notes: settings
+                .active_profile
+                .as_deref()
+                .map(snapshots::load_notes)
+                .unwrap_or_default(),
+            active_profile: settings.active_profile,
+            open_section: None,
+            pending_focus: None,
+            window_title: DEFAULT_WINDOW_TITLE.to_string(),
+            shown_title: None,
+            new_snapshot_name: String::new(),
+            // A hand-edited name that matches no installation selects none
+            active_installation: settings
+                .active_installation
+                .filter(|name| installs::find(&settings.installations, name).is_some()),
+            installations: settings.installations,
+            new_installation_name: String::new(),
+            saved_settings: serde_json::Value::Null,
+            unsaved: Vec::new(),
+            close_answered: None,
+        };
+        app.refresh_snapshots();
+        app.saved_settings = app.settings_value();
+        app
     }
 }
 
 impl EntitanApp {
+    /// The persisted settings as JSON, to compare with what was saved.
+    fn settings_value(&self) -> serde_json::Value {
+        serde_json::to_value(self.settings_snapshot()).unwrap_or_default()
+    }
+
+    /// Keys of the settings edited since they were last loaded or saved.
+    fn unsaved_changes(&self) -> Vec<String> {
+        let current = self.settings_value();
+        let (Some(current), Some(saved)) = (current.as_object(), self.saved_settings.as_object())
+        else {
+            return Vec::new();
+        };
+        current
+            .iter()
+            .filter(|(key, value)| {
+                !UNTRACKED_KEYS.contains(&key.as_str()) && saved.get(*key) != Some(*value)
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Write the settings and take them as the saved state.
+    fn save_now(&mut self) -> std::io::Result<()> {
+        let settings = self.settings_snapshot();
+        save_settings(&settings)?;
+        self.saved_settings = serde_json::to_value(&settings).unwrap_or_default();
+        self.unsaved.clear();
+        Ok(())
+    }
+
+    /// Write the settings as last saved, with only the untracked keys brought up to date.
+    fn save_discarding_changes(&self) -> std::io::Result<()> {
+        let mut value = self.saved_settings.clone();
+        let current = self.settings_value();
+        if let Some(map) = value.as_object_mut() {
+            for key in UNTRACKED_KEYS {
+                if let Some(v) = current.get(*key) {
+                    map.insert(key.to_string(), v.clone());
+                }
+            }
+        }
+        save_settings(&serde_json::from_value(value)?)
+    }
+
+    /// Problems of the three paths worth mentioning before they are saved.
+    fn path_problems(&self) -> Vec<&'static str> {
+        let mut problems = Vec::new();
+        if !is_file_with_ext(&self.battle_net_path, "exe") {
+            problems.push("Battle.net path must point to an existing .exe");
+        }
+        if !is_file_with_ext(&self.config_wtf_path, "wtf") {
+            problems.push("Config.wtf path must point to an existing .wtf file");
+        }
+        if !is_file_with_ext(&self.wow_executable_path, "exe") {
+            problems.push("WoW Executable must point to an existing .exe file");
+        }
+        problems
+    }
+
+    /// The Save button's action: write the settings, mentioning paths that don't resolve.
+    fn save_clicked(&mut self) {
+        let problems = self.path_problems();
+        self.status = Some(match self.save_now() {
+            Ok(()) if problems.is_empty() => "Settings saved".into(),
+            Ok(()) => format!("Settings saved; note: {}", problems.join("; ")),
+            Err(e) => format!("Error saving: {}", e),
+        });
+    }
+
+    /// Ask whether to save unsaved changes before the window closes. Returns false to keep
+    /// it open.
+    fn confirm_close(&mut self) -> bool {
+        if self.viewer() || self.unsaved.is_empty() {
+            return true;
+        }
+        let mut description = format!(
+            "Save your changes before closing?\n\nChanged: {}",
+            self.unsaved.join(", ")
+        );
+        let problems = self.path_problems();
+        if !problems.is_empty() {
+            description.push_str(&format!("\n\nNote: {}", problems.join("; ")));
+        }
+        let answer = rfd::MessageDialog::new()
+            .set_title("Unsaved changes")
+            .set_description(description)
+            .set_buttons(rfd::MessageButtons::YesNoCancel)
+            .show();
+        match answer {
+            rfd::MessageDialogResult::Yes => match self.save_now() {
+                Ok(()) => {
+                    self.close_answered = Some(true);
+                    true
+                }
+                Err(e) => {
+                    self.status = Some(format!("Error saving: {}", e));
+                    false
+                }
+            },
+            rfd::MessageDialogResult::No => {
+                self.close_answered = Some(false);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Collect the persisted parts of the app state (using cached window geometry).
+    fn settings_snapshot(&self) -> SettingsFile {
+        SettingsFile {
+            schema_version: migrate::CURRENT_VERSION,
+            launcher: self.battle_net_path.clone(),
+            config: self.config_wtf_path.clone(),
+            wow_executable: self.wow_executable_path.clone(),
+            launcher_args: self.battle_net_args.clone(),
+            realm_host: self.realm_host.clone(),
+            latency_check: self.latency_check,
+            latency_threshold_ms: self.latency_threshold_ms,
+            latency_abort: self.latency_abort,
+            wow_args: self.wow_args.clone(),
+            pre_launch_hook: self.pre_launch_hook.clone(),
+            pre_launch_hook_critical: self.pre_launch_hook_critical,
+            post_launch_hook: self.post_launch_hook.clone(),
+            post_launch_hook_critical: self.post_launch_hook_critical,
+            confirm_writes: self.confirm_writes,
+            viewer_mode: self.viewer_mode,
+            battle_net_locked: self.battle_net_locked,
+            config_wtf_locked: self.config_wtf_locked,
+            wow_locked: self.wow_locked,
+            recent_paths: self.recent_paths_snapshot(),
+            battle_net_elevated: self.battle_net_elevated,
+            wow_elevated: self.wow_elevated,
+            wow_priority: self.wow_priority,
+            wow_affinity: self.wow_affinity,
+            scheduled_time: self.scheduled_time.clone(),
+            log_font_size: self.log_font_size,
+            preferred_audio_locale: self.preferred_audio_locale.clone(),
+            preferred_text_locale: self.preferred_text_locale.clone(),
+            skip_running_programs: self.skip_running_programs,
+            write_locale_before_wow: self.write_locale_before_wow,
+            close_battle_net_after_wow: self.close_battle_net_after_wow,
+            relaunch_wait_for_idle: self.relaunch_wait_for_idle,
+            relaunch_idle_secs: self.relaunch_idle_secs,
+            confirm_login: self.confirm_login,
+            process_aware_waits: self.process_aware_waits,
+            wait_for_login_window: self.wait_for_login_window,
+            use_custom_sequence: self.use_custom_sequence,
+            custom_steps: self.custom_steps.clone(),
+            wow_launch_delay_secs: self.wow_launch_delay_secs,
+            relaunch_delay_secs: self.relaunch_delay_secs,
+            enforce_locale: self.enforce_locale,
+            check_clock_drift: self.check_clock_drift,
+            ntp_server: self.ntp_server.clone(),
+            clock_drift_threshold_secs: self.clock_drift_threshold_secs,
+            sync_battle_net_config: self.sync_battle_net_config,
+            protect_addons: self.protect_addons,
+            verify_locale: self.verify_locale,
+            organize_screenshots: self.organize_screenshots,
+            renderer: self.renderer,
+            software_rendering: self.software_rendering,
+            theme: self.theme,
+            ui_language: self.ui_language,
+            disable_animations: self.disable_animations,
+            high_contrast: self.high_contrast,
+            broadcast_enabled: self.broadcast_enabled,
+            broadcast_url: self.broadcast_url.clone(),
+            broadcast_template: self.broadcast_template.clone(),
+            player_name: self.player_name.clone(),
+            de_elevate_children: self.de_elevate_children,
+            sanitize_environment: self.sanitize_environment,
+            environment_blocklist: self.environment_blocklist.clone(),
+            background_image: self.background_image.clone(),
+            background_opacity: self.background_opacity,
+            background_grayscale: self.background_grayscale,
+            background_scaling: self.background_scaling,
+            background_tint: self.background_tint,
+            tint_over_image: self.tint_over_image,
+            active_profile: self.active_profile.clone(),
+            installations: self.installations_snapshot(),
+            active_installation: self.active_installation.clone(),
+            geometry: match (self.last_window_pos, self.last_inner_size) {
+                (Some((x, y)), Some((w, h))) => Some(Geometry {
+                    x,
+                    y,
+                    w,
+                    h,
+                    scale: self.last_scale,
+                    maximized: self.last_maximized,
+                    monitor: self.last_scale.and_then(|scale| {
+                        monitors::at((x * scale) as i32, (y * scale) as i32).map(|m| m.name)
+                    }),
+                }),
+                _ => None,
+            },
+            // Not editable in the UI; the clamped values are written back
+            limits: limits::get().clone(),
+        }
+    }
+
+    /// The current paths, preferred locales and delays as installation `name`.
+    fn current_installation(&self, name: &str) -> installs::Installation {
+        installs::Installation {
+            name: name.to_string(),
+            launcher: self.battle_net_path.clone(),
+            config: self.config_wtf_path.clone(),
+            wow_executable: self.wow_executable_path.clone(),
+            preferred_audio_locale: self.preferred_audio_locale.clone(),
+            preferred_text_locale: self.preferred_text_locale.clone(),
+            wow_launch_delay_secs: self.wow_launch_delay_secs,
+            relaunch_delay_secs: self.relaunch_delay_secs,
+        }
+    }
+
+    /// The installations list with the active one refreshed from the current fields.
+    fn installations_snapshot(&self) -> Vec<installs::Installation> {
+        let mut list = self.installations.clone();
+        if let Some(i) = self
+            .active_installation
+            .as_deref()
+            .and_then(|name| installs::find(&list, name))
+        {
+            list[i] = self.current_installation(&list[i].name);
+        }
+        list
+    }
+
+    /// Keep the current values in the active installation and load installation `index`.
+    /// Locked path fields keep their value.
+    fn switch_installation(&mut self, index: usize) {
+        self.installations = self.installations_snapshot();
+        self.recent_paths = self.recent_paths_snapshot();
+        let inst = self.installations[index].clone();
+        let preferred = |value: &str| {
+            locales::canonical(value)
+                .unwrap_or(locales::DEFAULT_LOCALE)
+                .to_string()
+        };
+        let mut skipped = Vec::new();
+        for (field, value) in [
+            (PathField::BattleNet, inst.launcher),
+            (PathField::ConfigWtf, inst.config),
+            (PathField::Wow, inst.wow_executable),
+        ] {
+            if self.locked(field) {
+                skipped.push(field.label());
+            } else {
+                self.set_path(field, value);
+            }
+        }
+        self.preferred_audio_locale = preferred(&inst.preferred_audio_locale);
+        self.preferred_text_locale = preferred(&inst.preferred_text_locale);
+        self.wow_launch_delay_secs = inst.wow_launch_delay_secs;
+        self.relaunch_delay_secs = inst.relaunch_delay_secs;
+        self.active_installation = Some(inst.name.clone());
+        // Check the new paths right away rather than at the next daily check
+        self.invalid_paths.clear();
+        self.last_path_check = None;
+        let mut status = format!("Switched to installation {} (unsaved)", inst.name);
+        if !skipped.is_empty() {
+            status.push_str(&format!("; kept locked {}", skipped.join(", ")));
+        }
+        self.status = Some(status);
+    }
+
+    /// "Installation:" row: pick, add (from the current values) or remove a named
+    /// installation. Switching is disabled while a run is active.
+    fn installation_controls(&mut self, ui: &mut egui::Ui, label_w: f32) {
+        ui.horizontal(|ui| {
+            let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Installation:")));
+            let mut chosen = None;
+            ui.add_enabled_ui(self.run.is_none(), |ui| {
+                egui::ComboBox::from_id_salt("installation")
+                    .width(160.0)
+                    .selected_text(self.active_installation.as_deref().unwrap_or("(none)"))
+                    .show_ui(ui, |ui| {
+                        for (i, inst) in self.installations.iter().enumerate() {
+                            let active = self.active_installation.as_deref() == Some(&inst.name);
+                            if ui.selectable_label(active, &inst.name).clicked() && !active {
+                                chosen = Some(i);
+                            }
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id)
+                    .on_disabled_hover_text("Wait for the run to finish");
+            });
+            if let Some(i) = chosen {
+                self.switch_installation(i);
+            }
+            ui.add_sized(
+                [120.0, 20.0],
+                egui::TextEdit::singleline(&mut self.new_installation_name).hint_text("e.g. PTR"),
+            )
+            .named(egui::WidgetType::TextEdit, "New installation name");
+            if ui
+                .button("Add")
+                .on_hover_text(
+                    "Save the current paths, preferred locales and delays under this name",
+                )
+                .clicked()
+            {
+                match installs::check_new_name(&self.installations, &self.new_installation_name) {
+                    Ok(name) => {
+                        self.installations = self.installations_snapshot();
+                        self.installations.push(self.current_installation(&name));
+                        self.active_installation = Some(name.clone());
+                        self.new_installation_name.clear();
+                        self.status = Some(format!("Added installation {} (unsaved)", name));
+                    }
+                    Err(e) => self.status = Some(e),
+                }
+            }
+            if let Some(name) = self.active_installation.clone()
+                && ui
+                    .button("Remove")
+                    .on_hover_text(format!(
+                        "Forget installation {}; the fields keep its values",
+                        name
+                    ))
+                    .clicked()
+            {
+                self.installations
+                    .retain(|i| !i.name.eq_ignore_ascii_case(&name));
+                self.active_installation = None;
+                self.status = Some(format!("Removed installation {} (unsaved)", name));
+            }
+        });
+    }
+
     /// Update cached `audio_locale` and `text_locale` if the config path changed.
     fn update_locales(&mut self) {
         let cfg = self.config_wtf_path.clone();
@@ -175,25 +1195,29 @@ impl EntitanApp {
         if self.last_config_path.as_ref() == Some(&cfg) {
             return;
         }
-        let old_path = self.last_config_path.clone();
         self.last_config_path = if cfg.is_empty() {
             None
         } else {
             Some(cfg.clone())
         };
 
-        // Update watcher registration if present
-        if let Some(ref mut watcher) = self.watcher {
-            if let Some(old) = old_path {
-                let _ = watcher.unwatch(Path::new(&old));
-            }
-            if !cfg.is_empty() {
-                let _ = watcher.watch(Path::new(&cfg), RecursiveMode::NonRecursive);
-            }
+        // Move the watcher registration over to the new directory (retried until it succeeds)
+        if self.watched_path != self.watch_dir() {
+            self.drop_watch();
+            self.last_watch_attempt = None;
+            self.ensure_watch();
         }
 
         self.audio_locale = None;
         self.text_locale = None;
+        self.config_readonly = false;
+        self.config_read_mtime = None;
+        self.config_findings.clear();
+        self.large_config_size = None;
+        self.account_configs.clear();
+        self.bnet_locales = bnetconfig::default_path()
+            .and_then(|p| bnetconfig::read(&p).ok())
+            .unwrap_or_default();
 
         if cfg.is_empty() {
             return;
@@ -203,544 +1227,3264 @@ impl EntitanApp {
             // leave as None
             return;
         }
-        if let Ok(meta) = p.metadata() {
-            if meta.len() >= 8192 {
-                // File too large — don't open
-                self.audio_locale = Some("(file too large)".into());
-                self.text_locale = Some("(file too large)".into());
-                return;
+        self.account_configs = accounts::discover(p);
+        self.config_read_mtime = p.metadata().and_then(|m| m.modified()).ok();
+        self.config_readonly = p
+            .metadata()
+            .map(|m| m.permissions().readonly())
+            .unwrap_or(false);
+        if let Ok(meta) = p.metadata()
+            && meta.len() > limits::get().max_config_bytes
+        {
+            // Large-file mode: scan only for the locale lines, keeping memory bounded
+            self.large_config_size = Some(meta.len());
+            match wtf::scan_values(p, &["audioLocale", "textLocale", "realmList"]) {
+                Ok(mut values) => {
+                    self.realm_list = values.pop().flatten();
+                    self.text_locale = values.pop().flatten();
+                    self.audio_locale = values.pop().flatten();
+                }
+                Err(e) => self.config_findings = vec![format!("cannot be read: {}", e)],
             }
+            return;
         }
-        if let Ok(contents) = fs::read_to_string(p) {
-            for line in contents.lines() {
-                let s = line.trim();
-                if s.starts_with("SET audioLocale") {
-                    if let Some(first) = s.find('"') {
-                        let rest = &s[first + 1..];
-                        if let Some(end) = rest.find('"') {
-                            self.audio_locale = Some(rest[..end].to_string());
-                        }
-                    }
-                } else if s.starts_with("SET textLocale") {
-                    if let Some(first) = s.find('"') {
-                        let rest = &s[first + 1..];
-                        if let Some(end) = rest.find('"') {
-                            self.text_locale = Some(rest[..end].to_string());
-                        }
-                    }
-                }
+        match fs::read(p)
+            .map_err(|e| e.to_string())
+            .and_then(|b| WtfDocument::parse(&b))
+        {
+            Ok(doc) => {
+                self.audio_locale = doc.get("audioLocale");
+                self.text_locale = doc.get("textLocale");
+                self.realm_list = doc.get("realmList");
+                self.config_findings = doc.lint().iter().map(ToString::to_string).collect();
             }
+            Err(e) => self.config_findings = vec![format!("cannot be read: {}", e)],
         }
     }
 
-    /// Update both `SET audioLocale` and `SET textLocale` lines in the Config.wtf file
-    /// to match `self.preferred_locale`. Performs existence and size checks (<8192 bytes).
-    fn update_config_file_locales(&mut self) -> Result<(), String> {
-        let cfg = self.config_wtf_path.clone();
-        if cfg.is_empty() {
-            return Err("Config.wtf path is not set".into());
+    /// Directory watched for Config.wtf changes. Battle.net replaces the file via
+    /// delete+rename, which would take a watch on the file itself with it, so we watch the
+    /// WTF directory and filter its events for the Config.wtf path.
+    fn watch_dir(&self) -> Option<PathBuf> {
+        if self.config_wtf_path.is_empty() {
+            return None;
         }
-        let p = Path::new(&cfg);
-        if !p.exists() || !p.is_file() {
-            return Err("Config.wtf path does not exist or is not a file".into());
+        Path::new(&self.config_wtf_path)
+            .parent()
+            .filter(|d| !d.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+    }
+
+    /// Register the WTF directory watch if it isn't active yet. Failed attempts (e.g. the
+    /// directory doesn't exist yet) are retried every `WATCH_RETRY_INTERVAL`.
+    fn ensure_watch(&mut self) {
+        if self.watched_path.is_some() {
+            return;
         }
-        let meta = p.metadata().map_err(|e| e.to_string())?;
-        if meta.len() >= 8192 {
-            return Err("Config.wtf file is too large to safely edit".into());
-        }
-        let contents = fs::read_to_string(p).map_err(|e| e.to_string())?;
-        let mut lines: Vec<String> = contents.lines().map(|l| l.to_string()).collect();
-        let mut found_audio = false;
-        let mut found_text = false;
-        for line in lines.iter_mut() {
-            let s = line.trim();
-            if s.starts_with("SET audioLocale") {
-                *line = format!("SET audioLocale \"{}\"", self.preferred_locale);
-                found_audio = true;
-            } else if s.starts_with("SET textLocale") {
-                *line = format!("SET textLocale \"{}\"", self.preferred_locale);
-                found_text = true;
-            }
-        }
-        if !found_audio {
-            lines.push(format!("SET audioLocale \"{}\"", self.preferred_locale));
-        }
-        if !found_text {
-            lines.push(format!("SET textLocale \"{}\"", self.preferred_locale));
-        }
-        let mut out = lines.join("\n");
-        out.push('\n');
-        fs::write(p, out).map_err(|e| e.to_string())?;
-        // Force a refresh of cached values even if the file path didn't change
+        let Some(dir) = self.watch_dir() else {
+            return;
+        };
+        let Some(ref mut watcher) = self.watcher else {
+            return;
+        };
+        if let Some(last) = self.last_watch_attempt
+            && last.elapsed() < WATCH_RETRY_INTERVAL
+        {
+            return;
+        }
+        self.last_watch_attempt = Some(Instant::now());
+        if watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+            self.watched_path = Some(dir);
+        }
+    }
+
+    /// Remove the current watch registration (if any), marking live reload inactive.
+    fn drop_watch(&mut self) {
+        if let Some(old) = self.watched_path.take()
+            && let Some(ref mut watcher) = self.watcher
+        {
+            let _ = watcher.unwatch(&old);
+        }
+    }
+
+    /// Whether the cached Config.wtf locales differ from the preferred ones.
+    fn locale_drifted(&self) -> bool {
+        [
+            (&self.audio_locale, &self.preferred_audio_locale),
+            (&self.text_locale, &self.preferred_text_locale),
+        ]
+        .into_iter()
+        .any(|(current, preferred)| {
+            current
+                .as_ref()
+                .is_some_and(|v| !v.eq_ignore_ascii_case(preferred))
+        })
+    }
+
+    /// Schedule an enforcement rewrite if enforcement is on and the locales drifted; every
+    /// further change pushes it back by `limits::get().enforce_debounce()`.
+    fn schedule_enforcement(&mut self) {
+        if self.enforce_locale && !self.viewer() && self.locale_drifted() {
+            self.enforce_due = Some(Instant::now() + limits::get().enforce_debounce());
+        }
+    }
+
+    /// Run a due enforcement rewrite. Returns how long until the pending one is due.
+    fn enforce_locale_if_due(&mut self) -> Option<Duration> {
+        let due = self.enforce_due?;
+        let now = Instant::now();
+        if now < due {
+            return Some(due - now);
+        }
+        self.enforce_due = None;
+        // Re-check: the file may have been fixed (or enforcement disabled) in the meantime
         self.last_config_path = None;
         self.update_locales();
-        Ok(())
+        if !self.enforce_locale || !self.locale_drifted() {
+            return None;
+        }
+        self.status = Some(match self.update_config_file_locales(false) {
+            Ok(()) => {
+                self.enforce_count += 1;
+                format!(
+                    "Config.wtf locale was reset; restored preferred locale ({} time(s))",
+                    self.enforce_count
+                )
+            }
+            Err(e) => format!("Error enforcing locale: {}", e),
+        });
+        None
     }
-}
 
-impl eframe::App for EntitanApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Try to load background.png once (from current working directory). If not present or fails, use embedded default.
-            if !self.background_load_attempted && self.background_texture.is_none() {
-                self.background_load_attempted = true;
-                let mut img_opt: Option<image::DynamicImage> = None;
-
-                // Prefer an external background.png if present (allows overrides without recompiling)
-                let bg_path = std::env::current_dir()
-                    .unwrap_or_else(|_| PathBuf::from("."))
-                    .join("background.png");
-                if bg_path.exists() {
-                    match image::open(&bg_path) {
-                        Ok(img) => {
-                            img_opt = Some(img);
-                        }
-                        Err(e) => {
-                            self.status = Some(format!("Failed to load background.png: {}", e));
-                        }
+    /// Start, move or stop the screenshot organizer to match the setting and WoW path, then
+    /// sort any settled screenshots. Returns how long until the next one settles.
+    fn update_screenshot_organizer(&mut self) -> Option<Duration> {
+        let dir = Some(&self.wow_executable_path)
+            .filter(|p| self.organize_screenshots && !self.viewer() && !p.is_empty())
+            .and_then(|p| Path::new(p).parent().map(|d| d.join("Screenshots")))
+            .filter(|d| d.is_dir());
+        if self.screenshot_organizer.as_ref().map(|o| o.dir()) != dir.as_deref() {
+            self.screenshot_organizer = None;
+            if let Some(dir) = dir {
+                match screenshots::Organizer::start(&dir) {
+                    Ok(organizer) => self.screenshot_organizer = Some(organizer),
+                    Err(e) => {
+                        self.organize_screenshots = false;
+                        self.status = Some(format!("Cannot watch {}: {}", dir.display(), e));
                     }
                 }
+            }
+        }
+        let organizer = self.screenshot_organizer.as_mut()?;
+        // Sort by the language the UI text in the screenshot is in
+        let locale = self
+            .text_locale
+            .as_deref()
+            .and_then(locales::canonical)
+            .unwrap_or(&self.preferred_text_locale)
+            .to_string();
+        for result in organizer.poll(&locale) {
+            self.status = Some(match result {
+                Ok(path) => format!("Moved screenshot to {}", path.display()),
+                Err(e) => format!("Error moving screenshot: {}", e),
+            });
+        }
+        organizer.next_poll_in()
+    }
 
-                // If no external image found, load embedded default
-                if img_opt.is_none() {
-                    match image::load_from_memory(DEFAULT_BACKGROUND_PNG) {
-                        Ok(img) => {
-                            img_opt = Some(img);
-                            self.status = Some("Using embedded default background image".into());
-                        }
-                        Err(e) => {
-                            self.status =
-                                Some(format!("Failed to decode embedded background image: {}", e));
-                        }
-                    }
-                }
+    /// Ask for a destination and write the locale report there.
+    fn export_locale_report(&mut self) {
+        let cfg = Path::new(&self.config_wtf_path);
+        if !is_file_with_ext(cfg, "wtf") {
+            self.status = Some("Config.wtf path must point to an existing .wtf file".into());
+            return;
+        }
+        let Some(out) = FileDialog::new()
+            .add_filter("json", &["json"])
+            .set_file_name("entitan-locale-report.json")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(
+            match report::export(
+                &out,
+                cfg,
+                Path::new(&self.wow_executable_path),
+                &self.preferred_audio_locale,
+                &self.preferred_text_locale,
+            ) {
+                Ok(()) => format!("Wrote locale report to {}", out.display()),
+                Err(e) => format!("Error writing locale report: {}", e),
+            },
+        );
+    }
 
-                if let Some(img) = img_opt {
-                    // convert to RGBA8 and then to grayscale with 10% opacity
-                    let img = img.to_rgba8();
-                    let w = img.width() as usize;
-                    let h = img.height() as usize;
-                    let mut pixels = img.into_vec();
-                    for chunk in pixels.chunks_exact_mut(4) {
-                        let r = chunk[0] as f32;
-                        let g = chunk[1] as f32;
-                        let b = chunk[2] as f32;
-                        let a = chunk[3];
-                        // luminance per Rec. 601
-                        let lum = (0.299 * r + 0.587 * g + 0.114 * b).round() as u8;
-                        chunk[0] = lum;
-                        chunk[1] = lum;
-                        chunk[2] = lum;
-                        // set opacity to 10% of original
-                        chunk[3] = ((a as f32) * 0.1).round() as u8;
-                    }
-                    let size = [w, h];
-                    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &pixels);
-                    let tex =
-                        ctx.load_texture("background", color_image, egui::TextureOptions::LINEAR);
-                    self.background_texture = Some(tex);
-                    self.background_size = Some([w, h]);
-                }
+    /// Look for a running WoW client (at most every `PROCESS_POLL_INTERVAL`) and read the
+    /// locale argument from its command line. Returns the time until the next check.
+    fn check_running_game(&mut self) -> Duration {
+        if let Some(last) = self.last_process_check
+            && last.elapsed() < PROCESS_POLL_INTERVAL
+        {
+            return PROCESS_POLL_INTERVAL - last.elapsed();
+        }
+        let first_check = self.last_process_check.is_none();
+        self.last_process_check = Some(Instant::now());
+        self.check_children();
+        let exe = Path::new(&self.wow_executable_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Wow.exe");
+        let previous = self.running_game.take();
+        let current = processes::find_by_name(exe).into_iter().next();
+        // Start and locale argument of a session that just ended
+        let ended = previous
+            .as_ref()
+            .filter(|game| current.as_ref().is_none_or(|p| p.pid != game.pid))
+            .map(|game| (game.since, game.locale_arg.clone()));
+        self.running_game = current.map(|p| match previous {
+            Some(game) if game.pid == p.pid => game,
+            _ => RunningGame {
+                pid: p.pid,
+                locale_arg: processes::command_line(p.pid)
+                    .as_deref()
+                    .and_then(locales::from_command_line),
+                since: processes::start_time(p.pid).unwrap_or_else(SystemTime::now),
+            },
+        });
+        // The client rewrites AddOns.txt when it exits; also look once at startup, for a
+        // session played without enTitan
+        if self.protect_addons && (ended.is_some() || first_check) {
+            let config = PathBuf::from(&self.config_wtf_path);
+            self.blanked_rx = Some(repaint::spawn(move || addons::find_blanked(&config)));
+        }
+        if self.verify_locale
+            && let Some((since, locale_arg)) = ended
+        {
+            self.verify_loaded_locale(Some(since), locale_arg.as_deref());
+        }
+        if let Some((launched, seen)) = self.wow_launch.as_mut() {
+            if launched.elapsed() > limits::get().early_exit() {
+                self.wow_launch = None;
+            } else if self.running_game.is_some() {
+                *seen = true;
+            } else if *seen {
+                self.wow_launch = None;
+                let programs = self
+                    .firewall_programs()
+                    .iter()
+                    .map(|(n, p)| (n.to_string(), p.to_string()))
+                    .collect();
+                self.firewall_check_rx = Some(firewall::rules_exist_async(programs));
             }
+        }
+        PROCESS_POLL_INTERVAL
+    }
 
-            // Paint background if we have it (preserve aspect ratio, cover, center crop)
-            if let Some(ref tex) = self.background_texture {
-                let rect = ui.max_rect();
-                if let Some([img_w, img_h]) = self.background_size {
-                    let img_w_f = img_w as f32;
-                    let img_h_f = img_h as f32;
-                    let rect_w = rect.width();
-                    let rect_h = rect.height();
-                    // scale so the image covers the rect
-                    let scale = f32::max(rect_w / img_w_f, rect_h / img_h_f);
-                    // visible size in texture pixels
-                    let visible_w = rect_w / scale;
-                    let visible_h = rect_h / scale;
-                    let u0 = ((img_w_f - visible_w) / 2.0) / img_w_f;
-                    let v0 = ((img_h_f - visible_h) / 2.0) / img_h_f;
-                    let u1 = u0 + visible_w / img_w_f;
-                    let v1 = v0 + visible_h / img_h_f;
-                    let uv_rect = egui::Rect::from_min_max(egui::pos2(u0, v0), egui::pos2(u1, v1));
-                    ui.painter()
-                        .image(tex.id(), rect, uv_rect, egui::Color32::WHITE);
-                } else {
-                    ui.painter().image(
-                        tex.id(),
-                        rect,
-                        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
-                        egui::Color32::WHITE,
-                    );
-                }
+    /// Report whether the client ran in the preferred text locale, judged from the files it
+    /// wrote since `since` (any time if None), with fixes for a mismatch.
+    fn verify_loaded_locale(&mut self, since: Option<SystemTime>, locale_arg: Option<&str>) {
+        let wow = Path::new(&self.wow_executable_path);
+        let msg = match verify::detect(wow, since) {
+            None => "Verify locale: the client left no trace of its locale (no Cache/WDB or Logs entries since it started)".to_string(),
+            Some(found) if found.locale.eq_ignore_ascii_case(&self.preferred_text_locale) => {
+                format!(
+                    "Verified: the client ran in {} (seen in {})",
+                    found.locale, found.source
+                )
             }
+            Some(found) => format!(
+                "Locale mismatch: the client ran in {}, not {} (seen in {}). {}",
+                found.locale,
+                self.preferred_text_locale,
+                found.source,
+                verify::suggestions(
+                    wow,
+                    &self.preferred_text_locale,
+                    locale_arg,
+                    self.sync_battle_net_config,
+                    self.enforce_locale,
+                )
+                .join("; ")
+            ),
+        };
+        self.log_status(eventlog::Kind::Status, msg);
+    }
 
-            // refresh cached locales if config path changed
-            self.update_locales();
+    /// Banner offering to restore AddOns.txt files the client blanked after a locale switch.
+    fn blanked_addons_banner(&mut self, ui: &mut egui::Ui) {
+        if self.blanked_addons.is_empty() {
+            return;
+        }
+        ui.horizontal(|ui| {
+            let characters: Vec<&str> = self
+                .blanked_addons
+                .iter()
+                .map(|b| b.character.as_str())
+                .collect();
+            ui.colored_label(
+                theme::bad(ui),
+                format!(
+                    "⚠ Addons were disabled after the locale switch for {}",
+                    characters.join(", ")
+                ),
+            );
+            if ui
+                .add_enabled(
+                    self.running_game.is_none(),
+                    egui::Button::new(tr("Restore")),
+                )
+                .on_hover_text("Put back the AddOns.txt saved before the locale was written")
+                .on_disabled_hover_text("Close WoW first; it rewrites AddOns.txt when it exits")
+                .confirmed(self.confirm_writes)
+            {
+                self.status = Some(match addons::restore(&self.blanked_addons) {
+                    Ok(count) => format!("Restored AddOns.txt of {} character(s)", count),
+                    Err(e) => format!("Error restoring AddOns.txt: {}", e),
+                });
+                self.blanked_addons.clear();
+            }
+            if ui
+                .button("Dismiss")
+                .on_hover_text("Keep the addon lists as they are now")
+                .clicked()
+            {
+                addons::discard(&self.blanked_addons);
+                self.blanked_addons.clear();
+            }
+        });
+    }
 
-            // update cached window geometry (so we can save on close without access to frame later)
-            let size = ctx.input(|i| i.content_rect().size());
-            self.last_inner_size = Some((size.x, size.y));
-            // update last_window_pos each frame too
-            self.last_window_pos = get_window_position(_frame);
+    /// Log launched programs that have exited, with their exit code; early exits and
+    /// failures also go to the status line.
+    fn check_children(&mut self) {
+        let mut i = 0;
+        while i < self.children.len() {
+            let launched = &mut self.children[i];
+            let Some(code) = launched.child.exited() else {
+                i += 1;
+                continue;
+            };
+            let launched = self.children.remove(i);
+            let ran = launched.started.elapsed();
+            let msg = format!(
+                "{} (pid {}) exited after {} s {}",
+                launched.name,
+                launched.child.pid(),
+                ran.as_secs(),
+                launch::describe_exit(code)
+            );
+            self.event_log.push(eventlog::Kind::Run, msg.clone());
+            if ran < limits::get().early_exit() || code != Some(0) {
+                self.status = Some(msg);
+                self.logged_status = self.status.clone();
+            }
+        }
+    }
 
-            ui.vertical(|ui| {
-                // Top labels for game language (left-aligned and not stretched)
-                let label_w = 140.0;
-                let btn_w = 80.0;
-                let gap = 6.0;
-                let right_pad = 8.0; // reserve an explicit right padding for buttons below
-                let total_avail = ui.available_width();
-                let btn_count_max = 2.0; // reserve for up to two buttons (Browse + Run)
-                let text_w =
-                    (total_avail - label_w - btn_w * btn_count_max - gap - right_pad).max(8.0);
+    /// Start the hosts/DNS diagnosis of the realm hostname in the background.
+    fn check_realm_host(&mut self) {
+        let Some((host, port)) = self.realm_target() else {
+            self.status = Some("Realm: no hostname set and Config.wtf has no realmList".into());
+            return;
+        };
+        self.hosts_diagnosis = None;
+        self.hosts_rx = Some(hosts::diagnose_async(host, port));
+    }
 
-                // audioLocale row (aligned and colored; value left-aligned to textfield column)
-                ui.horizontal(|ui| {
-                    ui.add_sized([label_w, 24.0], egui::Label::new("audioLocale:"));
-                    let a = self.audio_locale.as_deref().unwrap_or("(not available)");
-                    let a_color = if self
-                        .audio_locale
-                        .as_deref()
-                        .map(|v| v.eq_ignore_ascii_case(&self.preferred_locale))
-                        .unwrap_or(false)
-                    {
-                        egui::Color32::from_rgb(0, 160, 0)
+    /// Findings of the last hosts/DNS check, with the fix for stale hosts entries.
+    fn hosts_results(&mut self, ui: &mut egui::Ui, indent: f32) {
+        let Some(diagnosis) = &self.hosts_diagnosis else {
+            return;
+        };
+        let stale = diagnosis.stale.clone();
+        for result in &diagnosis.results {
+            ui.horizontal(|ui| {
+                ui.add_space(indent);
+                match result {
+                    Ok(msg) => ui.colored_label(theme::good(ui), msg),
+                    Err(msg) => ui.colored_label(theme::bad(ui), msg),
+                };
+            });
+        }
+        if stale.is_empty() {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.add_space(indent);
+            let button = ui.add_enabled(
+                self.hosts_fix_rx.is_none(),
+                egui::Button::new("Disable stale hosts entries…"),
+            );
+            if button.confirmed(self.confirm_writes) {
+                self.refocus(&button);
+                let confirmed = rfd::MessageDialog::new()
+                    .set_title("Disable stale hosts entries")
+                    .set_description(format!(
+                        "enTitan will comment out line(s) {} of {} so the realm hostname resolves normally again. A copy of the current file is kept in enTitan's settings folder.\n\nWindows will ask for administrator permission to change this file.",
+                        stale
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        hosts::hosts_path().display()
+                    ))
+                    .set_buttons(rfd::MessageButtons::OkCancel)
+                    .show();
+                if confirmed == rfd::MessageDialogResult::Ok {
+                    self.hosts_fix_rx = Some(hosts::disable_lines_async(stale));
+                }
+            }
+        });
+    }
+
+    fn path(&self, field: PathField) -> &String {
+        match field {
+            PathField::BattleNet => &self.battle_net_path,
+            PathField::ConfigWtf => &self.config_wtf_path,
+            PathField::Wow => &self.wow_executable_path,
+        }
+    }
+
+    /// The recent path lists with the current values of the path fields put first.
+    fn recent_paths_snapshot(&self) -> recent::RecentPaths {
+        let mut recent = self.recent_paths.clone();
+        for field in PathField::ALL {
+            recent.remember(field, self.path(field));
+        }
+        recent
+    }
+
+    /// Replace the value of a path field, keeping the old one in its drop-down.
+    fn set_path(&mut self, field: PathField, path: String) {
+        self.recent_paths = self.recent_paths_snapshot();
+        match field {
+            PathField::BattleNet => self.battle_net_path = path,
+            PathField::ConfigWtf => self.config_wtf_path = path,
+            PathField::Wow => self.wow_executable_path = path,
+        }
+    }
+
+    /// Whether viewer mode is on, by setting or by `--viewer`.
+    fn viewer(&self) -> bool {
+        self.viewer_mode || self.viewer_forced
+    }
+
+    /// Show `msg` in the status line and record it in the event log right away, so a later
+    /// message in the same frame (e.g. a countdown) can't replace it unlogged.
+    fn log_status(&mut self, kind: eventlog::Kind, msg: String) {
+        self.event_log.push(kind, msg.clone());
+        self.status = Some(msg);
+        self.logged_status = self.status.clone();
+    }
+
+    /// Whether animations are off, by setting or by the OS preference.
+    fn reduce_motion(&self) -> bool {
+        self.disable_animations || self.os_reduced_motion
+    }
+
+    fn locked(&self, field: PathField) -> bool {
+        match field {
+            PathField::BattleNet => self.battle_net_locked,
+            PathField::ConfigWtf => self.config_wtf_locked,
+            PathField::Wow => self.wow_locked,
+        }
+    }
+
+    /// Re-validate the stored paths at startup and every `PATH_CHECK_INTERVAL`, so a changed
+    /// drive letter or renamed folder shows up before Run. Returns the time until the next
+    /// check. Unset paths aren't reported; the rows ask for those already.
+    fn check_paths(&mut self) -> Duration {
+        if let Some(last) = self.last_path_check
+            && last.elapsed() < PATH_CHECK_INTERVAL
+        {
+            // Drop entries that were fixed in the meantime
+            let valid: Vec<PathField> = self
+                .invalid_paths
+                .iter()
+                .copied()
+                .filter(|&f| is_file_with_ext(self.path(f), f.ext()))
+                .collect();
+            self.invalid_paths.retain(|f| !valid.contains(f));
+            return PATH_CHECK_INTERVAL - last.elapsed();
+        }
+        self.last_path_check = Some(Instant::now());
+        self.invalid_paths = PathField::ALL
+            .into_iter()
+            .filter(|&f| !self.path(f).is_empty() && !is_file_with_ext(self.path(f), f.ext()))
+            .collect();
+        PATH_CHECK_INTERVAL
+    }
+
+    /// Let the user pick a new file for a path the check reported. A locked field is left
+    /// alone.
+    fn fix_path(&mut self, field: PathField) {
+        if self.locked(field) {
+            self.status = Some(format!("{} is locked; unlock it first", field.label()));
+            return;
+        }
+        self.pending_focus = Some(field.id());
+        let mut dialog = FileDialog::new().add_filter(field.ext(), &[field.ext()]);
+        // Start from the nearest folder of the old path that still exists
+        if let Some(dir) = Path::new(self.path(field))
+            .ancestors()
+            .skip(1)
+            .find(|d| d.is_dir())
+        {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(file) = dialog.pick_file() else {
+            return;
+        };
+        if !is_file_with_ext(&file, field.ext()) {
+            self.status = Some(format!("Selected file is not a .{} file", field.ext()));
+            return;
+        }
+        self.set_path(field, file.display().to_string());
+        self.invalid_paths.retain(|&f| f != field);
+        self.status = Some("Selected (unsaved)".into());
+    }
+
+    /// `(rule name, executable)` of the programs the firewall helper allows.
+    fn firewall_programs(&self) -> [(&str, &str); 2] {
+        [
+            ("Battle.net", &self.battle_net_path),
+            ("WoW", &self.wow_executable_path),
+        ]
+    }
+
+    /// Explain the elevation prompt, then create the firewall rules in the background.
+    fn create_firewall_rules(&mut self) {
+        let missing = self
+            .firewall_programs()
+            .into_iter()
+            .find(|(_, path)| !is_file_with_ext(path, "exe"))
+            .map(|(name, _)| name.to_string());
+        if let Some(name) = missing {
+            self.status = Some(format!(
+                "Firewall: the {} path must point to an existing .exe",
+                name
+            ));
+            return;
+        }
+        let confirmed = rfd::MessageDialog::new()
+            .set_title("Allow Battle.net and WoW through the firewall")
+            .set_description(
+                "enTitan will add Windows Defender Firewall rules allowing Battle.net and WoW to connect.\n\nWindows will ask for administrator permission to do this.",
+            )
+            .set_buttons(rfd::MessageButtons::OkCancel)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Ok {
+            return;
+        }
+        let programs = self
+            .firewall_programs()
+            .iter()
+            .map(|(n, p)| (n.to_string(), p.to_string()))
+            .collect();
+        self.status = Some("Firewall: waiting for administrator permission...".into());
+        self.firewall_rx = Some(firewall::allow_async(programs));
+    }
+
+    /// Ask for a destination and save settings, profiles and the event log as one zip.
+    fn backup_app(&mut self) {
+        // The archive holds the settings file, so bring it up to date first
+        if let Err(e) = self.save_now() {
+            self.status = Some(format!("Error saving settings before the backup: {}", e));
+            return;
+        }
+        let name = format!(
+            "entitan-backup-{}.zip",
+            &eventlog::format_time(eventlog::now())[..10]
+        );
+        let Some(out) = FileDialog::new()
+            .add_filter("zip", &["zip"])
+            .set_file_name(&name)
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match backup::create(&out) {
+            Ok(count) => format!("Backed up {} files to {}", count, out.display()),
+            Err(e) => format!("Error creating backup: {}", e),
+        });
+    }
+
+    /// Prefix the window title with the run's state ("[45s] enTitan …") while one is active,
+    /// so it can be followed from the taskbar.
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let title = match &self.run {
+            Some(run) => {
+                let state = if run.is_paused() {
+                    "paused".to_string()
+                } else if let Some(left) = run.countdown() {
+                    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+                    if secs < 60 {
+                        format!("{}s", secs)
                     } else {
-                        egui::Color32::from_rgb(200, 0, 0)
+                        format!("{}m {:02}s", secs / 60, secs % 60)
+                    }
+                } else if run.confirmation_prompt().is_some() {
+                    "waiting for you".to_string()
+                } else {
+                    "running".to_string()
+                };
+                format!("[{}] {}", state, self.window_title)
+            }
+            None => self.window_title.clone(),
+        };
+        if self.shown_title.as_ref() != Some(&title) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.shown_title = Some(title);
+        }
+    }
+
+    /// Start over from the files in the settings directory, keeping the window where it is.
+    fn reload(&mut self) {
+        *self = EntitanApp {
+            news_url: self.news_url.take(),
+            window_title: std::mem::take(&mut self.window_title),
+            last_inner_size: self.last_inner_size,
+            last_window_pos: self.last_window_pos,
+            last_scale: self.last_scale,
+            last_maximized: self.last_maximized,
+            ..Default::default()
+        };
+    }
+
+    /// After confirmation, set settings.json aside and start over from the first-run
+    /// defaults with the window at its default size. Profiles and the run history are kept.
+    fn reset_to_defaults(&mut self, ctx: &egui::Context) {
+        let confirmed = rfd::MessageDialog::new()
+            .set_title("Reset enTitan settings")
+            .set_description(format!(
+                "Reset all settings to their defaults? Paths, locales, options and the saved window position are cleared; profiles and the run history are kept.\n\nThe current settings are kept as {}.",
+                SETTINGS_BEFORE_RESET
+            ))
+            .set_buttons(rfd::MessageButtons::OkCancel)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Ok {
+            return;
+        }
+        let Some(path) = settings_file_path() else {
+            self.status = Some("Error resetting settings: cannot determine settings path".into());
+            return;
+        };
+        if path.exists()
+            && let Err(e) = fs::rename(&path, path.with_file_name(SETTINGS_BEFORE_RESET))
+        {
+            self.status = Some(format!("Error resetting settings: {}", e));
+            return;
+        }
+        // Otherwise a damaged file later would bring the old settings back
+        let _ = fs::remove_file(path.with_file_name(SETTINGS_BACKUP));
+        *self = EntitanApp {
+            news_url: self.news_url.take(),
+            window_title: std::mem::take(&mut self.window_title),
+            ..Default::default()
+        };
+        ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(
+            limits::get().min_window_width,
+            limits::get().min_window_height,
+        )));
+        self.status = Some(format!(
+            "Settings reset to defaults; the previous ones are in {}",
+            SETTINGS_BEFORE_RESET
+        ));
+    }
+
+    /// Use `dir` (the default folder when None, else one picked) as the settings folder. The
+    /// current files are copied there unless it already holds settings the user wants to keep,
+    /// in which case those are loaded instead.
+    fn move_settings_dir(&mut self, dir: Option<PathBuf>) {
+        let Some(dir) = dir.or_else(|| FileDialog::new().pick_folder()) else {
+            return;
+        };
+        if settings_dir().as_deref() == Some(dir.as_path()) {
+            return;
+        }
+        // Files already there (e.g. synced from another PC) may be the ones to keep
+        let keep_existing = if dir.join("settings.json").is_file() {
+            match rfd::MessageDialog::new()
+                .set_title("Settings folder")
+                .set_description(format!(
+                    "{} already holds enTitan settings.\n\nYes: use those settings.\nNo: replace them with the current ones.",
+                    dir.display()
+                ))
+                .set_buttons(rfd::MessageButtons::YesNoCancel)
+                .show()
+            {
+                rfd::MessageDialogResult::Yes => true,
+                rfd::MessageDialogResult::No => false,
+                _ => return,
+            }
+        } else {
+            false
+        };
+        // The copy should carry the latest values
+        if !keep_existing && let Err(e) = self.save_now() {
+            self.status = Some(format!("Error saving settings before the move: {}", e));
+            return;
+        }
+        match location::relocate(&dir, !keep_existing) {
+            Ok(copied) => {
+                if keep_existing {
+                    self.reload();
+                }
+                self.status = Some(if keep_existing {
+                    format!("Using the settings in {}", dir.display())
+                } else {
+                    format!(
+                        "Settings folder is now {} ({} file(s) copied)",
+                        dir.display(),
+                        copied
+                    )
+                });
+            }
+            Err(e) => self.status = Some(format!("Error changing the settings folder: {}", e)),
+        }
+        self.event_log.push(
+            eventlog::Kind::Status,
+            self.status.clone().unwrap_or_default(),
+        );
+    }
+
+    /// Ask for a destination and save the settings and profiles as a bundle for another PC.
+    fn export_bundle(&mut self) {
+        let settings = match serde_json::to_value(self.settings_snapshot()) {
+            Ok(settings) => settings,
+            Err(e) => {
+                self.status = Some(format!("Error exporting settings: {}", e));
+                return;
+            }
+        };
+        let Some(out) = FileDialog::new()
+            .add_filter("zip", &["zip"])
+            .set_file_name("entitan-settings.zip")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match bundle::export(&out, &settings) {
+            Ok(profiles) => format!(
+                "Exported settings and {} profile(s) to {}",
+                profiles,
+                out.display()
+            ),
+            Err(e) => format!("Error exporting settings: {}", e),
+        });
+    }
+
+    /// Take over the settings and profiles of a bundle, after confirmation, and reload.
+    fn import_bundle(&mut self) {
+        let Some(archive) = FileDialog::new().add_filter("zip", &["zip"]).pick_file() else {
+            return;
+        };
+        let profiles = match bundle::profiles(&archive) {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                self.status = Some(format!("Error importing settings: {}", e));
+                return;
+            }
+        };
+        let mut description = format!(
+            "Replace the current settings with those in {}?\n\nLaunch hooks, custom steps, \
+             elevation and the webhook are never imported; yours are kept.",
+            archive.display()
+        );
+        if !profiles.is_empty() {
+            description.push_str(&format!(
+                "\n\nProfiles {} will be added, replacing any of the same name.",
+                profiles.join(", ")
+            ));
+        }
+        let confirmed = rfd::MessageDialog::new()
+            .set_title("Import enTitan settings")
+            .set_description(description)
+            .set_buttons(rfd::MessageButtons::OkCancel)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Ok {
+            return;
+        }
+        match bundle::import(&archive) {
+            Ok(count) => {
+                self.reload();
+                self.status = Some(format!(
+                    "Imported settings and {} profile(s) from {}",
+                    count,
+                    archive.display()
+                ));
+            }
+            Err(e) => self.status = Some(format!("Error importing settings: {}", e)),
+        }
+    }
+
+    /// Replace settings, profiles and the event log with those of a backup and reload them.
+    fn restore_backup(&mut self) {
+        let Some(archive) = FileDialog::new().add_filter("zip", &["zip"]).pick_file() else {
+            return;
+        };
+        let confirmed = rfd::MessageDialog::new()
+            .set_title("Restore enTitan backup")
+            .set_description(format!(
+                "Replace the current settings, profiles and run history with those in {}?",
+                archive.display()
+            ))
+            .set_buttons(rfd::MessageButtons::OkCancel)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Ok {
+            return;
+        }
+        match backup::restore(&archive) {
+            Ok(count) => {
+                self.reload();
+                self.status = Some(format!(
+                    "Restored {} files from {}",
+                    count,
+                    archive.display()
+                ));
+            }
+            Err(e) => self.status = Some(format!("Error restoring backup: {}", e)),
+        }
+    }
+
+    /// Pick another launcher's .ini and take over its paths, arguments, realm and locale
+    /// (unsaved, like Browse). Locked path fields are left alone.
+    fn import_legacy(&mut self) {
+        let Some(file) = FileDialog::new()
+            .add_filter("Launcher settings", &["ini", "cfg", "txt"])
+            .pick_file()
+        else {
+            return;
+        };
+        let imported = match legacy::read_ini(&file) {
+            Ok(imported) if imported.is_empty() => {
+                self.status = Some(format!(
+                    "No launcher settings recognized in {}",
+                    file.display()
+                ));
+                return;
+            }
+            Ok(imported) => imported,
+            Err(e) => {
+                self.status = Some(format!("Error importing launcher settings: {}", e));
+                return;
+            }
+        };
+        let mut taken = Vec::new();
+        let mut skipped = Vec::new();
+        for (field, value) in [
+            (PathField::BattleNet, imported.battle_net),
+            (PathField::ConfigWtf, imported.config_wtf),
+            (PathField::Wow, imported.wow),
+        ] {
+            let Some(value) = value else {
+                continue;
+            };
+            if self.locked(field) {
+                skipped.push(field.label());
+                continue;
+            }
+            self.set_path(field, value);
+            taken.push(field.label());
+        }
+        if let Some(args) = imported.wow_args {
+            self.wow_args = args;
+            taken.push("WoW arguments");
+        }
+        if let Some(realm) = imported.realm_host {
+            self.realm_host = realm;
+            taken.push("Realm");
+        }
+        if let Some(locale) = imported.locale {
+            self.preferred_audio_locale = locale.to_string();
+            self.preferred_text_locale = locale.to_string();
+            taken.push("Preferred locale");
+        }
+        let mut status = format!("Imported {} (unsaved)", taken.join(", "));
+        if !skipped.is_empty() {
+            status.push_str(&format!("; kept locked {}", skipped.join(", ")));
+        }
+        self.status = Some(status);
+        self.update_locales();
+    }
+
+    /// Ask for a destination and write the full cvar set there as JSON or TOML.
+    fn export_cvars(&mut self) {
+        let cfg = Path::new(&self.config_wtf_path);
+        if !is_file_with_ext(cfg, "wtf") {
+            self.status = Some("Config.wtf path must point to an existing .wtf file".into());
+            return;
+        }
+        let Some(out) = FileDialog::new()
+            .add_filter("json", &["json"])
+            .add_filter("toml", &["toml"])
+            .set_file_name("config.json")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match cvarfile::export(cfg, &out) {
+            Ok(count) => format!("Exported {} cvars to {}", count, out.display()),
+            Err(e) => format!("Error exporting cvars: {}", e),
+        });
+    }
+
+    /// Pick a JSON or TOML cvar file and merge it into Config.wtf through `write_cvars`
+    /// (undoable).
+    fn import_cvars(&mut self) {
+        let cfg = PathBuf::from(&self.config_wtf_path);
+        if !is_file_with_ext(&cfg, "wtf") {
+            self.status = Some("Config.wtf path must point to an existing .wtf file".into());
+            return;
+        }
+        let Some(file) = FileDialog::new()
+            .add_filter("cvars", &["json", "toml"])
+            .pick_file()
+        else {
+            return;
+        };
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        let result = cvarfile::read(&file).and_then(|cvars| {
+            let changed = cvarfile::count_changes(&cfg, &cvars)?;
+            let cvars: Vec<(&str, &str)> = cvars
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            self.write_cvars(&cvars, format!("import {}", name), false)?;
+            Ok(changed)
+        });
+        self.status = Some(match result {
+            Ok(changed) => format!("Imported {}: {} cvar(s) changed", name, changed),
+            Err(e) => format!("Error importing cvars: {}", e),
+        });
+    }
+
+    /// Steps of the launch sequence: Battle.net, (confirm,) wait, WoW, wait, Battle.net again.
+    fn launch_steps(&self) -> Vec<sequence::Step> {
+        let mut steps = Vec::new();
+        if !self.pre_launch_hook.trim().is_empty() {
+            steps.push(sequence::Step::Hook {
+                label: "Pre-launch hook".into(),
+                command: self.pre_launch_hook.clone(),
+                critical: self.pre_launch_hook_critical,
+            });
+        }
+        if self.latency_check
+            && let Some((host, port)) = self.realm_target()
+        {
+            steps.push(sequence::Step::CheckLatency {
+                host,
+                port,
+                threshold: Duration::from_millis(self.latency_threshold_ms),
+                abort: self.latency_abort,
+            });
+        }
+        steps.extend(self.game_steps());
+        if !self.post_launch_hook.trim().is_empty() {
+            steps.push(sequence::Step::Hook {
+                label: "Post-launch hook".into(),
+                command: self.post_launch_hook.clone(),
+                critical: self.post_launch_hook_critical,
+            });
+        }
+        steps
+    }
+
+    /// Host and port of the realm: the Realm setting, else Config.wtf's realmList.
+    fn realm_target(&self) -> Option<(String, u16)> {
+        if self.realm_host.trim().is_empty() {
+            hosts::parse_realm_list(self.realm_list.as_deref()?)
+        } else {
+            hosts::parse_realm_list(&self.realm_host)
+        }
+    }
+
+    /// Launch options of other programs, Battle.net and WoW.
+    fn launch_options(
+        &self,
+    ) -> (
+        launch::LaunchOptions,
+        launch::LaunchOptions,
+        launch::LaunchOptions,
+    ) {
+        let options = launch::LaunchOptions {
+            de_elevate: self.de_elevate_children,
+            env_remove: if self.sanitize_environment {
+                launch::split_env_patterns(&self.environment_blocklist)
+            } else {
+                Vec::new()
+            },
+            ..Default::default()
+        };
+        let battle_net_options = launch::LaunchOptions {
+            elevate: self.battle_net_elevated,
+            ..options.clone()
+        };
+        let wow_options = launch::LaunchOptions {
+            elevate: self.wow_elevated,
+            priority: self.wow_priority,
+            affinity: self.wow_affinity,
+            ..options.clone()
+        };
+        (options, battle_net_options, wow_options)
+    }
+
+    /// Start just the executable of `field` with its arguments and options, without the
+    /// sequence, to tell which half of a failing run is broken. Its exit is reported like
+    /// that of a run's programs.
+    fn test_launch(&mut self, field: PathField) {
+        if self.viewer() {
+            self.status = Some(format!("Test not started: {}", VIEWER_REFUSAL));
+            return;
+        }
+        let (_, battle_net_options, wow_options) = self.launch_options();
+        let (name, args, options) = match field {
+            PathField::BattleNet => ("Battle.net", &self.battle_net_args, battle_net_options),
+            PathField::Wow => ("WoW", &self.wow_args, wow_options),
+            PathField::ConfigWtf => return,
+        };
+        let path = self.path(field).clone();
+        if !is_file_with_ext(&path, "exe") {
+            self.status = Some(format!(
+                "Test: {} must point to an existing .exe",
+                field.label()
+            ));
+            return;
+        }
+        let args = launch::split_args(args);
+        if options.elevate {
+            // The UAC prompt blocks the start until answered
+            self.status = Some(format!("Test: starting {} as administrator...", name));
+            self.test_launch_rx = Some(repaint::spawn(move || {
+                let started = launch::spawn(&path, &args, &options);
+                (name, options, started)
+            }));
+            return;
+        }
+        let started = launch::spawn(&path, &args, &options);
+        self.test_started(name, &options, started);
+    }
+
+    /// Report the start of a test launch and watch the program for its exit.
+    fn test_started(
+        &mut self,
+        name: &'static str,
+        options: &launch::LaunchOptions,
+        started: std::io::Result<launch::Child>,
+    ) {
+        let msg = match started {
+            Ok(child) => {
+                let msg = format!("Test: started {} (pid {})", name, child.pid());
+                if (options.priority != launch::Priority::Normal || options.affinity != 0)
+                    && let Err(e) =
+                        processes::set_scheduling(child.pid(), options.priority, options.affinity)
+                {
+                    self.event_log.push(
+                        eventlog::Kind::Run,
+                        format!("Test: {} scheduling not applied: {}", name, e),
+                    );
+                }
+                self.children.push(LaunchedChild {
+                    name: name.into(),
+                    child,
+                    started: Instant::now(),
+                });
+                msg
+            }
+            Err(e) => format!("Test: {} failed to start: {}", name, e),
+        };
+        self.log_status(eventlog::Kind::Run, msg);
+    }
+
+    /// The launch steps proper: the custom sequence, or Battle.net → WoW → Battle.net.
+    fn game_steps(&self) -> Vec<sequence::Step> {
+        use sequence::Step;
+        let (options, battle_net_options, wow_options) = self.launch_options();
+        if self.use_custom_sequence {
+            let exes = script::Executables {
+                battle_net: &self.battle_net_path,
+                battle_net_args: &self.battle_net_args,
+                battle_net_options: &battle_net_options,
+                wow: &self.wow_executable_path,
+                wow_args: &self.wow_args,
+                wow_options: &wow_options,
+            };
+            return self
+                .custom_steps
+                .iter()
+                .map(|s| s.to_step(&exes, &options))
+                .collect();
+        }
+        let battle_net_args = launch::split_args(&self.battle_net_args);
+        let mut steps = vec![Step::Launch {
+            name: "Battle.net".into(),
+            path: self.battle_net_path.clone(),
+            args: battle_net_args.clone(),
+            options: battle_net_options.clone(),
+            skip_if_running: self.skip_running_programs,
+        }];
+        if self.process_aware_waits {
+            steps.push(Step::WaitForProcess {
+                label: if self.wait_for_login_window {
+                    "Waiting for the Battle.net window".into()
+                } else {
+                    "Waiting for Battle.net to start".into()
+                },
+                process: BATTLE_NET_PROCESS.into(),
+                window: self.wait_for_login_window,
+                timeout: limits::get().process_wait_timeout(),
+            });
+        }
+        if self.confirm_login {
+            steps.push(Step::Confirm {
+                message: "Log into Battle.net, then press Continue".into(),
+            });
+        }
+        steps.push(Step::Wait {
+            label: "Waiting to launch WoW".into(),
+            duration: Duration::from_secs(self.wow_launch_delay_secs),
+        });
+        if self.write_locale_before_wow {
+            steps.push(Step::WriteLocale);
+        }
+        steps.push(Step::Launch {
+            name: "WoW".into(),
+            path: self.wow_executable_path.clone(),
+            args: launch::split_args(&self.wow_args),
+            options: wow_options,
+            skip_if_running: self.skip_running_programs,
+        });
+        if self.process_aware_waits
+            && let Some(wow) = Path::new(&self.wow_executable_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+        {
+            steps.push(Step::WaitForProcess {
+                label: "Waiting for WoW to start".into(),
+                process: wow.into(),
+                window: false,
+                timeout: limits::get().process_wait_timeout(),
+            });
+        }
+        if self.close_battle_net_after_wow {
+            steps.push(Step::Close {
+                process: BATTLE_NET_PROCESS.into(),
+                timeout: limits::get().close_timeout(),
+            });
+            return steps;
+        }
+        steps.push(Step::Wait {
+            label: "Waiting before re-launching Battle.net".into(),
+            duration: Duration::from_secs(self.relaunch_delay_secs),
+        });
+        if self.relaunch_wait_for_idle {
+            steps.push(Step::WaitForIdle {
+                label: "Waiting for you to stop typing before re-launching Battle.net".into(),
+                idle: Duration::from_secs(self.relaunch_idle_secs),
+            });
+        }
+        steps.push(Step::Launch {
+            name: "Battle.net (second)".into(),
+            path: self.battle_net_path.clone(),
+            args: battle_net_args,
+            options: battle_net_options,
+            // Launching the running Battle.net again brings it to the foreground
+            skip_if_running: false,
+        });
+        steps
+    }
+
+    /// Why the run can't start with the current paths/sequence, if it can't.
+    fn run_problem(&self) -> Option<String> {
+        if self.viewer() {
+            return Some(format!("Not started: {}", VIEWER_REFUSAL));
+        }
+        if self.use_custom_sequence {
+            if let Some(path) = self.custom_steps.iter().find_map(|s| match s {
+                script::ScriptStep::Launch { program, .. } => {
+                    Some(program.path(&self.battle_net_path, &self.wow_executable_path))
+                        .filter(|p| !is_file_with_ext(Path::new(p), "exe"))
+                }
+                _ => None,
+            }) {
+                return Some(format!("Run sequence: {:?} is not an existing .exe", path));
+            }
+            if self.custom_steps.is_empty() {
+                return Some("Run sequence has no steps".into());
+            }
+            return None;
+        }
+        let p1 = Path::new(&self.battle_net_path);
+        let p2 = Path::new(&self.wow_executable_path);
+        if !(p1.exists() && is_file_with_ext(p1, "exe")) {
+            Some("Battle.net path must point to an existing .exe".into())
+        } else if !(p2.exists() && is_file_with_ext(p2, "exe")) {
+            Some("WoW Executable must point to an existing .exe".into())
+        } else {
+            None
+        }
+    }
+
+    /// Start the scheduled run once it is due: apply the preferred locale, then run the
+    /// sequence. Returns when to check again while armed.
+    fn check_schedule(&mut self, frame: &eframe::Frame) -> Option<Duration> {
+        let at = self.scheduled_run?;
+        if let Ok(left) = at.duration_since(SystemTime::now()) {
+            // Once a second for the countdown
+            return Some(left.min(Duration::from_secs(1)));
+        }
+        self.scheduled_run = None;
+        if self.run.is_some() {
+            self.status = Some("Scheduled run skipped: a run is already active".into());
+            return None;
+        }
+        if let Some(problem) = self.run_problem() {
+            self.status = Some(format!("Scheduled run not started: {}", problem));
+            return None;
+        }
+        self.event_log
+            .push(eventlog::Kind::Run, "Scheduled run is due");
+        if let Err(e) = self.update_config_file_locales(false) {
+            // The run may still write the locale itself; don't miss the slot over it
+            self.event_log.push(
+                eventlog::Kind::Run,
+                format!("Scheduled run: locale not applied: {}", e),
+            );
+        }
+        self.start_run(frame);
+        None
+    }
+
+    /// Scheduled run row: time of day and Arm, or the countdown and Disarm.
+    fn schedule_controls(&mut self, ui: &mut egui::Ui, label_w: f32) {
+        ui.horizontal(|ui| {
+            let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Schedule:")));
+            if let Some(at) = self.scheduled_run {
+                let left = at
+                    .duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO);
+                ui.label(format!(
+                    "Run at {} starts in {}",
+                    self.scheduled_time.trim(),
+                    schedule::format_countdown(left)
+                ));
+                if ui.button(tr("Disarm")).clicked() {
+                    self.scheduled_run = None;
+                    self.event_log
+                        .push(eventlog::Kind::Run, "Scheduled run disarmed");
+                    self.status = Some("Scheduled run disarmed".into());
+                }
+                return;
+            }
+            ui.add_sized(
+                [60.0, 24.0],
+                egui::TextEdit::singleline(&mut self.scheduled_time).hint_text("05:55"),
+            )
+            .labelled_by(label.id)
+            .on_hover_text("Local time of day (24-hour HH:MM) to start the run sequence at");
+            let time = schedule::parse_time(&self.scheduled_time);
+            if ui
+                .add_enabled(time.is_some(), egui::Button::new(tr("Arm")))
+                .on_hover_text(
+                    "At that time, write the preferred locale and run the sequence; keep enTitan open until then",
+                )
+                .clicked()
+                && let Some((hour, minute)) = time
+            {
+                self.scheduled_time = format!("{:02}:{:02}", hour, minute);
+                self.scheduled_run = Some(schedule::next_occurrence(hour, minute));
+                let msg = format!("Scheduled run armed for {}", self.scheduled_time);
+                self.event_log.push(eventlog::Kind::Run, msg.clone());
+                self.status = Some(msg);
+            }
+        });
+    }
+
+    /// Start the run sequence and make the window topmost.
+    fn start_run(&mut self, frame: &eframe::Frame) {
+        self.status = Some("Starting run sequence...".into());
+        // Restore window if minimized and then attempt to set window topmost (best-effort)
+        let _ = set_window_minimized(frame, false);
+        let _ = set_window_topmost(frame, true);
+        self.event_log
+            .push(eventlog::Kind::Run, "Run sequence started");
+        self.step_failure = None;
+        self.run = Some(sequence::Sequence::new(self.launch_steps()));
+    }
+
+    /// Write the preferred locale for a run's `WriteLocale` step; a failure with a known fix
+    /// is offered in the failure banner while the run carries on.
+    fn write_locale_step(&mut self) {
+        self.last_write_error = None;
+        match self.update_config_file_locales(false) {
+            Ok(()) => {
+                self.step_failure = None;
+                self.status = Some(format!(
+                    "Wrote locale {}/{} to Config.wtf",
+                    self.preferred_audio_locale, self.preferred_text_locale
+                ));
+            }
+            Err(e) => {
+                let message = format!("Run sequence: error writing locale: {}", e);
+                let recovery = self.last_write_error.take().and_then(|error| {
+                    recovery::for_write(Path::new(&self.config_wtf_path), &error)
+                });
+                match recovery {
+                    Some(recovery) => {
+                        self.event_log.push(
+                            eventlog::Kind::Run,
+                            format!("{}; {}", message, recovery.advice),
+                        );
+                        self.status = Some(recovery.advice.clone());
+                        self.step_failure = Some(StepFailure {
+                            recovery,
+                            run: None,
+                        });
+                    }
+                    None => self.status = Some(message),
+                }
+            }
+        }
+    }
+
+    /// Quick fixes for the last failed step, and Dismiss.
+    fn step_failure_banner(&mut self, ui: &mut egui::Ui, frame: &eframe::Frame) {
+        let Some(failure) = self.step_failure.as_ref() else {
+            return;
+        };
+        let resumes = failure.run.is_some();
+        let mut chosen = None;
+        let mut dismissed = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.colored_label(theme::bad(ui), "⚠ Quick fix:");
+            for (i, action) in failure.recovery.actions.iter().enumerate() {
+                let (label, hint) = match action {
+                    recovery::Action::RetryElevated => (
+                        "Retry as administrator".to_string(),
+                        "Start the program again with admin rights (UAC prompt) and continue the run",
+                    ),
+                    recovery::Action::Browse => (
+                        "Browse…".to_string(),
+                        "Pick the program's executable again and continue the run",
+                    ),
+                    recovery::Action::CloseLocking(holders) => (
+                        format!(
+                            "Close {}",
+                            holders
+                                .iter()
+                                .map(|p| p.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                        "Terminate the programs holding the file open (unsaved changes in them are lost), then write again",
+                    ),
+                    recovery::Action::Retry if resumes => (
+                        "Retry".to_string(),
+                        "Run the failed step again and continue the run",
+                    ),
+                    recovery::Action::Retry => (
+                        "Retry".to_string(),
+                        "Write the preferred locale to Config.wtf again",
+                    ),
+                };
+                // Those that write Config.wtf again follow the hold-to-confirm setting
+                let writes = matches!(action, recovery::Action::CloseLocking(_))
+                    || (!resumes && matches!(action, recovery::Action::Retry));
+                let button = ui.button(label).on_hover_text(hint);
+                let pressed = if writes {
+                    button.confirmed(self.confirm_writes)
+                } else {
+                    button.clicked()
+                };
+                if pressed {
+                    chosen = Some(i);
+                }
+            }
+            dismissed = ui.button(tr("Dismiss")).clicked();
+        });
+        if dismissed {
+            self.step_failure = None;
+            return;
+        }
+        let Some(i) = chosen else {
+            return;
+        };
+        let Some(mut failure) = self.step_failure.take() else {
+            return;
+        };
+        match &failure.recovery.actions[i] {
+            recovery::Action::RetryElevated => {
+                if let Some(sequence::Step::Launch { options, .. }) =
+                    failure.run.as_mut().and_then(|r| r.current_step_mut())
+                {
+                    options.elevate = true;
+                }
+            }
+            recovery::Action::Browse => {
+                let Some(sequence::Step::Launch { path, .. }) =
+                    failure.run.as_mut().and_then(|r| r.current_step_mut())
+                else {
+                    return;
+                };
+                // A path from the settings is fixed there too, like a path the check reported,
+                // unless the field is locked: then only this run uses the new file
+                let field = [PathField::BattleNet, PathField::Wow]
+                    .into_iter()
+                    .find(|&f| self.path(f) == path && !self.locked(f));
+                let picked = match field {
+                    Some(field) => {
+                        self.fix_path(field);
+                        Some(self.path(field).clone())
+                    }
+                    None => FileDialog::new()
+                        .add_filter("exe", &["exe"])
+                        .pick_file()
+                        .map(|p| p.display().to_string()),
+                };
+                match picked {
+                    Some(picked) if picked != *path => *path = picked,
+                    _ => {
+                        // Cancelled: keep offering the fixes
+                        self.step_failure = Some(failure);
+                        return;
+                    }
+                }
+            }
+            recovery::Action::CloseLocking(holders) => {
+                let errors: Vec<String> = holders
+                    .iter()
+                    .filter_map(|p| {
+                        processes::kill(p.pid)
+                            .err()
+                            .map(|e| format!("{}: {}", p.name, e))
+                    })
+                    .collect();
+                if !errors.is_empty() {
+                    self.status = Some(format!("Could not close {}", errors.join("; ")));
+                    self.step_failure = Some(failure);
+                    return;
+                }
+                self.event_log.push(
+                    eventlog::Kind::Run,
+                    format!(
+                        "Closed {} process(es) holding Config.wtf open",
+                        holders.len()
+                    ),
+                );
+            }
+            recovery::Action::Retry => {}
+        }
+        match failure.run {
+            Some(mut run) => {
+                run.retry();
+                let _ = set_window_topmost(frame, true);
+                self.event_log.push(
+                    eventlog::Kind::Run,
+                    "Run sequence resumed at the failed step",
+                );
+                self.status = Some("Retrying the failed step...".into());
+                self.run = Some(run);
+            }
+            None => self.write_locale_step(),
+        }
+    }
+
+    /// Controls of an active run: Continue for a confirmation prompt, or the wait controls,
+    /// and Cancel.
+    fn run_controls(&mut self, ui: &mut egui::Ui, frame: &eframe::Frame) {
+        let Some(run) = self.run.as_mut() else {
+            return;
+        };
+        if run.confirmation_prompt().is_some() {
+            ui.add_space(8.0);
+            if ui
+                .add(egui::Button::new(tr("Continue")).min_size(egui::vec2(80.0, 24.0)))
+                .clicked()
+            {
+                run.confirm();
+            }
+        } else {
+            self.wait_controls(ui);
+        }
+        ui.add_space(8.0);
+        if ui
+            .add(egui::Button::new(tr("Cancel")).min_size(egui::vec2(80.0, 24.0)))
+            .on_hover_text(input::Action::Cancel.hint(
+                ui.ctx(),
+                "Stop the run sequence and its running command; programs it already started keep running",
+            ))
+            .clicked()
+        {
+            self.cancel_run(frame);
+        }
+    }
+
+    fn cancel_run(&mut self, frame: &eframe::Frame) {
+        self.run = None;
+        set_window_topmost(frame, false);
+        self.log_status(eventlog::Kind::Run, "Run sequence cancelled".into());
+    }
+
+    /// The Run button's action: start the sequence unless something is missing for it.
+    fn run_clicked(&mut self, frame: &eframe::Frame) {
+        match self.run_problem() {
+            Some(problem) => self.status = Some(problem),
+            None => self.start_run(frame),
+        }
+    }
+
+    /// The Update button's action: write the preferred locales to Config.wtf.
+    fn update_clicked(&mut self) {
+        match self.update_config_file_locales(false) {
+            Ok(()) => self.status = Some("Config.wtf updated".into()),
+            Err(e) => self.status = Some(format!("Error updating config: {}", e)),
+        }
+    }
+
+    /// Act on the keyboard shortcuts pressed this frame, for the actions whose button is
+    /// usable right now. With "Hold to confirm writes" on, Update must be held instead.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let enabled: Vec<input::Action> = input::Action::ALL
+            .into_iter()
+            .filter(|action| match action {
+                input::Action::Run => self.run.is_none() && !self.viewer(),
+                input::Action::UpdateLocale => !self.viewer(),
+                input::Action::Save => !self.unsaved.is_empty() && !self.viewer(),
+                input::Action::Cancel => self.run.is_some(),
+            })
+            .collect();
+        for action in input::pressed(ctx, &enabled) {
+            match action {
+                input::Action::Run => self.run_clicked(frame),
+                input::Action::UpdateLocale if self.confirm_writes => {
+                    self.status = Some(
+                        "Hold Update to write Config.wtf (Hold to confirm writes is on)".into(),
+                    );
+                }
+                input::Action::UpdateLocale => self.update_clicked(),
+                input::Action::Save => self.save_clicked(),
+                input::Action::Cancel => self.cancel_run(frame),
+            }
+        }
+    }
+
+    /// Pause/extend/skip buttons, enabled while the current step is a wait.
+    fn wait_controls(&mut self, ui: &mut egui::Ui) {
+        let Some(run) = self.run.as_mut() else {
+            return;
+        };
+        let waiting = run.is_waiting();
+        ui.add_space(8.0);
+        let pause_label = if run.is_paused() { "Resume" } else { "Pause" };
+        if ui
+            .add(egui::Button::new(pause_label).min_size(egui::vec2(80.0, 24.0)))
+            .on_hover_text(
+                "Hold the sequence (a wait stops counting) until Resume, e.g. to log in manually",
+            )
+            .clicked()
+        {
+            let paused = !run.is_paused();
+            run.set_paused(paused);
+            let msg = if paused {
+                "Run sequence paused"
+            } else {
+                "Run sequence resumed"
+            };
+            self.event_log.push(eventlog::Kind::Run, msg);
+            self.status = Some(msg.into());
+        }
+        if ui
+            .add_enabled(
+                waiting,
+                egui::Button::new("+10s").min_size(egui::vec2(48.0, 24.0)),
+            )
+            .named(egui::WidgetType::Button, "Extend the wait by 10 seconds")
+            .on_hover_text("Extend the current wait by 10 seconds")
+            .clicked()
+        {
+            run.extend(Duration::from_secs(10));
+        }
+        // A wait right before a launch skips straight to it
+        let (skip_label, skip_hint) = match run.next_launch() {
+            Some(name) => (
+                "Launch now".to_string(),
+                format!("End the current wait and start {} now", name),
+            ),
+            None => (
+                "Skip".to_string(),
+                "End the current wait and continue with the next step".to_string(),
+            ),
+        };
+        if ui
+            .add_enabled(
+                waiting,
+                egui::Button::new(skip_label).min_size(egui::vec2(64.0, 24.0)),
+            )
+            .on_hover_text(skip_hint)
+            .clicked()
+        {
+            run.skip();
+        }
+    }
+
+    /// Give the focus back to `response`'s widget once the dialog it opened has closed.
+    fn refocus(&mut self, response: &egui::Response) {
+        self.pending_focus = Some(response.id);
+    }
+
+    /// Open state for a collapsible section: forced open once if it was requested with `--tab`.
+    fn take_open(&mut self, section: Section) -> Option<bool> {
+        if self.open_section == Some(section) {
+            self.open_section = None;
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Collapsible panel listing account-level configs and their locales, with a sync action.
+    fn accounts_panel(&mut self, ui: &mut egui::Ui) {
+        let header = format!("Account configs ({})", self.account_configs.len());
+        egui::CollapsingHeader::new(header)
+            .id_salt("account_configs")
+            .open(self.take_open(Section::Accounts))
+            .show(ui, |ui| {
+                if self.viewer() {
+                    ui.disable();
+                }
+                if self.account_configs.is_empty() {
+                    ui.label("No Config-cache.wtf or WTF/Account configs found next to Config.wtf");
+                    return;
+                }
+                let config = PathBuf::from(&self.config_wtf_path);
+                egui::Grid::new("account_configs_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("File");
+                        ui.strong("audioLocale");
+                        ui.strong("textLocale");
+                        ui.end_row();
+                        for account in &self.account_configs {
+                            let name = account.display_name(&config);
+                            // Korean and Chinese realm and character names
+                            if needs_fallback_fonts(&name) {
+                                load_fallback_fonts(ui.ctx());
+                            }
+                            ui.label(name);
+                            for (value, preferred) in [
+                                (&account.audio_locale, &self.preferred_audio_locale),
+                                (&account.text_locale, &self.preferred_text_locale),
+                            ] {
+                                match value {
+                                    Some(v) => {
+                                        let (text, color) = theme::verdict(
+                                            ui,
+                                            v.eq_ignore_ascii_case(preferred),
+                                            locales::label(v),
+                                        );
+                                        ui.colored_label(color, text);
+                                    }
+                                    None => {
+                                        ui.label("(not set)");
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if ui
+                    .button("Sync locale to all")
+                    .on_hover_text("Rewrite the locale lines these files already contain to the preferred locale")
+                    .confirmed(self.confirm_writes)
+                {
+                    self.sync_account_locales();
+                }
+            });
+    }
+
+    /// Collapsible panel with appearance settings (theme, background image and tint, renderer).
+    fn appearance_panel(&mut self, ui: &mut egui::Ui) {
+        let open = self.take_open(Section::Appearance);
+        egui::CollapsingHeader::new(tr("Appearance"))
+            .open(open)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Background image:"));
+                    let shown = Path::new(&self.background_image)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned());
+                    match shown {
+                        Some(name) => ui.label(name).on_hover_text(&self.background_image),
+                        None => ui.weak("background.png").on_hover_text(
+                            "A background.png/.jpg/.webp in the working directory, the settings folder or next to entitan.exe, else the built-in image",
+                        ),
                     };
+                    if ui.button(tr("Browse")).clicked() {
+                        let mut dialog = FileDialog::new()
+                            .add_filter("Images", &["png", "jpg", "jpeg", "webp"]);
+                        if let Some(parent) = Path::new(&self.background_image).parent()
+                            && parent.is_dir()
+                        {
+                            dialog = dialog.set_directory(parent);
+                        }
+                        if let Some(path) = dialog.pick_file() {
+                            self.background_image = path.display().to_string();
+                        }
+                    }
+                    if ui
+                        .add_enabled(
+                            !self.background_image.is_empty(),
+                            egui::Button::new(tr("Use default")),
+                        )
+                        .clicked()
                     {
-                        let (rect, _resp) =
-                            ui.allocate_exact_size(egui::vec2(text_w, 24.0), egui::Sense::hover());
-                        let pos = rect.left_center();
-                        ui.painter().text(
-                            pos + egui::vec2(4.0, 0.0),
-                            egui::Align2::LEFT_CENTER,
-                            a,
-                            egui::TextStyle::Body.resolve(ui.style()),
-                            a_color,
+                        self.background_image.clear();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("Opacity:"));
+                    ui.add(
+                        egui::Slider::new(&mut self.background_opacity, 0.0..=1.0)
+                            .custom_formatter(|v, _| format!("{:.0}%", v * 100.0))
+                            .custom_parser(|s| {
+                                s.trim().trim_end_matches('%').parse::<f64>().ok().map(|v| v / 100.0)
+                            }),
+                    )
+                    .labelled_by(label.id);
+                    ui.checkbox(&mut self.background_grayscale, tr("Grayscale"));
+                    let label = ui.label(tr("Scaling:"));
+                    egui::ComboBox::from_id_salt("background_scaling")
+                        .selected_text(tr(self.background_scaling.label()))
+                        .show_ui(ui, |ui| {
+                            for scaling in background::Scaling::ALL {
+                                ui.selectable_value(
+                                    &mut self.background_scaling,
+                                    scaling,
+                                    tr(scaling.label()),
+                                );
+                            }
+                        })
+                        .response
+                        .labelled_by(label.id);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Background tint:"));
+                    ui.color_edit_button_srgba_unmultiplied(&mut self.background_tint);
+                    ui.checkbox(&mut self.tint_over_image, tr("Draw over the image"))
+                        .on_hover_text(
+                            "Unchecked: the tint is drawn underneath the (translucent) image",
                         );
+                    if ui.button(tr("Clear")).clicked() {
+                        self.background_tint = [0, 0, 0, 0];
                     }
                 });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("Theme:"));
+                    egui::ComboBox::from_id_salt("theme")
+                        .selected_text(tr(self.theme.label()))
+                        .show_ui(ui, |ui| {
+                            for theme in theme::Theme::ALL {
+                                ui.selectable_value(&mut self.theme, theme, tr(theme.label()));
+                            }
+                        })
+                        .response
+                        .labelled_by(label.id);
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("Language:"));
+                    egui::ComboBox::from_id_salt("ui_language")
+                        .selected_text(tr(self.ui_language.label()))
+                        .show_ui(ui, |ui| {
+                            for language in i18n::Language::ALL {
+                                ui.selectable_value(
+                                    &mut self.ui_language,
+                                    language,
+                                    tr(language.label()),
+                                );
+                            }
+                        })
+                        .response
+                        .labelled_by(label.id);
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("Renderer:"));
+                    egui::ComboBox::from_id_salt("renderer")
+                        .selected_text(self.renderer.label())
+                        .show_ui(ui, |ui| {
+                            for backend in renderer::Backend::ALL {
+                                ui.selectable_value(&mut self.renderer, backend, backend.label());
+                            }
+                        })
+                        .response
+                        .labelled_by(label.id);
+                    ui.checkbox(&mut self.software_rendering, tr("Software rendering"))
+                        .on_hover_text(
+                            "Draw on the CPU; for graphics drivers that can't open the window",
+                        );
+                    ui.label(tr("(after restart)"));
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.disable_animations, tr("Disable animations"))
+                        .on_hover_text("No sliding panels or spinning progress indicators");
+                    if self.os_reduced_motion {
+                        ui.label("(off anyway: Windows animations are turned off)");
+                    }
+                });
+                ui.checkbox(&mut self.high_contrast, tr("High contrast"))
+                    .on_hover_text(
+                        "Blue and orange instead of green and red for matching and differing values, text at full contrast, no background image",
+                    );
+            });
+    }
+
+    /// Collapsible panel configuring the guild broadcast webhook.
+    fn broadcast_panel(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("Guild broadcast")).show(ui, |ui| {
+            if self.viewer() {
+                ui.disable();
+            }
+            ui.checkbox(
+                &mut self.broadcast_enabled,
+                "Announce when the run sequence completes",
+            );
+            egui::Grid::new("broadcast_grid").show(ui, |ui| {
+                let label = ui.label(tr("Player:"));
+                ui.add_sized(
+                    [320.0, 20.0],
+                    egui::TextEdit::singleline(&mut self.player_name),
+                )
+                .labelled_by(label.id);
+                ui.end_row();
+                let label = ui.label("Webhook URL:");
+                ui.add_sized(
+                    [320.0, 20.0],
+                    egui::TextEdit::singleline(&mut self.broadcast_url)
+                        .hint_text("https://…"),
+                )
+                .labelled_by(label.id);
+                ui.end_row();
+                let label = ui.label("Payload:").on_hover_text(
+                    "JSON sent to the webhook. Placeholders: {player}, {locale}, {audio_locale}, {time}",
+                );
+                ui.add_sized(
+                    [320.0, 48.0],
+                    egui::TextEdit::multiline(&mut self.broadcast_template).code_editor(),
+                )
+                .labelled_by(label.id);
+                ui.end_row();
+            });
+            if ui
+                .add_enabled(self.broadcast_rx.is_none(), egui::Button::new(tr("Send test")))
+                .clicked()
+            {
+                self.send_broadcast();
+            }
+        });
+    }
+
+    /// Render the broadcast template and POST it in the background.
+    fn send_broadcast(&mut self) {
+        if self.broadcast_url.trim().is_empty() {
+            self.status = Some("Guild broadcast: no webhook URL set".into());
+            return;
+        }
+        let time = eventlog::format_time(eventlog::now()) + " UTC";
+        let body = webhook::render_template(
+            &self.broadcast_template,
+            &[
+                ("player", &self.player_name),
+                ("locale", &self.preferred_text_locale),
+                ("audio_locale", &self.preferred_audio_locale),
+                ("time", &time),
+            ],
+        );
+        if let Err(e) = serde_json::from_str::<serde_json::Value>(&body) {
+            self.status = Some(format!("Guild broadcast: payload is not valid JSON: {}", e));
+            return;
+        }
+        self.broadcast_rx = Some(webhook::post_json_async(self.broadcast_url.clone(), body));
+    }
 
-                // textLocale row (aligned and colored; value left-aligned to textfield column)
+    /// Collapsible panel showing the latest event log entries, with CSV export.
+    fn event_log_panel(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("Event log")).show(ui, |ui| {
+            if self.log_detached {
+                ui.horizontal(|ui| {
+                    ui.label("Shown in a separate window.");
+                    if ui.button("Attach").clicked() {
+                        self.log_detached = false;
+                    }
+                });
+            } else {
+                egui::ScrollArea::vertical()
+                    .max_height(120.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| self.event_log_lines(ui, 50, None));
+                if ui
+                    .button("Detach")
+                    .on_hover_text(
+                        "Open the log in its own always-on-top window, e.g. to share it on a call",
+                    )
+                    .clicked()
+                {
+                    self.log_detached = true;
+                }
+            }
+            ui.horizontal(|ui| {
+                ui.label("Columns:");
+                ui.checkbox(&mut self.csv_columns.time, "Time");
+                ui.checkbox(&mut self.csv_columns.kind, "Kind");
+                ui.checkbox(&mut self.csv_columns.message, "Message");
+            });
+            ui.horizontal(|ui| {
+                let label = ui.label("From:");
+                ui.add_sized(
+                    [90.0, 20.0],
+                    egui::TextEdit::singleline(&mut self.csv_from).hint_text("YYYY-MM-DD"),
+                )
+                .labelled_by(label.id);
+                let label = ui.label("To:");
+                ui.add_sized(
+                    [90.0, 20.0],
+                    egui::TextEdit::singleline(&mut self.csv_to).hint_text("YYYY-MM-DD"),
+                )
+                .labelled_by(label.id);
+                let button = ui
+                    .button("Export CSV…")
+                    .on_hover_text("Dates are UTC and inclusive; leave empty for no limit");
+                if button.clicked() {
+                    self.export_event_log();
+                    self.refocus(&button);
+                }
+            });
+        });
+    }
+
+    /// The last `count` event log entries, one label each, at `size` points (or the body
+    /// size).
+    fn event_log_lines(&self, ui: &mut egui::Ui, count: usize, size: Option<f32>) {
+        for entry in self.event_log.entries().iter().rev().take(count).rev() {
+            let text = format!(
+                "{}  [{}]  {}",
+                eventlog::format_time(entry.time),
+                entry.kind.as_str(),
+                entry.message
+            );
+            match size {
+                Some(size) => ui.label(egui::RichText::new(text).size(size)),
+                None => ui.label(text),
+            };
+        }
+    }
+
+    /// The detached event log: a separate always-on-top window with text size controls and
+    /// a toggle to stop following new entries while reading older ones. Closing it puts the
+    /// log back into the main window.
+    fn detached_log_window(&mut self, ctx: &egui::Context) {
+        if !self.log_detached {
+            return;
+        }
+        let builder = egui::ViewportBuilder::default()
+            .with_title("Entitan event log")
+            .with_inner_size([760.0, 420.0])
+            .with_min_inner_size([320.0, 160.0])
+            .with_always_on_top();
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("event_log"),
+            builder,
+            |ctx, _| {
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    self.log_detached = false;
+                    return;
+                }
+                egui::TopBottomPanel::top("event_log_controls").show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Text size:"));
+                        if ui
+                            .add_enabled(self.log_font_size > LOG_FONT_MIN, egui::Button::new("A−"))
+                            .named(egui::WidgetType::Button, "Smaller text")
+                            .clicked()
+                        {
+                            self.log_font_size = (self.log_font_size - 2.0).max(LOG_FONT_MIN);
+                        }
+                        ui.label(format!("{:.0}", self.log_font_size));
+                        if ui
+                            .add_enabled(self.log_font_size < LOG_FONT_MAX, egui::Button::new("A+"))
+                            .named(egui::WidgetType::Button, "Larger text")
+                            .clicked()
+                        {
+                            self.log_font_size = (self.log_font_size + 2.0).min(LOG_FONT_MAX);
+                        }
+                        ui.separator();
+                        ui.checkbox(&mut self.log_autoscroll_paused, tr("Pause autoscroll"))
+                            .on_hover_text("Keep the view where it is while new entries arrive");
+                        ui.separator();
+                        if ui.button("Attach").clicked() {
+                            self.log_detached = false;
+                        }
+                    });
+                });
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::both()
+                        .auto_shrink([false, false])
+                        .stick_to_bottom(!self.log_autoscroll_paused)
+                        .show(ui, |ui| {
+                            self.event_log_lines(ui, DETACHED_LOG_ENTRIES, Some(self.log_font_size))
+                        });
+                });
+            },
+        );
+    }
+
+    /// Validate the date range, ask for a destination and write the CSV.
+    fn export_event_log(&mut self) {
+        let parse = |text: &str| -> Result<Option<u64>, String> {
+            if text.trim().is_empty() {
+                return Ok(None);
+            }
+            eventlog::parse_date(text)
+                .map(Some)
+                .ok_or_else(|| format!("{:?} is not a YYYY-MM-DD date", text))
+        };
+        let range = parse(&self.csv_from).and_then(|from| {
+            // The "to" date is inclusive: stop at the following midnight
+            Ok((from, parse(&self.csv_to)?.map(|to| to + 86_400)))
+        });
+        let (from, to) = match range {
+            Ok(range) => range,
+            Err(e) => {
+                self.status = Some(format!("Error exporting event log: {}", e));
+                return;
+            }
+        };
+        let Some(out) = FileDialog::new()
+            .add_filter("csv", &["csv"])
+            .set_file_name("entitan-events.csv")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(
+            match eventlog::export_csv(self.event_log.entries(), &out, &self.csv_columns, from, to)
+            {
+                Ok(rows) => format!("Exported {} event(s) to {}", rows, out.display()),
+                Err(e) => format!("Error exporting event log: {}", e),
+            },
+        );
+    }
+
+    /// Collapsible editor of the custom run sequence (add/remove/reorder steps).
+    fn sequence_panel(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("Run sequence")).show(ui, |ui| {
+            if self.viewer() {
+                ui.disable();
+            }
+            if ui
+                .checkbox(
+                    &mut self.use_custom_sequence,
+                    "Run these steps instead of the built-in sequence",
+                )
+                .changed()
+                && self.use_custom_sequence
+                && self.custom_steps.is_empty()
+            {
+                self.custom_steps =
+                    script::default_script(self.wow_launch_delay_secs, self.relaunch_delay_secs);
+            }
+            ui.add_enabled_ui(self.use_custom_sequence, |ui| {
+                // Reorder/remove after the loop so indices stay valid while drawing
+                let mut move_up = None;
+                let mut remove = None;
+                let count = self.custom_steps.len();
+                for (i, step) in self.custom_steps.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}.", i + 1));
+                        let mut kind = step.kind();
+                        egui::ComboBox::from_id_salt(("script_kind", i))
+                            .width(120.0)
+                            .selected_text(script::KINDS[kind])
+                            .show_ui(ui, |ui| {
+                                for (k, name) in script::KINDS.iter().enumerate() {
+                                    ui.selectable_value(&mut kind, k, *name);
+                                }
+                            })
+                            .response
+                            .named(egui::WidgetType::ComboBox, &format!("Step {}", i + 1));
+                        if kind != step.kind() {
+                            *step = script::ScriptStep::new(kind);
+                        }
+                        script_step_fields(ui, i, step);
+                        if ui
+                            .add_enabled(i > 0, egui::Button::new("⏶"))
+                            .named(egui::WidgetType::Button, "Move step up")
+                            .clicked()
+                        {
+                            move_up = Some(i);
+                        }
+                        if ui
+                            .add_enabled(i + 1 < count, egui::Button::new("⏷"))
+                            .named(egui::WidgetType::Button, "Move step down")
+                            .clicked()
+                        {
+                            move_up = Some(i + 1);
+                        }
+                        if ui
+                            .button("✕")
+                            .named(egui::WidgetType::Button, "Remove step")
+                            .on_hover_text("Remove step")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = move_up {
+                    self.custom_steps.swap(i - 1, i);
+                }
+                if let Some(i) = remove {
+                    self.custom_steps.remove(i);
+                }
                 ui.horizontal(|ui| {
-                    ui.add_sized([label_w, 24.0], egui::Label::new("textLocale:"));
-                    let t = self.text_locale.as_deref().unwrap_or("(not available)");
-                    let t_color = if self
-                        .text_locale
-                        .as_deref()
-                        .map(|v| v.eq_ignore_ascii_case(&self.preferred_locale))
-                        .unwrap_or(false)
-                    {
-                        egui::Color32::from_rgb(0, 160, 0)
-                    } else {
-                        egui::Color32::from_rgb(200, 0, 0)
-                    };
+                    if ui.button(tr("Add step")).clicked() {
+                        self.custom_steps.push(script::ScriptStep::new(1));
+                    }
+                    if ui
+                        .button("Reset to built-in")
+                        .on_hover_text(
+                            "Replace the steps with the default Battle.net → WoW → Battle.net flow",
+                        )
+                        .clicked()
                     {
-                        let (rect, _resp) =
-                            ui.allocate_exact_size(egui::vec2(text_w, 24.0), egui::Sense::hover());
-                        let pos = rect.left_center();
-                        ui.painter().text(
-                            pos + egui::vec2(4.0, 0.0),
-                            egui::Align2::LEFT_CENTER,
-                            t,
-                            egui::TextStyle::Body.resolve(ui.style()),
-                            t_color,
+                        self.custom_steps = script::default_script(
+                            self.wow_launch_delay_secs,
+                            self.relaunch_delay_secs,
                         );
                     }
                 });
+            });
+        });
+    }
 
-                ui.separator();
-                ui.add_space(6.0);
-
-                // Preferred Locale row (aligned)
-                ui.horizontal(|ui| {
-                    // reuse label_w, btn_w, text_w from above
-                    ui.add_sized([label_w, 24.0], egui::Label::new("Preferred Locale:"));
-                    ui.add_sized(
-                        [text_w, 24.0],
-                        egui::TextEdit::singleline(&mut self.preferred_locale),
-                    );
+    /// Collapsible panel with one-click graphics presets.
+    fn presets_panel(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("Graphics presets")).show(ui, |ui| {
+            if self.viewer() {
+                ui.disable();
+            }
+            ui.horizontal(|ui| {
+                for preset in presets::PRESETS {
+                    let cvars: Vec<String> = preset
+                        .cvars
+                        .iter()
+                        .map(|(k, v)| format!("{} = {}", k, v))
+                        .collect();
                     if ui
-                        .add_sized([btn_w, 24.0], egui::Button::new("Update"))
-                        .clicked()
+                        .add_sized([100.0, 24.0], egui::Button::new(preset.name))
+                        .on_hover_text(format!("{}\n\n{}", preset.description, cvars.join("\n")))
+                        .confirmed(self.confirm_writes)
                     {
-                        match self.update_config_file_locales() {
-                            Ok(()) => self.status = Some("Config.wtf updated".into()),
-                            Err(e) => self.status = Some(format!("Error updating config: {}", e)),
-                        }
-                    }
-                    // reserve space for a potential second button so alignment matches WoW row
-                    ui.add_sized([btn_w, 24.0], egui::Label::new(""));
-                    ui.add_sized([right_pad, 24.0], egui::Label::new(""));
-                });
-                // Enforce only ASCII letters and max length 4; reset invalid values to enUS
-                let orig_pref = self.preferred_locale.clone();
-                let filtered: String = orig_pref
-                    .chars()
-                    .filter(|c| c.is_ascii_alphabetic())
-                    .take(4)
-                    .collect();
-                if filtered.is_empty() {
-                    // If user cleared the field, keep default; if it's invalid (e.g., geometry), reset and warn
-                    if orig_pref.is_empty() {
-                        self.preferred_locale = "enUS".into();
-                    } else {
-                        self.preferred_locale = "enUS".into();
-                        self.status = Some("Preferred locale invalid; reset to enUS".into());
+                        self.status = Some(match self.apply_preset(preset) {
+                            Ok(backup) => format!(
+                                "Applied the {} preset (previous config saved as snapshot \"{}\")",
+                                preset.name, backup
+                            ),
+                            Err(e) => format!("Error applying preset: {}", e),
+                        });
                     }
-                } else if filtered != orig_pref {
-                    self.preferred_locale = filtered;
-                    self.status = Some("Preferred locale filtered to letters only (max 4)".into());
                 }
+            });
+        });
+    }
 
-                ui.add_space(6.0);
+    /// Save a backup snapshot of Config.wtf, then write the preset's cvars. Returns the
+    /// backup's snapshot name.
+    fn apply_preset(&mut self, preset: &presets::Preset) -> Result<String, String> {
+        let cfg = PathBuf::from(&self.config_wtf_path);
+        if !is_file_with_ext(&cfg, "wtf") {
+            return Err("Config.wtf path must point to an existing .wtf file".into());
+        }
+        let backup = format!("Before {} preset", preset.name);
+        snapshots::save(&backup, &cfg)?;
+        self.refresh_snapshots();
+        self.write_cvars(preset.cvars, format!("apply {} preset", preset.name), false)?;
+        Ok(backup)
+    }
 
-                // Battle.net row (aligned)
+    /// Collapsible panel listing saved Config.wtf snapshots with save/apply/delete actions.
+    fn profiles_panel(&mut self, ui: &mut egui::Ui) {
+        let open = self.take_open(Section::Profiles);
+        egui::CollapsingHeader::new(tr("Profiles (Config.wtf snapshots)"))
+            .open(open)
+            .show(ui, |ui| {
+                if self.viewer() {
+                    ui.disable();
+                }
                 ui.horizontal(|ui| {
-                    // reuse label_w, btn_w, text_w from above
-                    ui.add_sized([label_w, 24.0], egui::Label::new("Battle.net"));
                     ui.add_sized(
-                        [text_w, 24.0],
-                        egui::TextEdit::singleline(&mut self.battle_net_path),
-                    );
+                        [240.0, 24.0],
+                        egui::TextEdit::singleline(&mut self.new_snapshot_name)
+                            .hint_text("Snapshot name"),
+                    )
+                    .named(egui::WidgetType::TextEdit, "Snapshot name");
                     if ui
-                        .add_sized([btn_w, 24.0], egui::Button::new("Browse"))
+                        .add_sized([120.0, 24.0], egui::Button::new(tr("Save snapshot")))
                         .clicked()
                     {
-                        let mut dialog = FileDialog::new();
-                        if !self.battle_net_path.is_empty() {
-                            if let Some(parent) = Path::new(&self.battle_net_path).parent() {
-                                dialog = dialog.set_directory(parent);
+                        let cfg = Path::new(&self.config_wtf_path);
+                        if !is_file_with_ext(cfg, "wtf") {
+                            self.status =
+                                Some("Config.wtf path must point to an existing .wtf file".into());
+                        } else {
+                            match snapshots::save(&self.new_snapshot_name, cfg) {
+                                Ok(()) => {
+                                    let name = self.new_snapshot_name.trim().to_string();
+                                    self.status = Some(format!("Saved snapshot \"{}\"", name));
+                                    self.set_active_profile(Some(name));
+                                    self.new_snapshot_name.clear();
+                                    self.refresh_snapshots();
+                                }
+                                Err(e) => {
+                                    self.status = Some(format!("Error saving snapshot: {}", e))
+                                }
                             }
                         }
-                        if let Some(file) = dialog.add_filter("exe", &["exe"]).pick_file() {
-                            if is_file_with_ext(&file, "exe") {
-                                self.battle_net_path = file.display().to_string();
-                                self.status = Some("Selected (unsaved)".into());
+                    }
+                });
+                if self.snapshot_names.is_empty() {
+                    ui.label(tr("No snapshots saved yet"));
+                }
+                let mut refresh = false;
+                let mut activate = None;
+                let mut switch = None;
+                let mut link = None;
+                for name in &self.snapshot_names {
+                    let folder = self.snapshot_folders.get(name);
+                    ui.horizontal(|ui| {
+                        let label =
+                            ui.add_sized([240.0, 24.0], egui::Label::new(name.as_str()).truncate());
+                        if let Some(folder) = folder {
+                            label.on_hover_text(format!("Server folder: {}", folder.display()));
+                        }
+                        let apply = ui.add_sized([56.0, 24.0], egui::Button::new(tr("Apply")));
+                        let apply = match folder {
+                            Some(_) => apply.on_hover_text(
+                                "Swap the server folder's Config.wtf, realmlist.wtf and AddOns into the installation",
+                            ),
+                            None => apply,
+                        };
+                        if apply.confirmed(self.confirm_writes) {
+                            if let Some(folder) = folder {
+                                switch = Some((name.clone(), folder.clone()));
                             } else {
-                                self.status = Some("Selected file is not an .exe".into());
+                                let cfg = Path::new(&self.config_wtf_path);
+                                let before = fs::read(cfg).unwrap_or_default();
+                                match snapshots::apply(name, cfg) {
+                                    Ok(()) => {
+                                        self.history.record(
+                                            cfg,
+                                            format!("apply snapshot \"{}\"", name),
+                                            before,
+                                            fs::read(cfg).unwrap_or_default(),
+                                        );
+                                        self.status =
+                                            Some(format!("Applied snapshot \"{}\"", name));
+                                        activate = Some(Some(name.clone()));
+                                        // Force a refresh of cached values
+                                        self.last_config_path = None;
+                                    }
+                                    Err(e) => {
+                                        self.status =
+                                            Some(format!("Error applying snapshot: {}", e))
+                                    }
+                                }
                             }
                         }
+                        if ui
+                            .add_sized([64.0, 24.0], egui::Button::new(tr("Folder…")))
+                            .on_hover_text(
+                                "Link a server folder holding this server's Config.wtf, realmlist.wtf and AddOns folder",
+                            )
+                            .clicked()
+                            && let Some(dir) = FileDialog::new().pick_folder()
+                        {
+                            link = Some((name.clone(), Some(dir)));
+                        }
+                        if folder.is_some()
+                            && ui
+                                .add_sized([56.0, 24.0], egui::Button::new(tr("Unlink")))
+                                .clicked()
+                        {
+                            link = Some((name.clone(), None));
+                        }
+                        if ui
+                            .add_sized([56.0, 24.0], egui::Button::new(tr("Delete")))
+                            .clicked()
+                        {
+                            match snapshots::delete(name) {
+                                Ok(()) => {
+                                    self.status = Some(format!("Deleted snapshot \"{}\"", name));
+                                    if self.active_profile.as_ref() == Some(name) {
+                                        activate = Some(None);
+                                    }
+                                }
+                                Err(e) => {
+                                    self.status = Some(format!("Error deleting snapshot: {}", e))
+                                }
+                            }
+                            refresh = true;
+                        }
+                    });
+                }
+                if let Some((name, folder)) = link {
+                    self.link_server_folder(&name, folder.as_deref());
+                    refresh = true;
+                }
+                if let Some((name, folder)) = switch {
+                    match self.switch_server(&folder) {
+                        Ok(swapped) => {
+                            self.status = Some(format!(
+                                "Switched to \"{}\": {}",
+                                name,
+                                swapped.join(", ")
+                            ));
+                            activate = Some(Some(name));
+                        }
+                        Err(e) => self.status = Some(format!("Error switching server: {}", e)),
                     }
-                    // reserve space for a second button so buttons align across rows
-                    ui.add_sized([btn_w, 24.0], egui::Label::new(""));
-                    ui.add_sized([right_pad, 24.0], egui::Label::new(""));
-                });
+                }
+                let cfg = PathBuf::from(&self.config_wtf_path);
+                if servers::is_active(&cfg) {
+                    ui.horizontal(|ui| {
+                        ui.label("A server folder replaced this installation's files.");
+                        if ui
+                            .add_enabled(
+                                self.running_game.is_none(),
+                                egui::Button::new(tr("Restore original files")),
+                            )
+                            .on_disabled_hover_text(
+                                "Close WoW first; it rewrites Config.wtf when it exits",
+                            )
+                            .confirmed(self.confirm_writes)
+                        {
+                            let before = fs::read(&cfg).unwrap_or_default();
+                            self.status = Some(match servers::restore_original(&cfg) {
+                                Ok(n) => {
+                                    self.history.record(
+                                        &cfg,
+                                        "restore original files",
+                                        before,
+                                        fs::read(&cfg).unwrap_or_default(),
+                                    );
+                                    self.last_config_path = None;
+                                    activate = Some(None);
+                                    format!("Restored {} original file(s)", n)
+                                }
+                                Err(e) => format!("Error restoring original files: {}", e),
+                            });
+                        }
+                    });
+                }
+                if let Some(profile) = activate {
+                    self.set_active_profile(profile);
+                }
+                if refresh {
+                    self.refresh_snapshots();
+                }
+                self.update_locales();
+            });
+    }
 
-                ui.add_space(6.0);
+    /// Reload the snapshot names and their linked server folders.
+    fn refresh_snapshots(&mut self) {
+        self.snapshot_names = snapshots::list();
+        self.snapshot_folders = self
+            .snapshot_names
+            .iter()
+            .filter_map(|name| Some((name.clone(), snapshots::load_folder(name)?)))
+            .collect();
+    }
 
-                // Config.wtf row (aligned)
-                ui.horizontal(|ui| {
-                    // reuse label_w, btn_w, text_w from above
-                    ui.add_sized([label_w, 24.0], egui::Label::new("Config.wtf:"));
-                    ui.add_sized(
-                        [text_w, 24.0],
-                        egui::TextEdit::singleline(&mut self.config_wtf_path),
-                    );
-                    if ui
-                        .add_sized([btn_w, 24.0], egui::Button::new("Browse"))
-                        .clicked()
-                    {
-                        let mut dialog = FileDialog::new();
-                        if !self.config_wtf_path.is_empty() {
-                            if let Some(parent) = Path::new(&self.config_wtf_path).parent() {
-                                dialog = dialog.set_directory(parent);
-                            }
-                        }
-                        if let Some(file) = dialog.add_filter("wtf", &["wtf"]).pick_file() {
-                            if is_file_with_ext(&file, "wtf") {
-                                self.config_wtf_path = file.display().to_string();
-                                self.status = Some("Selected (unsaved)".into());
-                                // refresh cached locale values immediately
-                                self.update_locales();
-                            } else {
-                                self.status = Some("Selected file is not a .wtf file".into());
-                            }
+    /// Link `folder` to the snapshot `name` (None unlinks it).
+    fn link_server_folder(&mut self, name: &str, folder: Option<&Path>) {
+        let result = match folder {
+            Some(folder) => {
+                servers::check(folder).and_then(|()| snapshots::save_folder(name, Some(folder)))
+            }
+            None => snapshots::save_folder(name, None),
+        };
+        self.status = Some(match (result, folder) {
+            (Ok(()), Some(folder)) => {
+                format!("Linked server folder {} to \"{}\"", folder.display(), name)
+            }
+            (Ok(()), None) => format!("Unlinked the server folder of \"{}\"", name),
+            (Err(e), _) => format!("Error linking server folder: {}", e),
+        });
+    }
+
+    /// Swap the server folder `folder` into the installation, recording the Config.wtf
+    /// change in the undo history.
+    fn switch_server(&mut self, folder: &Path) -> Result<Vec<String>, String> {
+        if self.viewer() {
+            return Err(VIEWER_REFUSAL.into());
+        }
+        if self.running_game.is_some() {
+            return Err(WOW_RUNNING_REFUSAL.into());
+        }
+        let cfg = PathBuf::from(&self.config_wtf_path);
+        if !is_file_with_ext(&cfg, "wtf") {
+            return Err("Config.wtf path must point to an existing .wtf file".into());
+        }
+        let before = fs::read(&cfg).unwrap_or_default();
+        let swapped = servers::activate(folder, &cfg)?;
+        self.history.record(
+            &cfg,
+            "switch server folder",
+            before,
+            fs::read(&cfg).unwrap_or_default(),
+        );
+        // Force a refresh of cached values
+        self.last_config_path = None;
+        Ok(swapped)
+    }
+
+    /// Switch the launcher tab's notes to `profile` (None hides them).
+    fn set_active_profile(&mut self, profile: Option<String>) {
+        self.profile_notes = profile
+            .as_deref()
+            .map(snapshots::load_notes)
+            .unwrap_or_default();
+        self.active_profile = profile;
+    }
+
+    /// Update both `SET audioLocale` and `SET textLocale` lines in the Config.wtf file
+    /// to match the preferred audio and text locales.
+    ///
+    /// A read-only Config.wtf is refused unless `clear_readonly` is set, in which case the flag
+    /// is cleared for the write and restored afterwards.
+    fn update_config_file_locales(&mut self, clear_readonly: bool) -> Result<(), String> {
+        if self.viewer() {
+            return Err(VIEWER_REFUSAL.into());
+        }
+        let (audio, text) = (
+            self.preferred_audio_locale.clone(),
+            self.preferred_text_locale.clone(),
+        );
+        // The client may reset the characters' addon lists after a locale switch; a failed
+        // copy is logged rather than holding up the write
+        if self.protect_addons
+            && let Err(e) = addons::snapshot(Path::new(&self.config_wtf_path))
+        {
+            self.event_log.push(
+                eventlog::Kind::Status,
+                format!("Could not save AddOns.txt copies: {}", e),
+            );
+        }
+        self.write_cvars(
+            &[("audioLocale", &audio), ("textLocale", &text)],
+            format!("set locale to {}/{}", audio, text),
+            clear_readonly,
+        )?;
+        // Battle.net writes its own per-game language back into Config.wtf; keep them equal
+        if self.sync_battle_net_config {
+            self.sync_battle_net_config_file()?;
+        }
+        Ok(())
+    }
+
+    /// Set `cvars` in Config.wtf as one undoable edit named `label`. Performs existence and
+    /// size checks (`limits::get().max_config_bytes`) and replaces the file atomically (temp file + rename);
+    /// see `update_config_file_locales` for `clear_readonly`.
+    fn write_cvars(
+        &mut self,
+        cvars: &[(&str, &str)],
+        label: String,
+        clear_readonly: bool,
+    ) -> Result<(), String> {
+        if self.viewer() {
+            return Err(VIEWER_REFUSAL.into());
+        }
+        let cfg = self.config_wtf_path.clone();
+        if cfg.is_empty() {
+            return Err("Config.wtf path is not set".into());
+        }
+        let p = Path::new(&cfg);
+        if !p.exists() || !p.is_file() {
+            return Err("Config.wtf path does not exist or is not a file".into());
+        }
+        let meta = p.metadata().map_err(|e| e.to_string())?;
+        let readonly = meta.permissions().readonly();
+        if readonly && !clear_readonly {
+            return Err("Config.wtf is read-only".into());
+        }
+        self.confirm_external_edit(p, &meta, cvars)?;
+        if meta.len() > limits::get().max_config_bytes {
+            // Large-file mode: stream the targeted lines instead of loading the whole file.
+            // Such a file is too big to keep in the undo history.
+            let mut io_error = None;
+            let written = with_writable(p, readonly, || {
+                wtf::stream_set(p, cvars).map_err(|e| {
+                    let message = e.to_string();
+                    io_error = Some(e);
+                    message
+                })
+            });
+            self.last_write_error = io_error;
+            written?;
+            self.event_log
+                .push(eventlog::Kind::Status, format!("Config.wtf: {}", label));
+        } else {
+            // Lint before the write; the refresh below lints what actually ended up on disk
+            let mut findings = None;
+            let edited = self.edit_wtf(p, "Config.wtf", label, readonly, |doc| {
+                findings = Some(doc.lint().iter().map(ToString::to_string).collect());
+                for (key, value) in cvars {
+                    doc.set(key, value);
+                }
+            });
+            if let Some(findings) = findings {
+                self.config_findings = findings;
+            }
+            edited?;
+        }
+        // Force a refresh of cached values even if the file path didn't change
+        self.last_config_path = None;
+        self.update_locales();
+        Ok(())
+    }
+
+    /// Rewrite the locale lines the account configs already contain to the preferred locales.
+    /// Each changed file is its own undoable edit; read-only ones are refused.
+    fn sync_account_locales(&mut self) {
+        if self.viewer() {
+            self.status = Some(VIEWER_REFUSAL.into());
+            return;
+        }
+        let config = PathBuf::from(&self.config_wtf_path);
+        let (audio, text) = (
+            self.preferred_audio_locale.clone(),
+            self.preferred_text_locale.clone(),
+        );
+        // As for Config.wtf: the client may reset the addon lists after a locale switch
+        if self.protect_addons
+            && let Err(e) = addons::snapshot(&config)
+        {
+            self.event_log.push(
+                eventlog::Kind::Status,
+                format!("Could not save AddOns.txt copies: {}", e),
+            );
+        }
+        let mut changed = 0;
+        let mut errors = Vec::new();
+        let files: Vec<(PathBuf, String)> = self
+            .account_configs
+            .iter()
+            .map(|a| (a.path.clone(), a.display_name(&config)))
+            .collect();
+        for (path, name) in &files {
+            let result = path.metadata().map_err(|e| e.to_string()).and_then(|meta| {
+                if meta.permissions().readonly() {
+                    Err("file is read-only".into())
+                } else if meta.len() > limits::get().max_config_bytes {
+                    Err("file is too large".into())
+                } else {
+                    self.edit_wtf(
+                        path,
+                        name,
+                        format!("set locale to {}/{}", audio, text),
+                        false,
+                        |doc| accounts::sync_locale(doc, &audio, &text),
+                    )
+                }
+            });
+            match result {
+                Ok(true) => changed += 1,
+                Ok(false) => {}
+                Err(e) => errors.push(format!("{}: {}", name, e)),
+            }
+        }
+        self.status = Some(if errors.is_empty() {
+            format!("Updated locale in {} account config(s)", changed)
+        } else {
+            format!("Error syncing account configs: {}", errors.join("; "))
+        });
+        self.account_configs = accounts::discover(&config);
+    }
+
+    /// Rewrite the .wtf file `p` through `edit` as one undoable edit named `label`, logged as
+    /// `name: label`. Encoding, BOM and untouched lines are preserved as-is, and the file is
+    /// only written if `edit` changed it; `readonly` clears the read-only flag for the write.
+    /// Returns whether the file changed.
+    fn edit_wtf(
+        &mut self,
+        p: &Path,
+        name: &str,
+        label: String,
+        readonly: bool,
+        edit: impl FnOnce(&mut WtfDocument),
+    ) -> Result<bool, String> {
+        let bytes = fs::read(p).map_err(|e| e.to_string())?;
+        let mut doc = WtfDocument::parse(&bytes)?;
+        edit(&mut doc);
+        let new_bytes = doc.to_bytes();
+        // Values already as wanted: leave the file (and its timestamp) alone
+        if new_bytes == bytes {
+            return Ok(false);
+        }
+        self.write_wtf(p, &format!("{}: {}", name, label), readonly, &new_bytes)?;
+        self.history.record(p, label, bytes, new_bytes);
+        Ok(true)
+    }
+
+    /// Replace the .wtf file `p` with `bytes` atomically and log `event`; `readonly` clears
+    /// the read-only flag for the write.
+    fn write_wtf(
+        &mut self,
+        p: &Path,
+        event: &str,
+        readonly: bool,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        let mut io_error = None;
+        let written = with_writable(p, readonly, || {
+            wtf::write_atomic(p, bytes).map_err(|e| {
+                let message = e.to_string();
+                io_error = Some(e);
+                message
+            })
+        });
+        self.last_write_error = io_error;
+        written?;
+        self.event_log.push(eventlog::Kind::Status, event);
+        Ok(())
+    }
+
+    /// Undo (or with `redo`, redo) the latest edit in the history. A read-only file is
+    /// refused, and one changed since the edit is only overwritten after confirmation.
+    /// Returns the edit's label.
+    fn step_history(&mut self, redo: bool) -> Result<String, String> {
+        if self.viewer() {
+            return Err(VIEWER_REFUSAL.into());
+        }
+        let mut history = std::mem::take(&mut self.history);
+        let (action, label) = if redo {
+            ("redo", history.redo_label())
+        } else {
+            ("undo", history.undo_label())
+        };
+        let label = label.unwrap_or_default().to_string();
+        let write = |p: &Path, expected: &[u8], contents: &[u8]| {
+            let name = p.file_name().map_or_else(
+                || p.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            let meta = p.metadata().map_err(|e| format!("{}: {}", name, e))?;
+            if meta.permissions().readonly() {
+                return Err(format!("{} is read-only", name));
+            }
+            if fs::read(p).map_err(|e| format!("{}: {}", name, e))? != expected {
+                let answer = rfd::MessageDialog::new()
+                    .set_level(rfd::MessageLevel::Warning)
+                    .set_title(format!("{} changed on disk", name))
+                    .set_description(format!(
+                        "{} was changed since this edit was made; to {} it, those changes are overwritten.\n\nOverwrite them?",
+                        p.display(),
+                        action
+                    ))
+                    .set_buttons(rfd::MessageButtons::OkCancel)
+                    .show();
+                if answer != rfd::MessageDialogResult::Ok {
+                    return Err(format!("{} was changed on disk; nothing was written", name));
+                }
+            }
+            self.write_wtf(
+                p,
+                &format!("{}: {} {}", name, action, label),
+                false,
+                contents,
+            )
+        };
+        let result = if redo {
+            history.redo(write)
+        } else {
+            history.undo(write)
+        };
+        self.history = history;
+        result
+    }
+
+    /// Before writing `cvars`, check whether Config.wtf changed since its values were last read
+    /// (e.g. edited by hand while live reload is off, or before the watcher's event arrived).
+    /// When that changed a locale the write would overwrite, ask first, showing the values on
+    /// disk and the new ones; declining reloads the file and returns an error.
+    fn confirm_external_edit(
+        &mut self,
+        p: &Path,
+        meta: &fs::Metadata,
+        cvars: &[(&str, &str)],
+    ) -> Result<(), String> {
+        let Some(read_mtime) = self.config_read_mtime else {
+            return Ok(());
+        };
+        if meta.modified().ok() == Some(read_mtime) {
+            return Ok(());
+        }
+        let keys = ["audioLocale", "textLocale"];
+        let on_disk = if meta.len() > limits::get().max_config_bytes {
+            wtf::scan_values(p, &keys).map_err(|e| e.to_string())?
+        } else {
+            let doc = WtfDocument::parse(&fs::read(p).map_err(|e| e.to_string())?)?;
+            keys.iter().map(|key| doc.get(key)).collect()
+        };
+        let last_read = [self.audio_locale.clone(), self.text_locale.clone()];
+        let show =
+            |value: Option<&str>| value.map_or_else(|| "(not set)".to_string(), locales::label);
+        let mut changes = Vec::new();
+        for ((key, disk), read) in keys.iter().zip(&on_disk).zip(&last_read) {
+            let Some((_, new)) = cvars.iter().find(|(k, _)| k == key) else {
+                continue;
+            };
+            if disk != read && disk.as_deref() != Some(*new) {
+                changes.push(format!(
+                    "{}: {} on disk (was {}), to be written: {}",
+                    key,
+                    show(disk.as_deref()),
+                    show(read.as_deref()),
+                    show(Some(new))
+                ));
+            }
+        }
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let answer = rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Warning)
+            .set_title("Config.wtf changed on disk")
+            .set_description(format!(
+                "Config.wtf was changed since enTitan last read it:\n\n{}\n\nOverwrite the changed values?",
+                changes.join("\n")
+            ))
+            .set_buttons(rfd::MessageButtons::OkCancel)
+            .show();
+        if answer == rfd::MessageDialogResult::Ok {
+            return Ok(());
+        }
+        self.last_config_path = None;
+        self.update_locales();
+        Err("Config.wtf was changed on disk; not overwritten, its values are reloaded".into())
+    }
+
+    /// Write the preferred locales into the WoW entries of Battle.net.config.
+    fn sync_battle_net_config_file(&self) -> Result<(), String> {
+        let path = bnetconfig::default_path()
+            .filter(|p| p.is_file())
+            .ok_or("Config.wtf updated, but Battle.net.config was not found")?;
+        bnetconfig::sync(
+            &path,
+            &self.preferred_audio_locale,
+            &self.preferred_text_locale,
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Config.wtf updated, but not Battle.net.config: {}", e))
+    }
+}
+
+impl eframe::App for EntitanApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.unsaved = self.unsaved_changes();
+        if ctx.input(|i| i.viewport().close_requested())
+            && self.close_answered.is_none()
+            && !self.confirm_close()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        }
+        // Place the window at its saved physical position now that its monitor's scale is known
+        if let Some(pos) = self.restore_position.take() {
+            let pos = pos / ctx.pixels_per_point();
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
+        }
+        // A native dialog closed last frame: take the window and widget focus back
+        if let Some(id) = self.pending_focus.take() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            ctx.memory_mut(|m| m.request_focus(id));
+        }
+        i18n::set(self.ui_language);
+        self.handle_shortcuts(ctx, _frame);
+        if ctx.options(|o| o.theme_preference) != self.theme.preference() {
+            ctx.set_theme(self.theme.preference());
+        }
+        let animation_time = if self.reduce_motion() {
+            0.0
+        } else {
+            egui::Style::default().animation_time
+        };
+        if ctx.style().animation_time != animation_time {
+            ctx.style_mut(|s| s.animation_time = animation_time);
+        }
+        theme::set_high_contrast(self.high_contrast);
+        let text_color = theme::text_override(ctx.style().visuals.dark_mode);
+        if ctx.style().visuals.override_text_color != text_color {
+            ctx.style_mut(|s| s.visuals.override_text_color = text_color);
+        }
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // (Re)load the background image when its file, grayscale or tiling changed
+            let wanted = (
+                self.background_image.clone(),
+                self.background_grayscale,
+                self.background_scaling == background::Scaling::Tile,
+            );
+            if self.background_loaded.as_ref() != Some(&wanted) {
+                self.background = match background::load(
+                    ctx,
+                    &self.background_image,
+                    self.background_grayscale,
+                    self.background_scaling,
+                ) {
+                    Ok((bg, source)) => {
+                        if self.background_loaded.is_some() {
+                            self.status = Some(format!("Background image: {}", source));
                         }
+                        Some(bg)
                     }
-                    // reserve space for a second button so buttons align across rows
-                    ui.add_sized([btn_w, 24.0], egui::Label::new(""));
-                    ui.add_sized([right_pad, 24.0], egui::Label::new(""));
-                });
+                    Err(e) => {
+                        self.status = Some(format!("Failed to load the background image: {}", e));
+                        None
+                    }
+                };
+                self.background_loaded = Some(wanted);
+            }
 
-                ui.add_space(6.0);
+            // Optional tint layer, drawn under or over the background image
+            let tint = egui::Color32::from_rgba_unmultiplied(
+                self.background_tint[0],
+                self.background_tint[1],
+                self.background_tint[2],
+                self.background_tint[3],
+            );
+            if !self.tint_over_image {
+                ui.painter().rect_filled(ui.max_rect(), 0.0, tint);
+            }
+
+            if let Some(bg) = self.background.as_ref().filter(|_| !self.high_contrast) {
+                bg.paint(
+                    ui.painter(),
+                    ui.max_rect(),
+                    self.background_scaling,
+                    self.background_opacity,
+                );
+            }
+            if self.tint_over_image {
+                ui.painter().rect_filled(ui.max_rect(), 0.0, tint);
+            }
+
+            // refresh cached locales if config path changed
+            self.update_locales();
+
+            // update cached window geometry (so we can save on close without access to frame later)
+            let (size, outer, scale, maximized, minimized) = ctx.input(|i| {
+                (
+                    i.content_rect().size(),
+                    i.viewport().outer_rect,
+                    i.pixels_per_point,
+                    i.viewport().maximized,
+                    i.viewport().minimized,
+                )
+            });
+            if let Some(maximized) = maximized
+                && minimized != Some(true)
+            {
+                self.last_maximized = maximized;
+            }
+            // While the window moves to a monitor with another scale, size and position are
+            // briefly measured at different scales; skip the frame the scale changed on. A
+            // maximized or minimized window's rectangle isn't the one to restore.
+            let normal = maximized != Some(true) && minimized != Some(true);
+            if self.last_scale == Some(scale) && normal {
+                self.last_inner_size = Some((size.x, size.y));
+                if let Some(outer) = outer {
+                    self.last_window_pos = Some((outer.min.x, outer.min.y));
+                }
+            }
+            self.last_scale = Some(scale);
+
+            ui.vertical(|ui| {
+                // Top labels for game language (left-aligned and not stretched)
+                let label_w = 140.0;
+                let btn_w = 80.0;
+                let gap = 6.0;
+                let right_pad = 8.0; // reserve an explicit right padding for buttons below
+                let total_avail = ui.available_width();
+                let btn_count_max = 2.0; // reserve for up to two buttons (Browse + Run)
+                let text_w =
+                    (total_avail - label_w - btn_w * btn_count_max - gap - right_pad).max(8.0);
+                // Path fields leave room for their recent-paths drop-down and padlock
+                let path_w = (text_w - 56.0).max(8.0);
 
-                // WoW Executable row (aligned)
+                // Session indicator, whether or not enTitan launched the game
                 ui.horizontal(|ui| {
-                    ui.add_sized([label_w, 24.0], egui::Label::new("WoW Executable:"));
-                    ui.add_sized(
-                        [text_w, 24.0],
-                        egui::TextEdit::singleline(&mut self.wow_executable_path),
-                    );
-                    if ui
-                        .add_sized([btn_w, 24.0], egui::Button::new("Browse"))
-                        .clicked()
-                    {
-                        let mut dialog = FileDialog::new();
-                        if !self.wow_executable_path.is_empty() {
-                            if let Some(parent) = Path::new(&self.wow_executable_path).parent() {
-                                dialog = dialog.set_directory(parent);
-                            }
+                    match &self.running_game {
+                        Some(game) => ui.colored_label(
+                            theme::good(ui),
+                            format!(
+                                "● WoW running for {}",
+                                format_session(game.since.elapsed().unwrap_or_default())
+                            ),
+                        ),
+                        None => ui.weak("○ WoW not running"),
+                    };
+                });
+
+                if self.viewer() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            theme::warn(ui),
+                            "👁 Viewer mode: nothing is written, launched or saved",
+                        );
+                        if ui
+                            .add_enabled(!self.viewer_forced, egui::Button::new(tr("Leave")))
+                            .on_hover_text("Allow changes again")
+                            .on_disabled_hover_text("Started with --viewer; restart without it")
+                            .clicked()
+                        {
+                            self.viewer_mode = false;
+                            self.status = Some("Viewer mode off".into());
                         }
-                        if let Some(file) = dialog.add_filter("exe", &["exe"]).pick_file() {
-                            if is_file_with_ext(&file, "exe") {
-                                self.wow_executable_path = file.display().to_string();
-                                self.status = Some("Selected (unsaved)".into());
-                            } else {
-                                self.status = Some("Selected file is not an .exe".into());
-                            }
+                    });
+                }
+
+                // Banner for saved paths that stopped resolving since they were set
+                let mut fix = None;
+                for &field in &self.invalid_paths {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            theme::bad(ui),
+                            format!(
+                                "⚠ {} path no longer exists: {}",
+                                field.label(),
+                                self.path(field)
+                            ),
+                        );
+                        if ui
+                            .add_enabled(
+                                !self.locked(field) && !self.viewer(),
+                                egui::Button::new("Fix…"),
+                            )
+                            .on_disabled_hover_text("The field is locked; unlock it first")
+                            .clicked()
+                        {
+                            fix = Some(field);
                         }
-                    }
-                    ui.add_sized([right_pad, 24.0], egui::Label::new(""));
+                    });
+                }
+                if let Some(field) = fix {
+                    self.fix_path(field);
+                }
+                // Viewer mode shows the tabs but leaves the setup alone
+                let viewer = self.viewer();
+                ui.add_enabled_ui(!viewer, |ui| {
+                    self.blanked_addons_banner(ui);
+                    self.installation_controls(ui, label_w);
                 });
-            });
 
-            ui.separator();
+                ui.add_space(6.0);
+                tabs::bar(ui, &mut self.tab);
+                ui.separator();
+                let widths = tabs::Widths {
+                    label_w,
+                    btn_w,
+                    text_w,
+                    path_w,
+                    right_pad,
+                };
+                match self.tab {
+                    tabs::Tab::Paths => self.paths_tab(ui, &widths),
+                    tabs::Tab::Locale => self.locale_tab(ui, &widths),
+                    tabs::Tab::Launch => self.launch_tab(ui, &widths),
+                    tabs::Tab::Log => self.log_tab(ui, &widths),
+                }
+            });
             ui.add_space(12.0);
 
-            // If window is smaller than 600x400, show a warning
-            let screen_size = ctx.input(|i| i.content_rect().size());
-            let too_small = screen_size.x < 600.0 || screen_size.y < 400.0;
-            if too_small {
-                ui.colored_label(
-                    egui::Color32::from_rgb(200, 0, 0),
-                    "Window too small — enlarge to at least 600×400",
-                );
+            // If window is smaller than the minimum size, show a warning
+            let screen_size = ctx.input(|i| i.content_rect().size());
+            let limits = limits::get();
+            let too_small = screen_size.x < limits.min_window_width
+                || screen_size.y < limits.min_window_height;
+            if too_small {
+                ui.colored_label(
+                    theme::bad(ui),
+                    format!(
+                        "Window too small — enlarge to at least {}×{}",
+                        limits.min_window_width, limits.min_window_height
+                    ),
+                );
+                ui.add_space(6.0);
+            }
+
+            // Steps of the active run, or of the one stopped at a failed step
+            let run = self
+                .run
+                .as_ref()
+                .or_else(|| self.step_failure.as_ref().and_then(|f| f.run.as_ref()));
+            if let Some(run) = run {
+                timeline::show(ui, &run.timeline());
+                ui.add_space(6.0);
+            }
+
+            // Countdown of the current wait step of a run
+            if let Some((text, done)) = self.run.as_ref().and_then(|r| r.wait_progress()) {
+                ui.add(egui::ProgressBar::new(done).text(text));
+                ui.add_space(6.0);
+            }
+
+            if self.step_failure.is_some() {
+                self.step_failure_banner(ui, _frame);
                 ui.add_space(6.0);
             }
 
             // Bottom buttons (Run placed left of Close)
             ui.horizontal(|ui| {
                 // Run button starts the launch sequence (disabled while active)
-                let run_btn = ui.add_enabled(
-                    !self.run_active,
-                    egui::Button::new("Run").min_size(egui::vec2(80.0, 24.0)),
-                );
+                let run_btn = ui
+                    .add_enabled(
+                        self.run.is_none() && !self.viewer(),
+                        egui::Button::new(tr("Run")).min_size(egui::vec2(80.0, 24.0)),
+                    )
+                    .on_hover_text(input::Action::Run.hint(ctx, "Start the run sequence"));
                 if run_btn.clicked() {
-                    // validate paths first
-                    let p1 = Path::new(&self.battle_net_path);
-                    let p2 = Path::new(&self.wow_executable_path);
-                    if !(p1.exists() && is_file_with_ext(p1, "exe")) {
-                        self.status = Some("Battle.net path must point to an existing .exe".into());
-                    } else if !(p2.exists() && is_file_with_ext(p2, "exe")) {
-                        self.status = Some("WoW Executable must point to an existing .exe".into());
-                    } else {
-                        // set run_active, make window topmost, and spawn worker thread
-                        self.run_active = true;
-                        self.status = Some("Starting run sequence...".into());
-                        // Restore window if minimized and then attempt to set window topmost (best-effort)
-                        let _ = set_window_minimized(_frame, false);
-                        let _ = set_window_topmost(_frame, true);
-                        let tx = self.run_tx.clone();
-                        let battle_path = self.battle_net_path.clone();
-                        let wow_path = self.wow_executable_path.clone();
-                        std::thread::spawn(move || {
-                            use std::process::Command;
-                            use std::thread::sleep;
-                            use std::time::Duration;
-
-                            if let Err(e) = Command::new(&battle_path).spawn() {
-                                let _ = tx.send(format!("Failed to launch Battle.net: {}", e));
-                                let _ = tx.send("FINISHED".into());
-                                return;
-                            } else {
-                                let _ = tx.send("Launched Battle.net".into());
-                            }
+                    self.run_clicked(_frame);
+                }
+                self.run_controls(ui, _frame);
+                ui.add_space(8.0);
+                let dirty = !self.unsaved.is_empty();
+                if ui
+                    .add_enabled(
+                        dirty && !self.viewer(),
+                        egui::Button::new(tr("Save")).min_size(egui::vec2(80.0, 24.0)),
+                    )
+                    .on_hover_text(
+                        input::Action::Save.hint(ctx, "Write the settings to settings.json now"),
+                    )
+                    .clicked()
+                {
+                    self.save_clicked();
+                }
+                ui.add_space(8.0);
+                if ui
+                    .add_sized([80.0, 24.0], egui::Button::new(tr("Close")))
+                    .clicked()
+                {
+                    // Same as the title bar's close button: unsaved changes are asked about
+                    ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                if dirty {
+                    ui.add_space(8.0);
+                    ui.colored_label(theme::warn(ui), tr("● Unsaved changes"))
+                        .on_hover_text(format!("Changed: {}", self.unsaved.join(", ")));
+                }
+            });
 
-                            // 10-second countdown, send per-second updates
-                            for rem in (1..=10).rev() {
-                                let _ = tx.send(format!("Waiting to launch WoW: {}s", rem));
-                                sleep(Duration::from_secs(1));
-                            }
+            // Advance the run sequence and handle its status/finish events
+            let events = match self.run.as_mut() {
+                Some(run) => run.tick(Instant::now()),
+                None => Vec::new(),
+            };
+            for event in events {
+                match event {
+                    sequence::Event::Status(msg) => self.log_status(eventlog::Kind::Run, msg),
+                    sequence::Event::Countdown(msg) => {
+                        // Changes every second; kept out of the log
+                        self.status = Some(msg);
+                        self.logged_status = self.status.clone();
+                    }
+                    sequence::Event::Launched { name, child } => {
+                        if name == "WoW" {
+                            self.wow_launch = Some((Instant::now(), false));
+                        }
+                        let msg = format!("Launched {} (pid {}); it is running", name, child.pid());
+                        self.log_status(eventlog::Kind::Run, msg);
+                        // The sequence watched it for a moment before reporting it
+                        self.children.push(LaunchedChild {
+                            name,
+                            child,
+                            started: Instant::now()
+                                .checked_sub(sequence::LAUNCH_CHECK)
+                                .unwrap_or_else(Instant::now),
+                        });
+                    }
+                    sequence::Event::Warning(msg) => {
+                        self.log_status(eventlog::Kind::Run, msg);
+                    }
+                    sequence::Event::WriteLocale => self.write_locale_step(),
+                    sequence::Event::Output(msg) => {
+                        self.event_log.push(eventlog::Kind::Run, msg);
+                    }
+                    sequence::Event::Failed { message, recovery } => {
+                        let run = self.run.take();
+                        set_window_topmost(_frame, false);
+                        let Some(recovery) = recovery else {
+                            self.log_status(eventlog::Kind::Run, message);
+                            continue;
+                        };
+                        // The raw error stays in the log; the status line explains it
+                        self.event_log.push(
+                            eventlog::Kind::Run,
+                            format!("{}; {}", message, recovery.advice),
+                        );
+                        self.status = Some(recovery.advice.clone());
+                        self.logged_status = self.status.clone();
+                        self.step_failure = Some(StepFailure { recovery, run });
+                    }
+                    sequence::Event::Finished => {
+                        self.run = None;
+                        // clear topmost
+                        set_window_topmost(_frame, false);
+                        // minimize the window when the run completes (best-effort, Windows-only)
+                        let _ = set_window_minimized(_frame, true);
+                        self.log_status(eventlog::Kind::Run, "Run sequence completed".into());
+                        if self.broadcast_enabled {
+                            self.send_broadcast();
+                        }
+                    }
+                }
+            }
 
-                            match Command::new(&wow_path).spawn() {
-                                Ok(_child) => {
-                                    let _ = tx.send("Launched WoW".into());
-                                }
-                                Err(e) => {
-                                    let _ = tx.send(format!("Failed to launch WoW: {}", e));
-                                    let _ = tx.send("FINISHED".into());
-                                    return;
-                                }
-                            }
+            // Result of the startup clock drift check
+            if let Some(ref rx) = self.clock_rx {
+                match rx.try_recv() {
+                    Ok(result) => {
+                        if let Ok(offset) = result
+                            && offset.abs() > self.clock_drift_threshold_secs as f64
+                        {
+                            self.status = Some(format!(
+                                "Warning: the system clock is off by {:.0} s; server login may fail until it is synced",
+                                offset.abs()
+                            ));
+                        }
+                        self.clock_offset = Some(result);
+                        self.clock_rx = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.clock_rx = None,
+                }
+            }
+
+            // Outcome of a guild broadcast sent in the background
+            if let Some(ref rx) = self.broadcast_rx {
+                match rx.try_recv() {
+                    Ok(result) => {
+                        self.status = Some(match result {
+                            Ok(()) => "Guild broadcast sent".into(),
+                            Err(e) => format!("Guild broadcast failed: {}", e),
+                        });
+                        self.broadcast_rx = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.broadcast_rx = None,
+                }
+            }
 
-                            // 60-second countdown with per-second updates
-                            for rem in (1..=60).rev() {
-                                let _ = tx.send(format!(
-                                    "Waiting before re-launching Battle.net: {}s",
-                                    rem
+            // Results of the hosts/DNS check and of disabling stale hosts entries
+            if let Some(ref rx) = self.hosts_rx {
+                match rx.try_recv() {
+                    Ok(diagnosis) => {
+                        let problems = diagnosis.results.iter().filter(|r| r.is_err()).count();
+                        self.status = Some(if problems == 0 {
+                            "Realm: hosts/DNS check passed".into()
+                        } else {
+                            format!("Realm: hosts/DNS check found {} problem(s)", problems)
+                        });
+                        self.hosts_diagnosis = Some(diagnosis);
+                        self.hosts_rx = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.hosts_rx = None,
+                }
+            }
+            if let Some(ref rx) = self.hosts_fix_rx {
+                match rx.try_recv() {
+                    Ok(result) => {
+                        self.hosts_fix_rx = None;
+                        match result {
+                            Ok(backup) => {
+                                self.status = Some(format!(
+                                    "Disabled the stale hosts entries (previous file saved as {})",
+                                    backup.display()
                                 ));
-                                sleep(Duration::from_secs(1));
+                                self.check_realm_host();
                             }
-
-                            if let Err(e) = Command::new(&battle_path).spawn() {
-                                let _ =
-                                    tx.send(format!("Failed to launch Battle.net (second): {}", e));
-                            } else {
-                                let _ = tx.send("Launched Battle.net (second)".into());
+                            Err(e) => {
+                                self.status =
+                                    Some(format!("Could not change the hosts file: {}", e))
                             }
+                        }
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.hosts_fix_rx = None,
+                }
+            }
 
-                            let _ = tx.send("FINISHED".into());
+            // Result of the firewall helper
+            if let Some(ref rx) = self.firewall_rx {
+                match rx.try_recv() {
+                    Ok(result) => {
+                        self.status = Some(match result {
+                            Ok(()) => {
+                                self.firewall_hint = false;
+                                "Firewall rules for Battle.net and WoW created".into()
+                            }
+                            Err(e) => format!("Firewall: could not create rules: {}", e),
                         });
+                        self.firewall_rx = None;
                     }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.firewall_rx = None,
                 }
-                ui.add_space(8.0);
-                if ui
-                    .add_sized([80.0, 24.0], egui::Button::new("Close"))
-                    .clicked()
-                {
-                    let p1 = Path::new(&self.battle_net_path);
-                    let p2 = Path::new(&self.config_wtf_path);
-                    let p3 = Path::new(&self.wow_executable_path);
-                    if p1.exists()
-                        && is_file_with_ext(p1, "exe")
-                        && p2.exists()
-                        && is_file_with_ext(p2, "wtf")
-                        && p3.exists()
-                        && is_file_with_ext(p3, "exe")
-                    {
-                        // Use cached geometry
-                        let pos_opt = self.last_window_pos;
-                        let size_opt = self.last_inner_size;
-                        if let Err(e) = save_settings(
-                            &self.battle_net_path,
-                            &self.config_wtf_path,
-                            &self.wow_executable_path,
-                            &self.preferred_locale,
-                            pos_opt,
-                            size_opt,
-                        ) {
-                            self.status = Some(format!("Error saving: {}", e));
-                        } else {
-                            std::process::exit(0);
-                        }
-                    } else {
-                        let mut msgs = vec![];
-                        if !(p1.exists() && is_file_with_ext(p1, "exe")) {
-                            msgs.push("Battle.net path must point to an existing .exe");
-                        }
-                        if !(p2.exists() && is_file_with_ext(p2, "wtf")) {
-                            msgs.push("Config.wtf path must point to an existing .wtf file");
-                        }
-                        if !(p3.exists() && is_file_with_ext(p3, "exe")) {
-                            msgs.push("WoW Executable must point to an existing .exe file");
+            }
+
+            // AddOns.txt files found blanked after the client exited
+            if let Some(ref rx) = self.blanked_rx {
+                match rx.try_recv() {
+                    Ok(blanked) => {
+                        self.blanked_rx = None;
+                        if self.protect_addons {
+                            self.blanked_addons = blanked;
                         }
-                        self.status = Some(msgs.join("; ").into());
                     }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.blanked_rx = None,
                 }
-            });
+            }
 
-            // Drain run-thread messages to update status and handle finish events
-            while let Ok(msg) = self.run_rx.try_recv() {
-                if msg == "FINISHED" {
-                    self.run_active = false;
-                    // clear topmost
-                    set_window_topmost(_frame, false);
-                    // minimize the window when the run completes (best-effort, Windows-only)
-                    let _ = set_window_minimized(_frame, true);
-                    self.status = Some("Run sequence completed".into());
-                } else {
-                    self.status = Some(msg);
+            // Test launch as administrator, once the UAC prompt was answered
+            if let Some(ref rx) = self.test_launch_rx {
+                match rx.try_recv() {
+                    Ok((name, options, started)) => {
+                        self.test_launch_rx = None;
+                        self.test_started(name, &options, started);
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => self.test_launch_rx = None,
+                }
+            }
+
+            // Whether the firewall may have blocked a WoW that closed right after launching
+            if let Some(ref rx) = self.firewall_check_rx {
+                match rx.try_recv() {
+                    Ok(exist) => {
+                        if exist == Some(false) {
+                            self.firewall_hint = true;
+                            self.status = Some(
+                                "WoW closed right after launching; if it couldn't connect, the firewall may be blocking it (see Firewall)".into(),
+                            );
+                        }
+                        self.firewall_check_rx = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        self.firewall_check_rx = None
+                    }
                 }
             }
 
@@ -755,17 +4499,30 @@ impl eframe::App for EntitanApp {
                 for res in events {
                     match res {
                         Ok(event) => {
+                            let removed = matches!(event.kind, notify::EventKind::Remove(_));
                             for path in event.paths {
-                                if !self.config_wtf_path.is_empty() {
-                                    if Path::new(&self.config_wtf_path) == path.as_path() {
-                                        // Force refresh immediately
-                                        self.last_config_path = None;
-                                        self.update_locales();
-                                        self.status =
-                                            Some("Config.wtf changed on disk; reloaded".into());
-                                        ctx.request_repaint();
-                                        break;
-                                    }
+                                // A removed WTF directory takes its watch with it; let the retry re-register
+                                if removed && self.watched_path.as_deref() == Some(path.as_path())
+                                {
+                                    self.drop_watch();
+                                    self.last_config_path = None;
+                                    self.update_locales();
+                                    break;
+                                }
+                                // Delete, create and rename-into-place of Config.wtf all land here
+                                if !self.config_wtf_path.is_empty()
+                                    && Path::new(&self.config_wtf_path) == path.as_path()
+                                {
+                                    // Force refresh immediately
+                                    self.last_config_path = None;
+                                    self.update_locales();
+                                    self.log_status(
+                                        eventlog::Kind::Status,
+                                        "Config.wtf changed on disk; reloaded".into(),
+                                    );
+                                    self.schedule_enforcement();
+                                    ctx.request_repaint();
+                                    break;
                                 }
                             }
                         }
@@ -776,156 +4533,856 @@ impl eframe::App for EntitanApp {
                 }
             }
 
-            // If a run is active, request repaint every second so countdown messages update even without user input
-            if self.run_active {
-                ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            // Rewrite Config.wtf once Battle.net has stopped touching it
+            if let Some(wait) = self.enforce_locale_if_due() {
+                ctx.request_repaint_after(wait);
+            }
+
+            // Re-validate the saved paths daily
+            let next_check = self.check_paths();
+            ctx.request_repaint_after(next_check);
+
+            // Keep the running-game locale check current
+            let next_check = self.check_running_game();
+            ctx.request_repaint_after(next_check);
+
+            // Sort new screenshots once WoW has finished writing them
+            if let Some(wait) = self.update_screenshot_organizer() {
+                ctx.request_repaint_after(wait);
+            }
+
+            // Re-attempt a failed WTF directory watch and keep repainting so the retry actually runs
+            self.ensure_watch();
+            if self.watched_path.is_none() && !self.config_wtf_path.is_empty() {
+                ctx.request_repaint_after(WATCH_RETRY_INTERVAL);
+            }
+
+            // Run the next frame right away when it has a widget to focus
+            if self.pending_focus.is_some() {
+                ctx.request_repaint();
+            }
+
+            // Start an armed scheduled run once due, repainting for its countdown meanwhile
+            if let Some(wait) = self.check_schedule(_frame) {
+                ctx.request_repaint_after(wait);
+            }
+
+            // If a run is active, repaint when the countdown next changes so it updates without user input
+            if let Some(wait) = self.run.as_ref().and_then(|r| r.next_tick_in()) {
+                ctx.request_repaint_after(wait);
             }
 
             if let Some(ref s) = self.status {
                 ui.add_space(6.0);
                 ui.label(s);
             }
+            // Record new status messages in the event log
+            if self.status != self.logged_status {
+                if let Some(ref s) = self.status {
+                    self.event_log.push(eventlog::Kind::Status, s.clone());
+                }
+                self.logged_status = self.status.clone();
+            }
+
+            if let Some(ref url) = self.news_url {
+                ui.add_space(6.0);
+                ui.hyperlink_to("News", url);
+            }
         });
+        self.detached_log_window(ctx);
+        self.update_window_title(ctx);
     }
 
     // Called when eframe wants to save app state (on shutdown or periodically)
     fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        // Viewer mode leaves settings.json as it found it
+        if self.viewer() {
+            return;
+        }
         // attempt to save using cached geometry
-        let _ = save_settings(
-            &self.battle_net_path,
-            &self.config_wtf_path,
-            &self.wow_executable_path,
-            &self.preferred_locale,
-            self.last_window_pos,
-            self.last_inner_size,
-        );
+        let _ = save_settings(&self.settings_snapshot());
     }
 
     // Called once on exit; ensure we persist settings here as a fallback
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        let _ = save_settings(
-            &self.battle_net_path,
-            &self.config_wtf_path,
-            &self.wow_executable_path,
-            &self.preferred_locale,
-            self.last_window_pos,
-            self.last_inner_size,
-        );
+        if self.viewer() {
+            return;
+        }
+        // Changes the user chose to discard on closing stay out; the window placement is kept
+        let _ = if self.close_answered == Some(false) {
+            self.save_discarding_changes()
+        } else {
+            self.save_now()
+        };
     }
 }
 
 fn settings_file_path() -> Option<PathBuf> {
-    // Use JSON filename from now on
-    let fname = "settings.json";
-    if cfg!(target_os = "windows") {
-        env::var("APPDATA")
-            .ok()
-            .map(|a| PathBuf::from(a).join("entitan").join(fname))
-    } else {
-        if let Ok(xdg) = env::var("XDG_CONFIG_HOME") {
-            Some(PathBuf::from(xdg).join("entitan").join(fname))
-        } else if let Ok(home) = env::var("HOME") {
-            Some(
-                PathBuf::from(home)
-                    .join(".config")
-                    .join("entitan")
-                    .join(fname),
-            )
-        } else {
-            None
-        }
-    }
+    settings_dir().map(|d| d.join("settings.json"))
+}
+
+/// Directory holding `settings.json` and other per-user data (snapshots, lock file); see
+/// `location` for where it is.
+fn settings_dir() -> Option<PathBuf> {
+    location::current()
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 struct SettingsFile {
-    #[serde(rename = "launcher", alias = "battle")]
+    // Layout version, see `migrate`; missing in files written before versioning
+    #[serde(rename = "schemaVersion", default)]
+    schema_version: u32,
+    #[serde(rename = "launcher")]
     launcher: String,
     config: String,
-    #[serde(rename = "wowExecutable", alias = "wow")]
+    #[serde(rename = "wowExecutable")]
     wow_executable: String,
-    #[serde(rename = "preferredLocale")]
-    preferred_locale: String,
+    // Extra command-line arguments for the launcher and WoW
+    #[serde(rename = "launcherArgs")]
+    launcher_args: String,
+    #[serde(rename = "wowArgs")]
+    wow_args: String,
+    // Hook commands around the run sequence
+    #[serde(rename = "preLaunchHook")]
+    pre_launch_hook: String,
+    #[serde(rename = "preLaunchHookCritical")]
+    pre_launch_hook_critical: bool,
+    #[serde(rename = "postLaunchHook")]
+    post_launch_hook: String,
+    #[serde(rename = "postLaunchHookCritical")]
+    post_launch_hook_critical: bool,
+    // Hold-to-confirm for buttons that write Config.wtf or the hosts file
+    #[serde(rename = "confirmWrites")]
+    confirm_writes: bool,
+    // Open read-only, for inspecting a setup without changing it
+    #[serde(rename = "viewerMode")]
+    viewer_mode: bool,
+    // Padlocks of the three path fields
+    #[serde(rename = "battleNetLocked")]
+    battle_net_locked: bool,
+    #[serde(rename = "configLocked")]
+    config_wtf_locked: bool,
+    #[serde(rename = "wowLocked")]
+    wow_locked: bool,
+    // Earlier values of the three path fields, most recent first
+    #[serde(rename = "recentPaths")]
+    recent_paths: recent::RecentPaths,
+    // Launch the executable elevated, through ShellExecute's "runas" verb
+    #[serde(rename = "battleNetElevated")]
+    battle_net_elevated: bool,
+    #[serde(rename = "wowElevated")]
+    wow_elevated: bool,
+    // Scheduling of the WoW process, for old CPUs where the client stutters
+    #[serde(rename = "wowPriority")]
+    wow_priority: launch::Priority,
+    #[serde(rename = "wowAffinity")]
+    wow_affinity: u64,
+    // Time of day last used for a scheduled run
+    #[serde(rename = "scheduledTime")]
+    scheduled_time: String,
+    // Text size of the detached event log window
+    #[serde(rename = "logFontSize")]
+    log_font_size: f32,
+    // Realm hostname for the hosts file check, overriding Config.wtf's realmList
+    #[serde(rename = "realmHost")]
+    realm_host: String,
+    // Measure the latency to the realm before launching; abort or only warn above the limit
+    #[serde(rename = "latencyCheck")]
+    latency_check: bool,
+    #[serde(rename = "latencyThresholdMs")]
+    latency_threshold_ms: u64,
+    #[serde(rename = "latencyAbort")]
+    latency_abort: bool,
+    #[serde(rename = "preferredAudioLocale")]
+    preferred_audio_locale: String,
+    #[serde(rename = "preferredTextLocale")]
+    preferred_text_locale: String,
+    // Wait for the launched processes to appear before the delays below start
+    #[serde(rename = "processAwareWaits")]
+    process_aware_waits: bool,
+    #[serde(rename = "waitForLoginWindow")]
+    wait_for_login_window: bool,
+    // Run these steps instead of the built-in Battle.net → WoW → Battle.net flow
+    #[serde(rename = "useCustomSequence")]
+    use_custom_sequence: bool,
+    #[serde(rename = "customSteps")]
+    custom_steps: Vec<script::ScriptStep>,
+    // Waits before launching WoW and before launching Battle.net the second time
+    #[serde(rename = "wowLaunchDelaySecs")]
+    wow_launch_delay_secs: u64,
+    #[serde(rename = "relaunchDelaySecs")]
+    relaunch_delay_secs: u64,
+    // Skip the first Battle.net and the WoW launch when those programs already run
+    #[serde(rename = "skipRunningPrograms")]
+    skip_running_programs: bool,
+    // Battle.net may rewrite Config.wtf while the run waits; write the locales once more
+    #[serde(rename = "writeLocaleBeforeWow")]
+    write_locale_before_wow: bool,
+    // End the run by closing Battle.net (WM_CLOSE, then terminate) instead of relaunching it
+    #[serde(rename = "closeBattleNetAfterWow")]
+    close_battle_net_after_wow: bool,
+    // Hold the final Battle.net relaunch until the user has been idle for `relaunchIdleSecs`
+    #[serde(rename = "relaunchWaitForIdle")]
+    relaunch_wait_for_idle: bool,
+    #[serde(rename = "relaunchIdleSecs")]
+    relaunch_idle_secs: u64,
+    // Pause after launching Battle.net until the user confirms they are logged in
+    #[serde(rename = "confirmLogin")]
+    confirm_login: bool,
+    // Rewrite Config.wtf automatically when Battle.net resets the locales
+    #[serde(rename = "enforceLocale")]
+    enforce_locale: bool,
+    // Update Battle.net's per-game language together with Config.wtf
+    #[serde(rename = "syncBattleNetConfig")]
+    sync_battle_net_config: bool,
+    // Save the characters' AddOns.txt before a locale write, offering to restore them
+    #[serde(rename = "protectAddons")]
+    protect_addons: bool,
+    // Report the locale the client actually loaded after each session
+    #[serde(rename = "verifyLocale")]
+    verify_locale: bool,
+    // Sort new screenshots into Screenshots/<textLocale>
+    #[serde(rename = "organizeScreenshots")]
+    organize_screenshots: bool,
+    // Compare the system clock with an NTP server at startup
+    #[serde(rename = "checkClockDrift")]
+    check_clock_drift: bool,
+    #[serde(rename = "ntpServer")]
+    ntp_server: String,
+    #[serde(rename = "clockDriftThresholdSecs")]
+    clock_drift_threshold_secs: u64,
+    // Start children with the shell's non-elevated token when enTitan runs elevated
+    #[serde(rename = "deElevateChildren")]
+    de_elevate_children: bool,
+    // Variables (names or `PREFIX_*`) removed from the environment of launched programs
+    #[serde(rename = "sanitizeEnvironment")]
+    sanitize_environment: bool,
+    #[serde(rename = "environmentBlocklist")]
+    environment_blocklist: String,
+    // Background image file (empty: background.* or the embedded one), opacity, grayscale and
+    // how it fills the window
+    #[serde(rename = "backgroundImage")]
+    background_image: String,
+    #[serde(rename = "backgroundOpacity")]
+    background_opacity: f32,
+    #[serde(rename = "backgroundGrayscale")]
+    background_grayscale: bool,
+    #[serde(rename = "backgroundScaling")]
+    background_scaling: background::Scaling,
+    // Solid color (RGBA, unmultiplied) layered under or over the background image
+    #[serde(rename = "backgroundTint")]
+    background_tint: [u8; 4],
+    #[serde(rename = "tintOverImage")]
+    tint_over_image: bool,
+    // eframe renderer and CPU rasterizer fallback, for GPUs that can't open the window
+    renderer: renderer::Backend,
+    #[serde(rename = "softwareRendering")]
+    software_rendering: bool,
+    theme: theme::Theme,
+    #[serde(rename = "uiLanguage")]
+    ui_language: i18n::Language,
+    // For motion-sensitive users: no collapsing/spinner animations
+    #[serde(rename = "disableAnimations")]
+    disable_animations: bool,
+    // Blue/orange instead of green/red, text at full contrast and no background image
+    #[serde(rename = "highContrast")]
+    high_contrast: bool,
+    // Webhook notified when a run completes, with its JSON payload template
+    #[serde(rename = "broadcastEnabled")]
+    broadcast_enabled: bool,
+    #[serde(rename = "broadcastUrl")]
+    broadcast_url: String,
+    #[serde(rename = "broadcastTemplate")]
+    broadcast_template: String,
+    #[serde(rename = "playerName")]
+    player_name: String,
+    // Snapshot whose notes are shown on the launcher tab
+    #[serde(rename = "activeProfile")]
+    active_profile: Option<String>,
+    // Named installations and the one whose values the fields above hold
+    installations: Vec<installs::Installation>,
+    #[serde(rename = "activeInstallation")]
+    active_installation: Option<String>,
     geometry: Option<Geometry>,
+    // Advanced safety limits, only edited by hand in this file
+    limits: limits::Limits,
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
+impl Default for SettingsFile {
+    fn default() -> Self {
+        Self {
+            schema_version: migrate::CURRENT_VERSION,
+            launcher: String::new(),
+            config: String::new(),
+            wow_executable: String::new(),
+            launcher_args: String::new(),
+            wow_args: String::new(),
+            pre_launch_hook: String::new(),
+            pre_launch_hook_critical: false,
+            post_launch_hook: String::new(),
+            post_launch_hook_critical: false,
+            confirm_writes: false,
+            viewer_mode: false,
+            battle_net_locked: false,
+            config_wtf_locked: false,
+            wow_locked: false,
+            recent_paths: recent::RecentPaths::default(),
+            battle_net_elevated: false,
+            wow_elevated: false,
+            wow_priority: launch::Priority::Normal,
+            wow_affinity: 0,
+            scheduled_time: String::new(),
+            log_font_size: DEFAULT_LOG_FONT_SIZE,
+            realm_host: String::new(),
+            latency_check: false,
+            latency_threshold_ms: DEFAULT_LATENCY_THRESHOLD_MS,
+            latency_abort: true,
+            preferred_audio_locale: String::new(),
+            preferred_text_locale: String::new(),
+            process_aware_waits: true,
+            wait_for_login_window: false,
+            use_custom_sequence: false,
+            custom_steps: Vec::new(),
+            wow_launch_delay_secs: DEFAULT_WOW_LAUNCH_DELAY_SECS,
+            relaunch_delay_secs: DEFAULT_RELAUNCH_DELAY_SECS,
+            skip_running_programs: true,
+            write_locale_before_wow: true,
+            close_battle_net_after_wow: false,
+            relaunch_wait_for_idle: false,
+            relaunch_idle_secs: DEFAULT_RELAUNCH_IDLE_SECS,
+            confirm_login: false,
+            enforce_locale: false,
+            check_clock_drift: false,
+            ntp_server: DEFAULT_NTP_SERVER.to_string(),
+            clock_drift_threshold_secs: DEFAULT_CLOCK_DRIFT_THRESHOLD_SECS,
+            sync_battle_net_config: false,
+            protect_addons: false,
+            verify_locale: false,
+            organize_screenshots: false,
+            de_elevate_children: true,
+            sanitize_environment: false,
+            environment_blocklist: launch::DEFAULT_ENV_REMOVE.to_string(),
+            background_image: String::new(),
+            background_opacity: 0.1,
+            background_grayscale: true,
+            background_scaling: background::Scaling::Cover,
+            background_tint: [0, 0, 0, 0],
+            tint_over_image: true,
+            renderer: renderer::Backend::Glow,
+            software_rendering: false,
+            theme: theme::Theme::System,
+            ui_language: i18n::Language::System,
+            disable_animations: false,
+            high_contrast: false,
+            broadcast_enabled: false,
+            broadcast_url: String::new(),
+            broadcast_template: DEFAULT_BROADCAST_TEMPLATE.to_string(),
+            player_name: String::new(),
+            active_profile: None,
+            installations: Vec::new(),
+            active_installation: None,
+            geometry: None,
+            limits: limits::Limits::default(),
+        }
+    }
+}
+
+/// Window geometry in logical points: outer position and inner size.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct Geometry {
-    x: i32,
-    y: i32,
+    x: f32,
+    y: f32,
     w: f32,
     h: f32,
+    // Pixels per point the position was measured at; absent in older settings, whose
+    // position was stored in physical pixels
+    #[serde(default)]
+    scale: Option<f32>,
+    // Closed maximized; the position above is where it goes when restored
+    #[serde(default)]
+    maximized: bool,
+    // Device name of the monitor the window was on (Windows only)
+    #[serde(default)]
+    monitor: Option<String>,
 }
 
-// Loads battle, config, wow, preferred locale and optional geometry (x,y,w,h)
-// Reads settings exclusively from `settings.json`.
-fn load_settings_full() -> (String, String, String, String, Option<(i32, i32, f32, f32)>) {
-    let path = match settings_file_path() {
-        Some(p) => p,
-        None => {
-            return (
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                None,
-            );
+impl SettingsFile {
+    /// Reset values no enTitan writes (hand edits, a file patched together from others) to
+    /// their defaults. Returns a message per reset value.
+    fn repair(&mut self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(g) = &self.geometry
+            && !g.is_plausible()
+        {
+            self.geometry = None;
+            problems.push("Saved window position/size was invalid; using the default".into());
+        }
+        for (name, path) in [
+            ("Battle.net", &mut self.launcher),
+            ("Config.wtf", &mut self.config),
+            ("WoW", &mut self.wow_executable),
+        ] {
+            if path.chars().any(char::is_control) {
+                path.clear();
+                problems.push(format!(
+                    "Saved {} path contained control characters; cleared it",
+                    name
+                ));
+            }
+        }
+        if !self.log_font_size.is_finite() {
+            self.log_font_size = DEFAULT_LOG_FONT_SIZE;
+        }
+        problems
+    }
+}
+
+impl Geometry {
+    /// Whether the values can be a real window: finite, a positive size no larger than any
+    /// screen, a sensible scale.
+    fn is_plausible(&self) -> bool {
+        let values = [self.x, self.y, self.w, self.h];
+        values.iter().all(|v| v.is_finite() && v.abs() < 100_000.0)
+            && self.w > 0.0
+            && self.h > 0.0
+            && self
+                .scale
+                .is_none_or(|s| s.is_finite() && (0.25..=8.0).contains(&s))
+    }
+
+    /// Position in physical pixels, kept on the monitor it was saved on while that is still
+    /// connected (it may sit left of or above the primary one), otherwise clamped so the
+    /// window isn't placed off-screen.
+    fn physical_position(&self) -> egui::Pos2 {
+        let scale = self.scale.unwrap_or(1.0);
+        let (x, y) = (self.x * scale, self.y * scale);
+        if let Some(name) = &self.monitor
+            && let Some(monitor) = monitors::all().into_iter().find(|m| &m.name == name)
+        {
+            // A title bar partly off the top or left edge still counts as on the monitor
+            return if monitor.contains(x as i32 + 100, y as i32 + 20) {
+                egui::pos2(x, y)
+            } else {
+                egui::pos2(monitor.left as f32, monitor.top as f32)
+            };
         }
+        egui::pos2(x.max(0.0), y.max(0.0))
+    }
+}
+
+// Loads the persisted settings (paths, preferred locale and optional window geometry).
+// Reads settings exclusively from `settings.json`, upgraded from older layouts by `migrate`;
+// a missing file yields the first-run defaults.
+fn load_settings_full() -> SettingsFile {
+    load_settings_checked().0
+}
+
+/// Load settings like `load_settings_full`, also returning what was wrong with the stored
+/// file. A damaged settings.json (truncated, not JSON) is set aside and the copy kept by the
+/// last save (`settings.json.bak`) takes its place; implausible values are reset.
+fn load_settings_checked() -> (SettingsFile, Vec<String>) {
+    let Some(path) = settings_file_path().filter(|p| p.exists()) else {
+        return (first_run_settings(), Vec::new());
     };
 
-    if path.exists() {
-        if let Ok(contents) = fs::read_to_string(&path) {
-            if let Ok(s) = serde_json::from_str::<SettingsFile>(&contents) {
-                let geom = s.geometry.map(|g| (g.x, g.y, g.w, g.h));
-                return (
-                    s.launcher,
-                    s.config,
-                    s.wow_executable,
-                    s.preferred_locale,
-                    geom,
-                );
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            let msg = format!("Cannot read {}: {}", path.display(), e);
+            eprintln!("{}", msg);
+            return (SettingsFile::default(), vec![msg]);
+        }
+    };
+    let (mut settings, mut problems) = match parse_settings(&path, &text) {
+        Ok(settings) => (settings, Vec::new()),
+        Err(e) => {
+            // Set the broken file aside instead of overwriting it with defaults on save
+            let aside = path.with_file_name("settings.invalid.json");
+            let _ = fs::rename(&path, &aside);
+            let backup = path.with_file_name(SETTINGS_BACKUP);
+            let recovered = fs::read_to_string(&backup)
+                .map_err(|e| e.to_string())
+                .and_then(|text| parse_settings(&backup, &text));
+            match recovered {
+                Ok(settings) => {
+                    // Make the copy the live file again, so the next load finds it too
+                    let _ = fs::copy(&backup, &path);
+                    (
+                        settings,
+                        vec![format!(
+                            "settings.json was damaged ({}); restored the copy from the last save. The damaged file was moved to {}",
+                            e,
+                            aside.display()
+                        )],
+                    )
+                }
+                Err(_) => (
+                    first_run_settings(),
+                    vec![format!(
+                        "settings.json was damaged ({}) and there is no usable backup; starting from defaults. The damaged file was moved to {}",
+                        e,
+                        aside.display()
+                    )],
+                ),
             }
         }
+    };
+    problems.extend(settings.repair());
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+    (settings, problems)
+}
+
+/// Parse the contents `text` of the settings file `path`, upgrading an older layout.
+fn parse_settings(path: &Path, text: &str) -> Result<SettingsFile, String> {
+    let mut value = serde_json::from_str::<serde_json::Value>(text).map_err(|e| e.to_string())?;
+    let version = migrate::migrate(&mut value);
+    if version < migrate::CURRENT_VERSION {
+        // Keep the file as it was for going back to an older enTitan
+        let backup = path.with_file_name(format!("settings.v{}.json", version));
+        if !backup.exists() {
+            let _ = fs::write(&backup, text);
+        }
+    } else if version > migrate::CURRENT_VERSION {
+        eprintln!(
+            "{} was written by a newer enTitan (settings version {}); settings it added are dropped on save",
+            path.display(),
+            version
+        );
     }
+    serde_json::from_value::<SettingsFile>(value).map_err(|e| e.to_string())
+}
 
-    (
-        String::new(),
-        String::new(),
-        String::new(),
-        String::new(),
-        None,
-    )
+// Settings used before anything was saved: built-in defaults plus any distributor `defaults.toml`
+fn first_run_settings() -> SettingsFile {
+    let mut settings = SettingsFile::default();
+    // Start from the OS display language; the distributor's defaults still win
+    if let Some(locale) = locales::from_os() {
+        settings.preferred_audio_locale = locale.to_string();
+        settings.preferred_text_locale = locale.to_string();
+    }
+    if let Some(defaults) = defaults::Defaults::load() {
+        defaults.apply(&mut settings);
+    }
+    settings
 }
 
-fn save_settings(
-    battle: &str,
-    config: &str,
-    wow: &str,
-    preferred: &str,
-    position: Option<(i32, i32)>,
-    size: Option<(f32, f32)>,
-) -> std::io::Result<()> {
-    let path = settings_file_path().ok_or_else(|| {
-        std::io::Error::new(std::io::ErrorKind::Other, "cannot determine settings path")
-    })?;
+fn save_settings(settings: &SettingsFile) -> std::io::Result<()> {
+    let path = settings_file_path()
+        .ok_or_else(|| std::io::Error::other("cannot determine settings path"))?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let settings = SettingsFile {
-        launcher: battle.to_string(),
-        config: config.to_string(),
-        wow_executable: wow.to_string(),
-        preferred_locale: preferred.to_string(),
-        geometry: match (position, size) {
-            (Some((x, y)), Some((w, h))) => Some(Geometry { x, y, w, h }),
-            _ => None,
-        },
-    };
+    // Keep the previous file for `load_settings_checked` to fall back on; a damaged one must
+    // not replace a good copy
+    if fs::read(&path)
+        .ok()
+        .is_some_and(|b| serde_json::from_slice::<serde_json::Value>(&b).is_ok())
+    {
+        let _ = fs::copy(&path, path.with_file_name(SETTINGS_BACKUP));
+    }
+    // Replace the file in one step so a crash mid-write can't leave it truncated
+    wtf::write_atomic(&path, &serde_json::to_vec_pretty(settings)?)
+}
+
+/// Add the Windows Korean and Chinese fonts, where installed, as fallbacks after egui's
+/// built-in ones, which lack those scripts (e.g. the native names in the locale picker).
+/// They are about 45 MB, so they are read on a background thread the first time text needs
+/// them rather than at every startup.
+fn load_fallback_fonts(ctx: &egui::Context) {
+    static STARTED: std::sync::Once = std::sync::Once::new();
+    STARTED.call_once(|| {
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let fonts_dir = env::var_os("WINDIR")
+                .map_or_else(|| PathBuf::from(r"C:\Windows"), PathBuf::from)
+                .join("Fonts");
+            let mut fonts = egui::FontDefinitions::default();
+            let mut added = false;
+            for file in ["malgun.ttf", "msyh.ttc", "msjh.ttc"] {
+                let Ok(data) = fs::read(fonts_dir.join(file)) else {
+                    continue;
+                };
+                fonts
+                    .font_data
+                    .insert(file.to_string(), egui::FontData::from_owned(data).into());
+                for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+                    fonts
+                        .families
+                        .entry(family)
+                        .or_default()
+                        .push(file.to_string());
+                }
+                added = true;
+            }
+            if added {
+                ctx.set_fonts(fonts);
+                ctx.request_repaint();
+            }
+        });
+    });
+}
+
+/// Whether `text` has Korean or Chinese characters, which need `load_fallback_fonts`.
+fn needs_fallback_fonts(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            c,
+            '\u{1100}'..='\u{11FF}'
+                | '\u{2E80}'..='\u{9FFF}'
+                | '\u{AC00}'..='\u{D7AF}'
+                | '\u{F900}'..='\u{FAFF}'
+        )
+    })
+}
+
+/// Drop-down of the known client locales ("deDE — German (Germany) · Deutsch") bound to
+/// `locale`. Returns the drop-down's response, for tying it to its label.
+fn locale_combo(ui: &mut egui::Ui, id: &str, locale: &mut String, width: f32) -> egui::Response {
+    let selected = locales::picker_label(locale);
+    let wants_fonts = needs_fallback_fonts(&selected);
+    let combo = egui::ComboBox::from_id_salt(id)
+        .width(width)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (code, ..) in locales::KNOWN_LOCALES {
+                ui.selectable_value(locale, code.to_string(), locales::picker_label(code));
+            }
+        });
+    // The open list shows every native name
+    if wants_fonts || combo.inner.is_some() {
+        load_fallback_fonts(ui.ctx());
+    }
+    combo.response
+}
+
+/// Drop-down of the earlier values of a path field, other than `current`. Returns the one
+/// picked.
+fn recent_paths_combo(
+    ui: &mut egui::Ui,
+    field: PathField,
+    recent: &[String],
+    current: &str,
+    enabled: bool,
+) -> Option<String> {
+    let others: Vec<&String> = recent
+        .iter()
+        .filter(|p| !p.eq_ignore_ascii_case(current))
+        .collect();
+    let mut picked = None;
+    ui.add_enabled_ui(enabled && !others.is_empty(), |ui| {
+        egui::ComboBox::from_id_salt(("recent_paths", field))
+            .width(16.0)
+            .selected_text("")
+            .show_ui(ui, |ui| {
+                ui.set_min_width(320.0);
+                for path in others {
+                    let exists = is_file_with_ext(path, field.ext());
+                    let response = ui.add_enabled(exists, egui::Button::selectable(false, path));
+                    if response
+                        .on_disabled_hover_text("This file no longer exists")
+                        .clicked()
+                    {
+                        picked = Some(path.clone());
+                    }
+                }
+            })
+            .response
+            .named(
+                egui::WidgetType::ComboBox,
+                &format!("Recently used {} paths", field.label()),
+            )
+            .on_hover_text(format!("Recently used {} paths", field.label()))
+            .on_disabled_hover_text(if enabled {
+                "No other recently used paths"
+            } else {
+                "The field is locked; unlock it first"
+            });
+    });
+    picked
+}
+
+/// Value editors of one custom run sequence step (row `i` of the sequence panel).
+fn script_step_fields(ui: &mut egui::Ui, i: usize, step: &mut script::ScriptStep) {
+    use script::{Program, ScriptStep};
+    match step {
+        ScriptStep::Launch {
+            program,
+            args,
+            skip_if_running,
+        } => {
+            let label = match program {
+                Program::BattleNet => "Battle.net",
+                Program::Wow => "WoW",
+                Program::Path(_) => "Other program",
+            };
+            egui::ComboBox::from_id_salt(("script_program", i))
+                .width(110.0)
+                .selected_text(label)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(program, Program::BattleNet, "Battle.net");
+                    ui.selectable_value(program, Program::Wow, "WoW");
+                    if ui
+                        .selectable_label(matches!(program, Program::Path(_)), "Other program")
+                        .clicked()
+                        && !matches!(program, Program::Path(_))
+                    {
+                        *program = Program::Path(String::new());
+                    }
+                })
+                .response
+                .named(egui::WidgetType::ComboBox, "Program");
+            if let Program::Path(path) = program {
+                ui.add_sized(
+                    [200.0, 20.0],
+                    egui::TextEdit::singleline(path).hint_text("C:\\…\\program.exe"),
+                )
+                .named(egui::WidgetType::TextEdit, "Program path");
+            }
+            ui.add_sized(
+                [140.0, 20.0],
+                egui::TextEdit::singleline(args).hint_text("extra arguments"),
+            )
+            .named(egui::WidgetType::TextEdit, "Arguments");
+            ui.checkbox(skip_if_running, "skip if running");
+        }
+        ScriptStep::Wait { secs } => {
+            ui.add(egui::DragValue::new(secs).range(0..=3600).suffix(" s"))
+                .named(egui::WidgetType::DragValue, "Wait");
+        }
+        ScriptStep::WaitForProcess {
+            process,
+            window,
+            timeout_secs,
+        } => {
+            ui.add_sized([120.0, 20.0], egui::TextEdit::singleline(process))
+                .named(egui::WidgetType::TextEdit, "Process name");
+            ui.checkbox(window, "window");
+            let label = ui.label("give up after");
+            ui.add(
+                egui::DragValue::new(timeout_secs)
+                    .range(1..=3600)
+                    .suffix(" s"),
+            )
+            .labelled_by(label.id);
+        }
+        ScriptStep::WriteLocale => {
+            ui.label("(preferred audio/text locale)");
+        }
+        ScriptStep::Kill { process } => {
+            ui.add_sized([120.0, 20.0], egui::TextEdit::singleline(process))
+                .named(egui::WidgetType::TextEdit, "Process name");
+        }
+        ScriptStep::Pause { message } => {
+            ui.add_sized(
+                [240.0, 20.0],
+                egui::TextEdit::singleline(message).hint_text("message, e.g. Solve the captcha"),
+            )
+            .named(egui::WidgetType::TextEdit, "Message");
+        }
+    }
+}
+
+/// Names read out by screen readers, for widgets whose visible text doesn't say what they
+/// do (icon buttons, drop-downs without a label). Fields next to a label are tied to it with
+/// `labelled_by` instead.
+trait AccessibleName {
+    /// Announce the widget as a `kind` called `name`.
+    fn named(self, kind: egui::WidgetType, name: &str) -> Self;
+}
+
+impl AccessibleName for egui::Response {
+    fn named(self, kind: egui::WidgetType, name: &str) -> Self {
+        let enabled = self.enabled();
+        self.widget_info(|| egui::WidgetInfo::labeled(kind, enabled, name));
+        self
+    }
+}
+
+// How long a write button must be held when `confirm_writes` is on
+const HOLD_TO_CONFIRM_SECS: f64 = 1.5;
+
+/// `clicked()` for buttons that write game files, optionally requiring a press-and-hold.
+trait HoldToConfirm {
+    /// With `hold`, true once the button has been held for `HOLD_TO_CONFIRM_SECS` (progress
+    /// is drawn over it); otherwise a plain click.
+    fn confirmed(&self, hold: bool) -> bool;
+}
+
+impl HoldToConfirm for egui::Response {
+    fn confirmed(&self, hold: bool) -> bool {
+        if !hold {
+            return self.clicked();
+        }
+        let id = self.id.with("hold_to_confirm");
+        if !self.is_pointer_button_down_on() {
+            self.ctx.data_mut(|d| d.remove::<f64>(id));
+            return false;
+        }
+        let now = self.ctx.input(|i| i.time);
+        let start = self.ctx.data_mut(|d| *d.get_temp_mut_or(id, now));
+        // Fired already; wait for the release
+        if start.is_infinite() {
+            return false;
+        }
+        let progress = ((now - start) / HOLD_TO_CONFIRM_SECS).min(1.0) as f32;
+        let mut fill = self.rect;
+        fill.set_width(self.rect.width() * progress);
+        self.ctx.layer_painter(self.layer_id).rect_filled(
+            fill,
+            2.0,
+            self.ctx
+                .style()
+                .visuals
+                .selection
+                .bg_fill
+                .gamma_multiply(0.5),
+        );
+        if progress < 1.0 {
+            self.ctx.request_repaint();
+            return false;
+        }
+        self.ctx.data_mut(|d| d.insert_temp(id, f64::INFINITY));
+        true
+    }
+}
+
+/// Session length as `1 h 12 m` (or `12 m`).
+fn format_session(d: Duration) -> String {
+    let mins = d.as_secs() / 60;
+    if mins < 60 {
+        format!("{} m", mins)
+    } else {
+        format!("{} h {} m", mins / 60, mins % 60)
+    }
+}
 
-    let mut file = fs::File::create(path)?;
-    serde_json::to_writer_pretty(&mut file, &settings)?;
-    Ok(())
+/// Padlock button switching a path field between locked (read-only) and editable.
+fn lock_toggle(ui: &mut egui::Ui, locked: &mut bool) {
+    let (icon, name, hint) = if *locked {
+        (
+            "🔒",
+            "Unlock path",
+            "Locked; click to allow editing this path",
+        )
+    } else {
+        (
+            "🔓",
+            "Lock path",
+            "Lock this path against accidental changes",
+        )
+    };
+    if ui
+        .add_sized([20.0, 24.0], egui::Button::new(icon).frame(false))
+        .named(egui::WidgetType::Button, name)
+        .on_hover_text(hint)
+        .clicked()
+    {
+        *locked = !*locked;
+    }
 }
 
 fn is_file_with_ext(path: impl AsRef<Path>, ext: &str) -> bool {
@@ -937,34 +5394,6 @@ fn is_file_with_ext(path: impl AsRef<Path>, ext: &str) -> bool {
             .unwrap_or(false)
 }
 
-#[cfg(target_os = "windows")]
-fn get_window_position(frame: &eframe::Frame) -> Option<(i32, i32)> {
-    use raw_window_handle::HasWindowHandle;
-    use raw_window_handle::RawWindowHandle;
-    use windows_sys::Win32::Foundation::RECT;
-    use windows_sys::Win32::UI::WindowsAndMessaging::GetWindowRect;
-
-    // Use the new HasWindowHandle API
-    if let Ok(handle) = frame.window_handle() {
-        let raw: raw_window_handle::RawWindowHandle = handle.into();
-        if let RawWindowHandle::Win32(win) = raw {
-            // hwnd is NonZeroIsize
-            let hwnd = win.hwnd.get() as windows_sys::Win32::Foundation::HWND;
-            let mut rect = RECT {
-                left: 0,
-                top: 0,
-                right: 0,
-                bottom: 0,
-            };
-            let ok = unsafe { GetWindowRect(hwnd, &mut rect as *mut RECT) };
-            if ok != 0 {
-                return Some((rect.left, rect.top));
-            }
-        }
-    }
-    None
-}
-
 // Best-effort: set or clear always-on-top for our window (Windows only)
 fn set_window_topmost(frame: &eframe::Frame, topmost: bool) -> bool {
     #[cfg(target_os = "windows")]
@@ -1027,7 +5456,59 @@ fn set_window_minimized(frame: &eframe::Frame, minimized: bool) -> bool {
     }
 }
 
+/// Whether Windows' "Show animations in Windows" (reduced motion) setting is turned off.
+#[cfg(target_os = "windows")]
+fn os_reduced_motion() -> bool {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SPI_GETCLIENTAREAANIMATION, SystemParametersInfoW,
+    };
+
+    let mut enabled: windows_sys::core::BOOL = 1;
+    let ok = unsafe {
+        SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut enabled as *mut windows_sys::core::BOOL as *mut core::ffi::c_void,
+            0,
+        )
+    };
+    ok != 0 && enabled == 0
+}
+
+#[cfg(not(target_os = "windows"))]
+fn os_reduced_motion() -> bool {
+    false
+}
+
+/// Spinner for background work, or a static ellipsis when animations are off.
+fn busy_indicator(ui: &mut egui::Ui, reduce_motion: bool) {
+    if reduce_motion {
+        ui.label("…");
+    } else {
+        ui.spinner();
+    }
+}
+
+/// Time since the last keyboard/mouse input anywhere on the system (Windows only).
+#[cfg(target_os = "windows")]
+fn user_idle_time() -> Option<Duration> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    let ok = unsafe { GetLastInputInfo(&mut info) };
+    if ok == 0 {
+        return None;
+    }
+    // Both values are 32-bit tick counts; wrapping_sub handles the ~49 day rollover
+    let now = unsafe { GetTickCount() };
+    Some(Duration::from_millis(now.wrapping_sub(info.dwTime) as u64))
+}
+
 #[cfg(not(target_os = "windows"))]
-fn get_window_position(_frame: &eframe::Frame) -> Option<(i32, i32)> {
+fn user_idle_time() -> Option<Duration> {
     None
 }
@@ -0,0 +1,119 @@
+//! Upgrades of older settings.json layouts, applied to the raw JSON before it is
+//! deserialized so `SettingsFile` only has to know the current one.
+//!
+//! New fields don't need a migration (missing ones take their defaults); one is only added
+//! when a field is renamed, split or changes meaning. Each step upgrades one version.
+
+use serde_json::{Map, Value};
+
+/// Version written to `schemaVersion`; files without it are version 0.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Bring `settings` up to `CURRENT_VERSION`. Returns the version it had.
+pub fn migrate(settings: &mut Value) -> u32 {
+    let Some(map) = settings.as_object_mut() else {
+        return CURRENT_VERSION;
+    };
+    let version = map
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32);
+    if version < 1 {
+        v0_to_v1(map);
+    }
+    if version < CURRENT_VERSION {
+        map.insert("schemaVersion".into(), CURRENT_VERSION.into());
+    }
+    version
+}
+
+/// The first releases named the paths `battle`/`wow` and had one `preferredLocale` for
+/// both audio and text.
+fn v0_to_v1(map: &mut Map<String, Value>) {
+    rename(map, "battle", "launcher");
+    rename(map, "wow", "wowExecutable");
+    if let Some(locale) = map.remove("preferredLocale") {
+        for key in ["preferredAudioLocale", "preferredTextLocale"] {
+            if map
+                .get(key)
+                .and_then(Value::as_str)
+                .is_none_or(str::is_empty)
+            {
+                map.insert(key.into(), locale.clone());
+            }
+        }
+    }
+}
+
+fn rename(map: &mut Map<String, Value>, old: &str, new: &str) {
+    if let Some(value) = map.remove(old)
+        && !map.contains_key(new)
+    {
+        map.insert(new.into(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v0_renames_paths_and_splits_locale() {
+        let mut settings = json!({
+            "battle": "C:/Battle.net/Battle.net Launcher.exe",
+            "wow": "C:/WoW/Wow.exe",
+            "preferredLocale": "deDE",
+        });
+        assert_eq!(migrate(&mut settings), 0);
+        assert_eq!(
+            settings,
+            json!({
+                "launcher": "C:/Battle.net/Battle.net Launcher.exe",
+                "wowExecutable": "C:/WoW/Wow.exe",
+                "preferredAudioLocale": "deDE",
+                "preferredTextLocale": "deDE",
+                "schemaVersion": CURRENT_VERSION,
+            })
+        );
+    }
+
+    #[test]
+    fn v0_keeps_fields_already_in_the_new_layout() {
+        let mut settings = json!({
+            "battle": "old launcher",
+            "launcher": "new launcher",
+            "preferredLocale": "deDE",
+            "preferredAudioLocale": "frFR",
+            "preferredTextLocale": "",
+        });
+        migrate(&mut settings);
+        assert_eq!(
+            settings,
+            json!({
+                "launcher": "new launcher",
+                "preferredAudioLocale": "frFR",
+                "preferredTextLocale": "deDE",
+                "schemaVersion": CURRENT_VERSION,
+            })
+        );
+    }
+
+    #[test]
+    fn current_version_is_left_alone() {
+        let current = json!({
+            "schemaVersion": CURRENT_VERSION,
+            "wow": "kept as is",
+        });
+        let mut settings = current.clone();
+        assert_eq!(migrate(&mut settings), CURRENT_VERSION);
+        assert_eq!(settings, current);
+    }
+
+    #[test]
+    fn non_objects_are_ignored() {
+        let mut settings = json!([1, 2]);
+        assert_eq!(migrate(&mut settings), CURRENT_VERSION);
+        assert_eq!(settings, json!([1, 2]));
+    }
+}
@@ -0,0 +1,103 @@
+//! Display monitors, for putting the window back on the screen it was closed on. Monitors
+//! are told apart by their device name (e.g. `\\.\DISPLAY2`); coordinates are physical
+//! pixels on the virtual desktop, so monitors left of or above the primary one have
+//! negative coordinates.
+
+/// A monitor and its work area (without the taskbar).
+pub struct Monitor {
+    pub name: String,
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Monitor {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.left..self.right).contains(&x) && (self.top..self.bottom).contains(&y)
+    }
+}
+
+/// The monitor showing the physical point `(x, y)`, if any.
+#[cfg(target_os = "windows")]
+pub fn at(x: i32, y: i32) -> Option<Monitor> {
+    use windows_sys::Win32::Foundation::POINT;
+    use windows_sys::Win32::Graphics::Gdi::{MONITOR_DEFAULTTONULL, MonitorFromPoint};
+
+    let monitor = unsafe { MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONULL) };
+    if monitor.is_null() {
+        return None;
+    }
+    info(monitor)
+}
+
+/// Every connected monitor.
+#[cfg(target_os = "windows")]
+pub fn all() -> Vec<Monitor> {
+    use windows_sys::Win32::Foundation::{LPARAM, RECT};
+    use windows_sys::Win32::Graphics::Gdi::{EnumDisplayMonitors, HDC, HMONITOR};
+
+    unsafe extern "system" fn collect(
+        monitor: HMONITOR,
+        _: HDC,
+        _: *mut RECT,
+        data: LPARAM,
+    ) -> windows_sys::core::BOOL {
+        let monitors = unsafe { &mut *(data as *mut Vec<Monitor>) };
+        monitors.extend(info(monitor));
+        1
+    }
+
+    let mut monitors: Vec<Monitor> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            Some(collect),
+            &mut monitors as *mut Vec<Monitor> as LPARAM,
+        );
+    }
+    monitors
+}
+
+#[cfg(target_os = "windows")]
+fn info(monitor: windows_sys::Win32::Graphics::Gdi::HMONITOR) -> Option<Monitor> {
+    use windows_sys::Win32::Graphics::Gdi::{GetMonitorInfoW, MONITORINFO, MONITORINFOEXW};
+
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    if unsafe {
+        GetMonitorInfoW(
+            monitor,
+            &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+        )
+    } == 0
+    {
+        return None;
+    }
+    let len = info
+        .szDevice
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(info.szDevice.len());
+    let work = info.monitorInfo.rcWork;
+    Some(Monitor {
+        name: String::from_utf16_lossy(&info.szDevice[..len]),
+        left: work.left,
+        top: work.top,
+        right: work.right,
+        bottom: work.bottom,
+    })
+}
+
+/// The monitor showing a physical point (not available off Windows).
+#[cfg(not(target_os = "windows"))]
+pub fn at(_x: i32, _y: i32) -> Option<Monitor> {
+    None
+}
+
+/// Every connected monitor (not available off Windows).
+#[cfg(not(target_os = "windows"))]
+pub fn all() -> Vec<Monitor> {
+    Vec::new()
+}
@@ -0,0 +1,75 @@
+//! Graphics presets: groups of graphics cvars written to Config.wtf in one click.
+//!
+//! Every preset sets the same cvars, so applying one after another leaves nothing of the
+//! first behind.
+
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cvars: &'static [(&'static str, &'static str)],
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "Potato",
+        description: "Lowest settings and a 30 FPS cap, for old laptops",
+        cvars: &[
+            ("gxApi", "d3d9"),
+            ("graphicsQuality", "1"),
+            ("maxFPS", "30"),
+            ("maxFPSBk", "10"),
+            ("farclip", "177"),
+            ("groundEffectDensity", "16"),
+            ("groundEffectDist", "1"),
+            ("particleDensity", "0.1"),
+            ("shadowLevel", "0"),
+        ],
+    },
+    Preset {
+        name: "Balanced",
+        description: "Medium settings and a 60 FPS cap",
+        cvars: &[
+            ("gxApi", "D3D11"),
+            ("graphicsQuality", "5"),
+            ("maxFPS", "60"),
+            ("maxFPSBk", "30"),
+            ("farclip", "777"),
+            ("groundEffectDensity", "64"),
+            ("groundEffectDist", "70"),
+            ("particleDensity", "0.6"),
+            ("shadowLevel", "1"),
+        ],
+    },
+    Preset {
+        name: "Max",
+        description: "Highest settings, FPS capped at 200",
+        cvars: &[
+            ("gxApi", "D3D11"),
+            ("graphicsQuality", "10"),
+            ("maxFPS", "200"),
+            ("maxFPSBk", "60"),
+            ("farclip", "1277"),
+            ("groundEffectDensity", "256"),
+            ("groundEffectDist", "140"),
+            ("particleDensity", "1.0"),
+            ("shadowLevel", "2"),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_set_the_same_cvars() {
+        let names = |p: &Preset| {
+            let mut names: Vec<_> = p.cvars.iter().map(|(k, _)| *k).collect();
+            names.sort_unstable();
+            names
+        };
+        for preset in PRESETS {
+            assert_eq!(names(preset), names(&PRESETS[0]), "{}", preset.name);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Enumeration of running processes and their command lines.
+
+use crate::launch::Priority;
+
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Executable file name, e.g. `Wow.exe`
+    pub name: String,
+}
+
+/// Running processes whose executable name equals `name` (case-insensitive).
+pub fn find_by_name(name: &str) -> Vec<ProcessInfo> {
+    list()
+        .into_iter()
+        .filter(|p| p.name.eq_ignore_ascii_case(name))
+        .collect()
+}
+
+/// All running processes (best-effort; processes we can't inspect are skipped).
+#[cfg(target_os = "windows")]
+pub fn list() -> Vec<ProcessInfo> {
+    use crate::launch::OwnedHandle;
+    use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+    use windows_sys::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return Vec::new();
+    }
+    let snapshot = OwnedHandle(snapshot);
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+    };
+    let mut processes = Vec::new();
+    let mut ok = unsafe { Process32FirstW(snapshot.0, &mut entry) };
+    while ok != 0 {
+        let len = entry
+            .szExeFile
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(entry.szExeFile.len());
+        processes.push(ProcessInfo {
+            pid: entry.th32ProcessID,
+            name: String::from_utf16_lossy(&entry.szExeFile[..len]),
+        });
+        ok = unsafe { Process32NextW(snapshot.0, &mut entry) };
+    }
+    processes
+}
+
+#[cfg(target_os = "linux")]
+pub fn list() -> Vec<ProcessInfo> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let pid = e.file_name().to_str()?.parse().ok()?;
+            // argv[0] rather than `comm`, which is truncated to 15 characters
+            let cmdline = std::fs::read(e.path().join("cmdline")).ok()?;
+            let argv0 = cmdline.split(|&b| b == 0).next()?;
+            let argv0 = String::from_utf8_lossy(argv0);
+            let name = argv0.rsplit(['/', '\\']).next()?.to_string();
+            Some(ProcessInfo { pid, name })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn list() -> Vec<ProcessInfo> {
+    Vec::new()
+}
+
+/// Full command line of process `pid`, if it can be read.
+#[cfg(target_os = "windows")]
+pub fn command_line(pid: u32) -> Option<String> {
+    use crate::launch::OwnedHandle;
+    use windows_sys::Wdk::System::Threading::{
+        NtQueryInformationProcess, ProcessCommandLineInformation,
+    };
+    use windows_sys::Win32::Foundation::UNICODE_STRING;
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if process.is_null() {
+        return None;
+    }
+    let process = OwnedHandle(process);
+    // First call only reports the required buffer size
+    let mut len = 0u32;
+    unsafe {
+        NtQueryInformationProcess(
+            process.0,
+            ProcessCommandLineInformation,
+            std::ptr::null_mut(),
+            0,
+            &mut len,
+        )
+    };
+    if (len as usize) < std::mem::size_of::<UNICODE_STRING>() {
+        return None;
+    }
+    // u64 elements keep the UNICODE_STRING header suitably aligned
+    let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+    let status = unsafe {
+        NtQueryInformationProcess(
+            process.0,
+            ProcessCommandLineInformation,
+            buffer.as_mut_ptr() as *mut core::ffi::c_void,
+            len,
+            &mut len,
+        )
+    };
+    if status < 0 {
+        return None;
+    }
+    let string = unsafe { &*(buffer.as_ptr() as *const UNICODE_STRING) };
+    if string.Buffer.is_null() {
+        return None;
+    }
+    let units = unsafe { std::slice::from_raw_parts(string.Buffer, string.Length as usize / 2) };
+    Some(String::from_utf16_lossy(units))
+}
+
+#[cfg(target_os = "linux")]
+pub fn command_line(pid: u32) -> Option<String> {
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let args: Vec<String> = cmdline
+        .split(|&b| b == 0)
+        .filter(|a| !a.is_empty())
+        .map(|a| String::from_utf8_lossy(a).into_owned())
+        .collect();
+    Some(args.join(" "))
+}
+
+/// When process `pid` was started, if it can be queried (Windows only).
+#[cfg(target_os = "windows")]
+pub fn start_time(pid: u32) -> Option<std::time::SystemTime> {
+    use crate::launch::OwnedHandle;
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    // FILETIME counts 100 ns intervals since 1601-01-01
+    const UNIX_EPOCH_OFFSET_SECS: u64 = 11_644_473_600;
+
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if process.is_null() {
+        return None;
+    }
+    let process = OwnedHandle(process);
+    let mut times = [FILETIME::default(); 4];
+    let [created, exited, kernel, user] = &mut times;
+    if unsafe { GetProcessTimes(process.0, created, exited, kernel, user) } == 0 {
+        return None;
+    }
+    let created = times[0];
+    let ticks = (u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime);
+    let since_unix = (ticks / 10).checked_sub(UNIX_EPOCH_OFFSET_SECS * 1_000_000)?;
+    Some(std::time::UNIX_EPOCH + std::time::Duration::from_micros(since_unix))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start_time(_pid: u32) -> Option<std::time::SystemTime> {
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn command_line(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Terminate process `pid`.
+#[cfg(target_os = "windows")]
+pub fn kill(pid: u32) -> Result<(), String> {
+    use crate::launch::OwnedHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_TERMINATE, TerminateProcess};
+
+    let process = unsafe { OpenProcess(PROCESS_TERMINATE, 0, pid) };
+    if process.is_null() {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    let process = OwnedHandle(process);
+    if unsafe { TerminateProcess(process.0, 1) } == 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn kill(pid: u32) -> Result<(), String> {
+    let status = std::process::Command::new("kill")
+        .arg(pid.to_string())
+        .status()
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("kill {} failed ({})", pid, status))
+    }
+}
+
+/// Set the priority class and, unless `affinity` is 0, the CPU affinity mask of process `pid`.
+#[cfg(target_os = "windows")]
+pub fn set_scheduling(pid: u32, priority: Priority, affinity: u64) -> Result<(), String> {
+    use crate::launch::OwnedHandle;
+    use windows_sys::Win32::System::Threading::{
+        ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+        NORMAL_PRIORITY_CLASS, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+        PROCESS_SET_INFORMATION, SetPriorityClass, SetProcessAffinityMask,
+    };
+
+    let access = PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION;
+    let process = unsafe { OpenProcess(access, 0, pid) };
+    if process.is_null() {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    let process = OwnedHandle(process);
+    let class = match priority {
+        Priority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+        Priority::Normal => NORMAL_PRIORITY_CLASS,
+        Priority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+        Priority::High => HIGH_PRIORITY_CLASS,
+    };
+    if unsafe { SetPriorityClass(process.0, class) } == 0 {
+        return Err(format!(
+            "cannot set priority: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    if affinity != 0 && unsafe { SetProcessAffinityMask(process.0, affinity as usize) } == 0 {
+        return Err(format!(
+            "cannot set CPU affinity: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn set_scheduling(_pid: u32, _priority: Priority, _affinity: u64) -> Result<(), String> {
+    Err("process priority and CPU affinity can only be set on Windows".into())
+}
+
+/// Ask process `pid` to exit the polite way: WM_CLOSE to its top-level windows on Windows,
+/// SIGTERM elsewhere. Returns whether a request was delivered.
+#[cfg(target_os = "windows")]
+pub fn request_close(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{HWND, LPARAM};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowThreadProcessId, PostMessageW, WM_CLOSE,
+    };
+
+    // (target pid, windows closed)
+    unsafe extern "system" fn close(hwnd: HWND, lparam: LPARAM) -> windows_sys::core::BOOL {
+        let target = unsafe { &mut *(lparam as *mut (u32, u32)) };
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+        if pid == target.0 && unsafe { PostMessageW(hwnd, WM_CLOSE, 0, 0) } != 0 {
+            target.1 += 1;
+        }
+        1
+    }
+
+    let mut target = (pid, 0u32);
+    unsafe { EnumWindows(Some(close), &mut target as *mut (u32, u32) as LPARAM) };
+    target.1 > 0
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn request_close(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Whether any process named `name` shows a visible top-level window (e.g. Battle.net's
+/// login window). Where windows can't be enumerated, a running process counts as shown.
+pub fn has_visible_window(name: &str) -> bool {
+    let pids: Vec<u32> = find_by_name(name).iter().map(|p| p.pid).collect();
+    !pids.is_empty()
+        && visible_window_pids().is_none_or(|shown| pids.iter().any(|p| shown.contains(p)))
+}
+
+/// Processes that have the file `path` open, as far as the Restart Manager can tell.
+#[cfg(target_os = "windows")]
+pub fn locking(path: &std::path::Path) -> Vec<ProcessInfo> {
+    use windows_sys::Win32::Foundation::{ERROR_MORE_DATA, ERROR_SUCCESS};
+    use windows_sys::Win32::System::RestartManager::{
+        CCH_RM_SESSION_KEY, RM_PROCESS_INFO, RmEndSession, RmGetList, RmRegisterResources,
+        RmStartSession,
+    };
+
+    let mut session = 0u32;
+    let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+    if unsafe { RmStartSession(&mut session, 0, key.as_mut_ptr()) } != ERROR_SUCCESS {
+        return Vec::new();
+    }
+    let file = crate::launch::wide(&path.to_string_lossy());
+    let files = [file.as_ptr()];
+    let mut found = Vec::new();
+    if unsafe {
+        RmRegisterResources(
+            session,
+            1,
+            files.as_ptr(),
+            0,
+            std::ptr::null(),
+            0,
+            std::ptr::null(),
+        )
+    } == ERROR_SUCCESS
+    {
+        let mut needed = 0u32;
+        let mut count = 0u32;
+        let mut reasons = 0u32;
+        let mut infos: Vec<RM_PROCESS_INFO> = Vec::new();
+        // The first call asks for the count; a process opening the file in between makes
+        // the second one report more data again, which just leaves the list empty
+        let mut result = unsafe {
+            RmGetList(
+                session,
+                &mut needed,
+                &mut count,
+                std::ptr::null_mut(),
+                &mut reasons,
+            )
+        };
+        if result == ERROR_MORE_DATA {
+            infos.resize_with(needed as usize, Default::default);
+            count = needed;
+            result = unsafe {
+                RmGetList(
+                    session,
+                    &mut needed,
+                    &mut count,
+                    infos.as_mut_ptr(),
+                    &mut reasons,
+                )
+            };
+        }
+        if result == ERROR_SUCCESS {
+            let running = list();
+            for info in &infos[..(count as usize).min(infos.len())] {
+                let pid = info.Process.dwProcessId;
+                let name = running
+                    .iter()
+                    .find(|p| p.pid == pid)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| {
+                        let len = info
+                            .strAppName
+                            .iter()
+                            .position(|&c| c == 0)
+                            .unwrap_or(info.strAppName.len());
+                        String::from_utf16_lossy(&info.strAppName[..len])
+                    });
+                found.push(ProcessInfo { pid, name });
+            }
+        }
+    }
+    unsafe { RmEndSession(session) };
+    found
+}
+
+/// Processes that have a file open (not available off Windows).
+#[cfg(not(target_os = "windows"))]
+pub fn locking(_path: &std::path::Path) -> Vec<ProcessInfo> {
+    Vec::new()
+}
+
+/// Process ids owning at least one visible, titled top-level window.
+#[cfg(target_os = "windows")]
+fn visible_window_pids() -> Option<Vec<u32>> {
+    use windows_sys::Win32::Foundation::{HWND, LPARAM};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowTextLengthW, GetWindowThreadProcessId, IsWindowVisible,
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> windows_sys::core::BOOL {
+        let pids = unsafe { &mut *(lparam as *mut Vec<u32>) };
+        if unsafe { IsWindowVisible(hwnd) } != 0 && unsafe { GetWindowTextLengthW(hwnd) } > 0 {
+            let mut pid = 0u32;
+            unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+            pids.push(pid);
+        }
+        1
+    }
+
+    let mut pids: Vec<u32> = Vec::new();
+    let ok = unsafe { EnumWindows(Some(collect), &mut pids as *mut Vec<u32> as LPARAM) };
+    (ok != 0).then_some(pids)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn visible_window_pids() -> Option<Vec<u32>> {
+    None
+}
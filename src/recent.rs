@@ -0,0 +1,40 @@
+//! Recently used values of the three path fields, offered in a drop-down next to each so
+//! switching between installs doesn't mean browsing for every file again.
+
+use crate::PathField;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RecentPaths {
+    pub launcher: Vec<String>,
+    pub config: Vec<String>,
+    pub wow_executable: Vec<String>,
+}
+
+impl RecentPaths {
+    /// Values of `field`, most recent first.
+    pub fn list(&self, field: PathField) -> &[String] {
+        match field {
+            PathField::BattleNet => &self.launcher,
+            PathField::ConfigWtf => &self.config,
+            PathField::Wow => &self.wow_executable,
+        }
+    }
+
+    /// Put `path` first in the list of `field`, dropping an older copy and anything beyond
+    /// `limits::get().recent_paths`. Only existing files of the field's type are kept.
+    pub fn remember(&mut self, field: PathField, path: &str) {
+        if !crate::is_file_with_ext(path, field.ext()) {
+            return;
+        }
+        let list = match field {
+            PathField::BattleNet => &mut self.launcher,
+            PathField::ConfigWtf => &mut self.config,
+            PathField::Wow => &mut self.wow_executable,
+        };
+        list.retain(|p| !p.eq_ignore_ascii_case(path));
+        list.insert(0, path.to_string());
+        list.truncate(crate::limits::get().recent_paths);
+    }
+}
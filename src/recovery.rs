@@ -0,0 +1,125 @@
+//! Advice for the common OS errors a run step fails with: a plain explanation instead of
+//! the raw io error, and the quick fixes the failure banner offers for it.
+
+use crate::processes::ProcessInfo;
+use std::io;
+use std::path::Path;
+
+// Win32 error codes that have no io::ErrorKind of their own (winerror.h)
+const ERROR_SHARING_VIOLATION: i32 = 32;
+const ERROR_LOCK_VIOLATION: i32 = 33;
+const ERROR_BAD_EXE_FORMAT: i32 = 193;
+const ERROR_ELEVATION_REQUIRED: i32 = 740;
+const ERROR_CANCELLED: i32 = 1223;
+
+/// A quick fix offered next to the advice.
+pub enum Action {
+    /// Start the program again as administrator.
+    RetryElevated,
+    /// Pick the program's executable again, then retry.
+    Browse,
+    /// Terminate the processes holding the file open, then retry.
+    CloseLocking(Vec<ProcessInfo>),
+    Retry,
+}
+
+pub struct Recovery {
+    /// What went wrong and what to do about it, in plain words.
+    pub advice: String,
+    pub actions: Vec<Action>,
+}
+
+/// Advice for a program `name` that could not be started.
+pub fn for_launch(name: &str, error: &io::Error) -> Option<Recovery> {
+    let (advice, actions) = match windows_code(error) {
+        Some(ERROR_ELEVATION_REQUIRED) => (
+            format!(
+                "{} must run as administrator; retry elevated, or tick \"Run {} as administrator\"",
+                name, name
+            ),
+            vec![Action::RetryElevated],
+        ),
+        Some(ERROR_CANCELLED) => (
+            format!(
+                "The administrator prompt for {} was declined; retry and choose Yes",
+                name
+            ),
+            vec![Action::RetryElevated],
+        ),
+        Some(ERROR_BAD_EXE_FORMAT) => (
+            format!(
+                "The file set for {} is not a Windows program; pick its .exe again",
+                name
+            ),
+            vec![Action::Browse],
+        ),
+        _ => match error.kind() {
+            io::ErrorKind::NotFound => (
+                format!(
+                    "The executable of {} was not found; it may have moved after an update or reinstall",
+                    name
+                ),
+                vec![Action::Browse, Action::Retry],
+            ),
+            io::ErrorKind::PermissionDenied => (
+                format!(
+                    "Windows denied starting {}; the folder may need administrator rights or an antivirus blocked it",
+                    name
+                ),
+                vec![Action::RetryElevated, Action::Browse],
+            ),
+            _ => return None,
+        },
+    };
+    Some(Recovery { advice, actions })
+}
+
+/// Advice for a write of `path` that failed with `error`.
+pub fn for_write(path: &Path, error: &io::Error) -> Option<Recovery> {
+    let file = path.file_name()?.to_string_lossy();
+    let (advice, actions) = match windows_code(error) {
+        Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION) => {
+            let holders = crate::processes::locking(path);
+            if holders.is_empty() {
+                (
+                    format!(
+                        "{} is open in another program; close WoW or the editor that has it open, then retry",
+                        file
+                    ),
+                    vec![Action::Retry],
+                )
+            } else {
+                let names: Vec<&str> = holders.iter().map(|p| p.name.as_str()).collect();
+                (
+                    format!("{} is held open by {}", file, names.join(", ")),
+                    vec![Action::CloseLocking(holders), Action::Retry],
+                )
+            }
+        }
+        _ => match error.kind() {
+            io::ErrorKind::PermissionDenied => (
+                format!(
+                    "{} cannot be written; it may be read-only or in a protected folder such as Program Files",
+                    file
+                ),
+                vec![Action::Retry],
+            ),
+            io::ErrorKind::NotFound => (
+                format!("{} or its folder no longer exists", file),
+                vec![Action::Retry],
+            ),
+            _ => return None,
+        },
+    };
+    Some(Recovery { advice, actions })
+}
+
+/// The raw code of `error` where it is a Win32 error code; elsewhere the same numbers mean
+/// other things.
+fn windows_code(error: &io::Error) -> Option<i32> {
+    if cfg!(target_os = "windows") {
+        error.raw_os_error()
+    } else {
+        None
+    }
+}
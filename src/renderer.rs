@@ -0,0 +1,63 @@
+//! Choice of the eframe renderer, applied to the native options before the window exists.
+//!
+//! Some old GPUs can't open the default (glow/OpenGL) window at all; wgpu (Direct3D/Vulkan)
+//! or a software rasterizer gets the launcher up on those machines.
+
+use eframe::{egui_wgpu, wgpu};
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Glow,
+    Wgpu,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 2] = [Backend::Glow, Backend::Wgpu];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "glow" | "opengl" | "gl" => Some(Self::Glow),
+            "wgpu" => Some(Self::Wgpu),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Glow => "OpenGL (glow)",
+            Self::Wgpu => "Direct3D/Vulkan (wgpu)",
+        }
+    }
+}
+
+/// Select `backend` and, with `software`, a CPU rasterizer instead of the GPU.
+pub fn apply(options: &mut eframe::NativeOptions, backend: Backend, software: bool) {
+    match backend {
+        Backend::Glow => {
+            options.renderer = eframe::Renderer::Glow;
+            if software {
+                options.hardware_acceleration = eframe::HardwareAcceleration::Off;
+            }
+        }
+        Backend::Wgpu => {
+            options.renderer = eframe::Renderer::Wgpu;
+            if software {
+                let setup = egui_wgpu::WgpuSetupCreateNew {
+                    native_adapter_selector: Some(Arc::new(|adapters, _surface| {
+                        // WARP on Direct3D 12, llvmpipe/lavapipe elsewhere
+                        adapters
+                            .iter()
+                            .find(|a| a.get_info().device_type == wgpu::DeviceType::Cpu)
+                            .cloned()
+                            .ok_or_else(|| "no software (CPU) graphics adapter found".to_string())
+                    })),
+                    ..Default::default()
+                };
+                options.wgpu_options.wgpu_setup = egui_wgpu::WgpuSetup::CreateNew(setup);
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Channels for background work that wake the window: each send requests a repaint, so the
+//! UI picks a result up on the next frame instead of polling for it on a timer.
+//!
+//! `init` hands over the window's egui context at startup. Channels made without one (the
+//! headless subcommands) behave as plain channels.
+
+use eframe::egui;
+use std::sync::OnceLock;
+use std::sync::mpsc::{self, Receiver, SendError};
+
+static CONTEXT: OnceLock<egui::Context> = OnceLock::new();
+
+/// Use `ctx` for the channels made from now on.
+pub fn init(ctx: &egui::Context) {
+    let _ = CONTEXT.set(ctx.clone());
+}
+
+/// Sending half of `channel`.
+pub struct Sender<T> {
+    tx: mpsc::Sender<T>,
+    ctx: Option<egui::Context>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            ctx: self.ctx.clone(),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Send `value` and wake the window to receive it.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.tx.send(value)?;
+        if let Some(ctx) = &self.ctx {
+            ctx.request_repaint();
+        }
+        Ok(())
+    }
+}
+
+/// An mpsc channel whose sends repaint the window.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let ctx = CONTEXT.get().cloned();
+    (Sender { tx, ctx }, rx)
+}
+
+/// Run `work` on a background thread; the receiver yields its result, waking the window.
+pub fn spawn<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Receiver<T> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(work());
+    });
+    rx
+}
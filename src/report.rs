@@ -0,0 +1,78 @@
+//! Locale state report for translation projects: every locale-related CVar in Config.wtf plus
+//! the locale packs installed next to Wow.exe, written as JSON.
+
+use crate::wtf::WtfDocument;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(serde::Serialize)]
+struct Report {
+    #[serde(rename = "generatedBy")]
+    generated_by: String,
+    // Seconds since the Unix epoch
+    #[serde(rename = "generatedAt")]
+    generated_at: u64,
+    config: String,
+    #[serde(rename = "preferredAudioLocale")]
+    preferred_audio_locale: String,
+    #[serde(rename = "preferredTextLocale")]
+    preferred_text_locale: String,
+    cvars: BTreeMap<String, String>,
+    #[serde(rename = "installedLocales")]
+    installed_locales: Vec<String>,
+}
+
+/// Build the report for `config` and the client at `wow_executable` and write it to `out`.
+pub fn export(
+    out: &Path,
+    config: &Path,
+    wow_executable: &Path,
+    preferred_audio: &str,
+    preferred_text: &str,
+) -> Result<(), String> {
+    let meta = config.metadata().map_err(|e| e.to_string())?;
+    if meta.len() > crate::limits::get().max_config_bytes {
+        return Err("Config.wtf is too large".into());
+    }
+    let doc = WtfDocument::parse(&fs::read(config).map_err(|e| e.to_string())?)?;
+    let report = Report {
+        generated_by: format!("enTitan {}", env!("CARGO_PKG_VERSION")),
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        config: config.display().to_string(),
+        preferred_audio_locale: preferred_audio.to_string(),
+        preferred_text_locale: preferred_text.to_string(),
+        // Later duplicates overwrite earlier ones, matching what WoW applies
+        cvars: doc
+            .entries()
+            .into_iter()
+            .filter(|(k, _)| k.to_ascii_lowercase().contains("locale"))
+            .collect(),
+        installed_locales: installed_locales(wow_executable),
+    };
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(out, json).map_err(|e| e.to_string())
+}
+
+/// Known locale codes that have a `Data/<locale>` folder next to Wow.exe.
+pub fn installed_locales(wow_executable: &Path) -> Vec<String> {
+    let Some(data) = wow_executable.parent().map(|d| d.join("Data")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(data) else {
+        return Vec::new();
+    };
+    let mut locales: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            crate::locales::canonical(&e.file_name().to_string_lossy()).map(str::to_string)
+        })
+        .collect();
+    locales.sort();
+    locales
+}
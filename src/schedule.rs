@@ -0,0 +1,106 @@
+//! Scheduled launch: the run sequence started automatically at a local time of day, e.g.
+//! just before a raid reset.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Hour and minute of a `HH:MM` time of day.
+pub fn parse_time(text: &str) -> Option<(u32, u32)> {
+    let (h, m) = text.trim().split_once(':')?;
+    let (h, m): (u32, u32) = (h.trim().parse().ok()?, m.trim().parse().ok()?);
+    (h < 24 && m < 60).then_some((h, m))
+}
+
+/// The next moment the local clock shows `hour:minute`: today if that is still ahead,
+/// otherwise tomorrow.
+pub fn next_occurrence(hour: u32, minute: u32) -> SystemTime {
+    let now = SystemTime::now();
+    let utc = now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) as i64;
+    let local = utc + local_offset_secs();
+    let target = i64::from(hour * 3600 + minute * 60);
+    let mut wait = target - local.rem_euclid(86_400);
+    if wait <= 0 {
+        wait += 86_400;
+    }
+    now + Duration::from_secs(wait as u64)
+}
+
+/// Countdown text like `2 h 05 m 09 s`.
+pub fn format_countdown(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 3600 {
+        format!("{} m {:02} s", secs / 60, secs % 60)
+    } else {
+        format!(
+            "{} h {:02} m {:02} s",
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        )
+    }
+}
+
+/// Seconds the local time is ahead of UTC.
+#[cfg(target_os = "windows")]
+fn local_offset_secs() -> i64 {
+    use windows_sys::Win32::Foundation::SYSTEMTIME;
+    use windows_sys::Win32::System::SystemInformation::{GetLocalTime, GetSystemTime};
+
+    let mut utc = SYSTEMTIME::default();
+    let mut local = SYSTEMTIME::default();
+    unsafe {
+        GetSystemTime(&mut utc);
+        GetLocalTime(&mut local);
+    }
+    let secs = |t: &SYSTEMTIME| {
+        crate::eventlog::days_from_civil(i64::from(t.wYear), u32::from(t.wMonth), u32::from(t.wDay))
+            * 86_400
+            + i64::from(t.wHour) * 3600
+            + i64::from(t.wMinute) * 60
+            + i64::from(t.wSecond)
+    };
+    // Time zones are whole minutes apart; drop the second that may tick between the calls
+    ((secs(&local) - secs(&utc)) as f64 / 60.0).round() as i64 * 60
+}
+
+/// Seconds the local time is ahead of UTC (UTC assumed off Windows).
+#[cfg(not(target_os = "windows"))]
+fn local_offset_secs() -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_time_accepts_valid_times() {
+        assert_eq!(parse_time("00:00"), Some((0, 0)));
+        assert_eq!(parse_time("23:59"), Some((23, 59)));
+        assert_eq!(parse_time("7:05"), Some((7, 5)));
+        assert_eq!(parse_time(" 18 : 30 "), Some((18, 30)));
+    }
+
+    #[test]
+    fn parse_time_rejects_invalid_times() {
+        for text in [
+            "", "12", "24:00", "12:60", "-1:30", "12:3a", "12:30:00", ":30", "12:",
+        ] {
+            assert_eq!(parse_time(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn next_occurrence_is_within_a_day() {
+        let wait = next_occurrence(12, 0)
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        assert!(wait <= Duration::from_secs(86_400));
+    }
+
+    #[test]
+    fn countdown_formats() {
+        assert_eq!(format_countdown(Duration::from_secs(9)), "0 m 09 s");
+        assert_eq!(format_countdown(Duration::from_secs(3599)), "59 m 59 s");
+        assert_eq!(format_countdown(Duration::from_secs(7509)), "2 h 05 m 09 s");
+    }
+}
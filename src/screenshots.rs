@@ -0,0 +1,107 @@
+//! Optional sorting of new WoW screenshots into per-locale subfolders
+//! (`Screenshots/<locale>/WoWScrnShot_....jpg`), for people capturing localized UI text.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher, recommended_watcher};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+/// How long a new screenshot must stay unchanged before it is moved, so WoW has finished
+/// writing it.
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+const EXTENSIONS: [&str; 3] = ["jpg", "tga", "png"];
+
+/// Watches a Screenshots directory and moves newly written images into a locale subfolder.
+pub struct Organizer {
+    _watcher: RecommendedWatcher,
+    rx: Receiver<notify::Result<notify::Event>>,
+    dir: PathBuf,
+    // New screenshots and when they last changed
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl Organizer {
+    /// Start watching `dir` (non-recursively, so the locale subfolders are left alone).
+    pub fn start(dir: &Path) -> Result<Self, String> {
+        let (tx, rx) = crate::repaint::channel();
+        let mut watcher = recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .map_err(|e| e.to_string())?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            _watcher: watcher,
+            rx,
+            dir: dir.to_path_buf(),
+            pending: HashMap::new(),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Collect watcher events and move every settled screenshot into `dir/<locale>`.
+    /// Returns one result per attempted move (the new path, or an error message).
+    pub fn poll(&mut self, locale: &str) -> Vec<Result<PathBuf, String>> {
+        let now = Instant::now();
+        while let Ok(res) = self.rx.try_recv() {
+            let Ok(event) = res else {
+                continue;
+            };
+            for path in event.paths {
+                if path.parent() == Some(self.dir.as_path()) && is_screenshot(&path) {
+                    self.pending.insert(path, now);
+                }
+            }
+        }
+
+        let settled: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, seen)| now.duration_since(**seen) >= SETTLE_TIME)
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut results = Vec::new();
+        for path in settled {
+            self.pending.remove(&path);
+            // Gone already (moved by us, or deleted by the user)
+            if !path.is_file() {
+                continue;
+            }
+            results.push(self.move_to_locale(&path, locale));
+        }
+        results
+    }
+
+    /// Time until the next pending screenshot settles, if any.
+    pub fn next_poll_in(&self) -> Option<Duration> {
+        self.pending
+            .values()
+            .map(|seen| SETTLE_TIME.saturating_sub(seen.elapsed()))
+            .min()
+    }
+
+    fn move_to_locale(&self, path: &Path, locale: &str) -> Result<PathBuf, String> {
+        let name = path.file_name().ok_or("screenshot has no file name")?;
+        let target_dir = self.dir.join(locale);
+        fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+        let target = target_dir.join(name);
+        if target.exists() {
+            return Err(format!("{} already exists", target.display()));
+        }
+        fs::rename(path, &target).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(target)
+    }
+}
+
+fn is_screenshot(path: &Path) -> bool {
+    EXTENSIONS
+        .iter()
+        .any(|ext| crate::is_file_with_ext(path, ext))
+}
@@ -0,0 +1,231 @@
+//! User-editable run sequence: a list of steps stored in the settings and turned into
+//! `sequence::Step`s when a run starts.
+
+use crate::launch::{self, LaunchOptions};
+use crate::sequence::Step;
+use std::path::Path;
+use std::time::Duration;
+
+/// Paths, argument strings and launch options of the configured executables.
+pub struct Executables<'a> {
+    pub battle_net: &'a str,
+    pub battle_net_args: &'a str,
+    pub battle_net_options: &'a LaunchOptions,
+    pub wow: &'a str,
+    pub wow_args: &'a str,
+    pub wow_options: &'a LaunchOptions,
+}
+
+/// What a `Launch` step starts: one of the configured executables or any other program.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Program {
+    BattleNet,
+    Wow,
+    Path(String),
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "step", rename_all = "camelCase")]
+pub enum ScriptStep {
+    /// Start `program` with its configured arguments followed by `args`, unless
+    /// `skip_if_running` and it already runs.
+    Launch {
+        program: Program,
+        #[serde(default)]
+        args: String,
+        #[serde(default, rename = "skipIfRunning")]
+        skip_if_running: bool,
+    },
+    Wait {
+        secs: u64,
+    },
+    WaitForProcess {
+        process: String,
+        #[serde(default)]
+        window: bool,
+        #[serde(rename = "timeoutSecs")]
+        timeout_secs: u64,
+    },
+    /// Write the preferred locales into Config.wtf.
+    WriteLocale,
+    /// Terminate every process with this executable name.
+    Kill {
+        process: String,
+    },
+    /// Hold until the user presses Continue, showing `message` (e.g. for a captcha).
+    Pause {
+        #[serde(default)]
+        message: String,
+    },
+}
+
+/// Step kinds in the order the editor offers them; see `ScriptStep::kind`.
+pub const KINDS: [&str; 6] = [
+    "Launch",
+    "Wait",
+    "Wait for process",
+    "Write locale",
+    "Kill process",
+    "Pause",
+];
+
+// Timeout of a newly added "Wait for process" step
+const DEFAULT_PROCESS_TIMEOUT_SECS: u64 = 120;
+
+impl ScriptStep {
+    /// A step of kind `KINDS[kind]` with default values.
+    pub fn new(kind: usize) -> Self {
+        match kind {
+            0 => Self::Launch {
+                program: Program::Wow,
+                args: String::new(),
+                skip_if_running: true,
+            },
+            2 => Self::WaitForProcess {
+                process: "Wow.exe".into(),
+                window: false,
+                timeout_secs: DEFAULT_PROCESS_TIMEOUT_SECS,
+            },
+            3 => Self::WriteLocale,
+            4 => Self::Kill {
+                process: "Battle.net.exe".into(),
+            },
+            5 => Self::Pause {
+                message: String::new(),
+            },
+            _ => Self::Wait { secs: 10 },
+        }
+    }
+
+    /// Index of this step's kind in `KINDS`.
+    pub fn kind(&self) -> usize {
+        match self {
+            Self::Launch { .. } => 0,
+            Self::Wait { .. } => 1,
+            Self::WaitForProcess { .. } => 2,
+            Self::WriteLocale => 3,
+            Self::Kill { .. } => 4,
+            Self::Pause { .. } => 5,
+        }
+    }
+
+    /// The runnable step, with `Program::BattleNet`/`Program::Wow` resolved to the configured
+    /// executables; other programs are started with `options`.
+    pub fn to_step(&self, exes: &Executables, options: &LaunchOptions) -> Step {
+        match self {
+            Self::Launch {
+                program,
+                args,
+                skip_if_running,
+            } => {
+                let path = program.path(exes.battle_net, exes.wow);
+                let configured = match program {
+                    Program::BattleNet => exes.battle_net_args,
+                    Program::Wow => exes.wow_args,
+                    Program::Path(_) => "",
+                };
+                let mut all_args = launch::split_args(configured);
+                all_args.extend(launch::split_args(args));
+                let options = match program {
+                    Program::BattleNet => exes.battle_net_options,
+                    Program::Wow => exes.wow_options,
+                    Program::Path(_) => options,
+                };
+                Step::Launch {
+                    name: program.name(&path),
+                    path,
+                    args: all_args,
+                    options: options.clone(),
+                    skip_if_running: *skip_if_running,
+                }
+            }
+            Self::Wait { secs } => Step::Wait {
+                label: "Waiting".into(),
+                duration: Duration::from_secs(*secs),
+            },
+            Self::WaitForProcess {
+                process,
+                window,
+                timeout_secs,
+            } => Step::WaitForProcess {
+                label: format!("Waiting for {}", process),
+                process: process.clone(),
+                window: *window,
+                timeout: Duration::from_secs(*timeout_secs),
+            },
+            Self::WriteLocale => Step::WriteLocale,
+            Self::Kill { process } => Step::Kill {
+                process: process.clone(),
+            },
+            Self::Pause { message } => Step::Confirm {
+                message: if message.trim().is_empty() {
+                    "Paused; press Continue when ready".into()
+                } else {
+                    message.clone()
+                },
+            },
+        }
+    }
+}
+
+impl Program {
+    pub fn path(&self, battle_net: &str, wow: &str) -> String {
+        match self {
+            Self::BattleNet => battle_net.to_string(),
+            Self::Wow => wow.to_string(),
+            Self::Path(p) => p.clone(),
+        }
+    }
+
+    /// Name shown in status messages and the event log.
+    fn name(&self, path: &str) -> String {
+        match self {
+            Self::BattleNet => "Battle.net".into(),
+            Self::Wow => "WoW".into(),
+            Self::Path(_) => Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string()),
+        }
+    }
+}
+
+/// The built-in flow as an editable script, starting point for customization.
+pub fn default_script(wow_launch_delay_secs: u64, relaunch_delay_secs: u64) -> Vec<ScriptStep> {
+    vec![
+        ScriptStep::Launch {
+            program: Program::BattleNet,
+            args: String::new(),
+            skip_if_running: true,
+        },
+        ScriptStep::WaitForProcess {
+            process: "Battle.net.exe".into(),
+            window: false,
+            timeout_secs: DEFAULT_PROCESS_TIMEOUT_SECS,
+        },
+        ScriptStep::Wait {
+            secs: wow_launch_delay_secs,
+        },
+        ScriptStep::WriteLocale,
+        ScriptStep::Launch {
+            program: Program::Wow,
+            args: String::new(),
+            skip_if_running: true,
+        },
+        ScriptStep::WaitForProcess {
+            process: "Wow.exe".into(),
+            window: false,
+            timeout_secs: DEFAULT_PROCESS_TIMEOUT_SECS,
+        },
+        ScriptStep::Wait {
+            secs: relaunch_delay_secs,
+        },
+        ScriptStep::Launch {
+            program: Program::BattleNet,
+            args: String::new(),
+            // Started again on purpose, to bring the running Battle.net to the front
+            skip_if_running: false,
+        },
+    ]
+}
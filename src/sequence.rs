@@ -0,0 +1,762 @@
+//! Tick-driven launch sequence runner.
+//!
+//! A `Sequence` is a list of `Step`s advanced from the UI event loop via `tick()`, so waits
+//! don't block a thread and can be paused, extended or skipped while they count down.
+
+use crate::launch::{self, LaunchOptions};
+use crate::recovery::{self, Recovery};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+pub enum Step {
+    /// Spawn an executable with `args` and watch it for `LAUNCH_CHECK`: failing to start or
+    /// exiting within that time with an error code aborts the rest of the sequence. With
+    /// `skip_if_running` nothing is started when a process of the same file name exists.
+    Launch {
+        name: String,
+        path: String,
+        args: Vec<String>,
+        options: LaunchOptions,
+        skip_if_running: bool,
+    },
+    /// Count down for `duration`, shown as `label` with a progress bar (`wait_progress`).
+    Wait { label: String, duration: Duration },
+    /// Hold until the user has been idle (no keyboard/mouse input) for `idle`, so the next
+    /// step doesn't steal focus mid-typing. Skipped where idle time can't be queried.
+    WaitForIdle { label: String, idle: Duration },
+    /// Hold until a process named `process` runs (and, with `window`, shows a visible window).
+    /// After `timeout` the sequence carries on regardless, so a renamed executable can't hang
+    /// the run.
+    WaitForProcess {
+        label: String,
+        process: String,
+        window: bool,
+        timeout: Duration,
+    },
+    /// Measure the latency to `host:port` in the background. Above `threshold` or when the
+    /// host is unreachable the sequence is abandoned with `abort`, otherwise it only warns.
+    CheckLatency {
+        host: String,
+        port: u16,
+        threshold: Duration,
+        abort: bool,
+    },
+    /// Run the shell `command` in the background, logging its output; it is stopped after
+    /// `Limits::hook_timeout` or when the sequence is dropped. A failure abandons the
+    /// sequence when `critical`, otherwise it only warns.
+    Hook {
+        label: String,
+        command: String,
+        critical: bool,
+    },
+    /// Hold until the user presses Continue (e.g. after a manual Battle.net 2FA login).
+    Confirm { message: String },
+    /// Ask the app to write the preferred locales into Config.wtf (`Event::WriteLocale`).
+    WriteLocale,
+    /// Terminate every running process named `process`; failures are reported, not fatal.
+    Kill { process: String },
+    /// Ask every process named `process` to close, terminating those still running after
+    /// `timeout`.
+    Close { process: String, timeout: Duration },
+}
+
+impl Step {
+    /// Short name for the run timeline, e.g. "Start WoW" or "Wait 30s".
+    pub fn title(&self) -> String {
+        match self {
+            Self::Launch { name, .. } => format!("Start {}", name),
+            Self::Wait { duration, .. } => format!("Wait {}s", duration.as_secs()),
+            Self::WaitForIdle { .. } => "Wait for idle".into(),
+            Self::WaitForProcess { process, .. } => format!("Wait for {}", process),
+            Self::CheckLatency { host, .. } => format!("Ping {}", host),
+            Self::Hook { label, .. } => label.clone(),
+            Self::Confirm { .. } => "Confirm".into(),
+            Self::WriteLocale => "Write locale".into(),
+            Self::Kill { process } => format!("Kill {}", process),
+            Self::Close { process, .. } => format!("Close {}", process),
+        }
+    }
+}
+
+/// Where the sequence is with one of its steps, for the run timeline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StepState {
+    Done,
+    /// Running; with the fraction done (0 to 1) for steps that count down.
+    Current(Option<f32>),
+    /// The step the sequence was abandoned at.
+    Failed,
+    Pending,
+}
+
+// How often idle time is re-checked while waiting for the user to stop typing
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// How often the process list is re-checked while waiting for a process to appear
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// How long a launched program is watched before it counts as running, and how often it is
+// checked meanwhile; a client that crashes on start is gone well within this
+pub const LAUNCH_CHECK: Duration = Duration::from_secs(2);
+const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Longest hook output kept in the log
+const MAX_HOOK_OUTPUT: usize = 500;
+
+pub enum Event {
+    /// Progress for the status line and the log: steps starting and ending, prompts.
+    Status(String),
+    /// A countdown for the status line only; it changes every second.
+    Countdown(String),
+    /// A `Launch` step started its executable and it was still running after `LAUNCH_CHECK`.
+    Launched {
+        name: String,
+        child: launch::Child,
+    },
+    /// Something worth keeping in the log that doesn't stop the sequence.
+    Warning(String),
+    /// Output of a hook, for the log only.
+    Output(String),
+    /// A `WriteLocale` step is due; the app owns Config.wtf and performs the write.
+    WriteLocale,
+    /// A step failed and the rest of the sequence was abandoned; `recovery` is set for
+    /// failures with a known fix, which `retry` can then resume from.
+    Failed {
+        message: String,
+        recovery: Option<Recovery>,
+    },
+    Finished,
+}
+
+pub struct Sequence {
+    steps: Vec<Step>,
+    index: usize,
+    // Remaining time of the current wait step (None until that step has started) and the
+    // time added to it with `extend`
+    remaining: Option<Duration>,
+    extended: Duration,
+    last_tick: Option<Instant>,
+    last_shown_secs: Option<u64>,
+    paused: bool,
+    skip_requested: bool,
+    confirmed: bool,
+    finished: bool,
+    // Pending measurement of the current `CheckLatency` step
+    latency_rx: Option<Receiver<Result<Duration, String>>>,
+    // Pending result of the current `Hook` step; dropping `hook_running` stops the command
+    hook_rx: Option<Receiver<Result<String, String>>>,
+    hook_running: Option<Sender<()>>,
+    // Program started by the current `Launch` step, watched until `LAUNCH_CHECK` passed, and
+    // the pending start of one run as administrator (the UAC prompt blocks its thread)
+    launched: Option<launch::Child>,
+    spawn_rx: Option<Receiver<std::io::Result<launch::Child>>>,
+}
+
+impl Sequence {
+    pub fn new(steps: Vec<Step>) -> Self {
+        Self {
+            steps,
+            index: 0,
+            remaining: None,
+            extended: Duration::ZERO,
+            last_tick: None,
+            last_shown_secs: None,
+            paused: false,
+            skip_requested: false,
+            confirmed: false,
+            finished: false,
+            latency_rx: None,
+            hook_rx: None,
+            hook_running: None,
+            launched: None,
+            spawn_rx: None,
+        }
+    }
+
+    /// Advance the sequence to `now`, running every step that became due.
+    pub fn tick(&mut self, now: Instant) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut elapsed = self
+            .last_tick
+            .map(|t| now.saturating_duration_since(t))
+            .unwrap_or_default();
+        self.last_tick = Some(now);
+        if self.finished || self.paused {
+            return events;
+        }
+
+        while let Some(step) = self.steps.get(self.index) {
+            match step {
+                Step::Launch {
+                    name,
+                    path,
+                    args,
+                    options,
+                    skip_if_running,
+                } => {
+                    if *skip_if_running
+                        && let Some(file) = std::path::Path::new(path).file_name()
+                        && !crate::processes::find_by_name(&file.to_string_lossy()).is_empty()
+                    {
+                        events.push(Event::Status(format!(
+                            "{} is already running; not starting another",
+                            name
+                        )));
+                        self.advance();
+                        continue;
+                    }
+                    let Some(child) = self.launched.as_mut() else {
+                        let started = if options.elevate {
+                            let Some(rx) = &self.spawn_rx else {
+                                self.spawn_rx = Some(launch::spawn_async(
+                                    path.clone(),
+                                    args.clone(),
+                                    options.clone(),
+                                ));
+                                events.push(Event::Status(format!(
+                                    "Starting {} as administrator...",
+                                    name
+                                )));
+                                return events;
+                            };
+                            let started = match rx.try_recv() {
+                                Err(TryRecvError::Empty) => return events,
+                                Ok(started) => started,
+                                Err(TryRecvError::Disconnected) => {
+                                    Err(std::io::Error::other("the start was interrupted"))
+                                }
+                            };
+                            self.spawn_rx = None;
+                            started
+                        } else {
+                            launch::spawn(path, args, options)
+                        };
+                        match started {
+                            Ok(child) => {
+                                if (options.priority != launch::Priority::Normal
+                                    || options.affinity != 0)
+                                    && let Err(e) = crate::processes::set_scheduling(
+                                        child.pid(),
+                                        options.priority,
+                                        options.affinity,
+                                    )
+                                {
+                                    events.push(Event::Warning(format!("{}: {}", name, e)));
+                                }
+                                events.push(Event::Status(format!("Starting {}...", name)));
+                                self.launched = Some(child);
+                                self.remaining = Some(LAUNCH_CHECK);
+                                return events;
+                            }
+                            Err(e) => {
+                                events.push(Event::Failed {
+                                    message: format!("{} failed to start: {}", name, e),
+                                    recovery: recovery::for_launch(name, &e),
+                                });
+                                self.finished = true;
+                                return events;
+                            }
+                        }
+                    };
+                    if let Some(code) = child.exited() {
+                        let msg = format!(
+                            "{} (pid {}) started but exited immediately {}",
+                            name,
+                            child.pid(),
+                            launch::describe_exit(code)
+                        );
+                        self.launched = None;
+                        // A launcher may hand over to an instance that already runs and exit
+                        // cleanly; only an error code means it crashed
+                        if code == Some(0) {
+                            events.push(Event::Warning(msg));
+                            self.advance();
+                            continue;
+                        }
+                        events.push(Event::Failed {
+                            message: format!("{}; run aborted", msg),
+                            recovery: None,
+                        });
+                        self.finished = true;
+                        return events;
+                    }
+                    let remaining = self
+                        .remaining
+                        .unwrap_or(LAUNCH_CHECK)
+                        .saturating_sub(std::mem::take(&mut elapsed));
+                    if !remaining.is_zero() {
+                        self.remaining = Some(remaining);
+                        return events;
+                    }
+                    if let Some(child) = self.launched.take() {
+                        events.push(Event::Launched {
+                            name: name.clone(),
+                            child,
+                        });
+                    }
+                    self.advance();
+                }
+                Step::Wait { .. } | Step::WaitForIdle { .. } | Step::WaitForProcess { .. }
+                    if std::mem::take(&mut self.skip_requested) =>
+                {
+                    self.advance();
+                }
+                Step::Wait { duration, .. } => {
+                    // Only time spent while this step was already running counts towards it;
+                    // the countdown itself is shown by `wait_progress`
+                    let remaining = match self.remaining {
+                        Some(r) => r.saturating_sub(std::mem::take(&mut elapsed)),
+                        None => *duration,
+                    };
+                    if remaining.is_zero() {
+                        self.advance();
+                        continue;
+                    }
+                    self.remaining = Some(remaining);
+                    return events;
+                }
+                Step::WaitForIdle { label, idle } => {
+                    let Some(current) = crate::user_idle_time() else {
+                        self.advance();
+                        continue;
+                    };
+                    if current >= *idle {
+                        self.advance();
+                        continue;
+                    }
+                    let secs = ceil_secs(*idle - current);
+                    if self.last_shown_secs != Some(secs) {
+                        self.last_shown_secs = Some(secs);
+                        events.push(Event::Countdown(format!("{}: {}s", label, secs)));
+                    }
+                    return events;
+                }
+                Step::WaitForProcess {
+                    process,
+                    window,
+                    timeout,
+                    ..
+                } => {
+                    let found = if *window {
+                        crate::processes::has_visible_window(process)
+                    } else {
+                        !crate::processes::find_by_name(process).is_empty()
+                    };
+                    if found {
+                        self.advance();
+                        continue;
+                    }
+                    // Time waited so far is tracked in `remaining` counting down from the timeout
+                    let remaining = match self.remaining {
+                        Some(r) => r.saturating_sub(std::mem::take(&mut elapsed)),
+                        None => *timeout,
+                    };
+                    if remaining.is_zero() {
+                        events.push(Event::Status(format!(
+                            "{} not detected after {}s; continuing",
+                            process,
+                            timeout.as_secs()
+                        )));
+                        self.advance();
+                        continue;
+                    }
+                    self.remaining = Some(remaining);
+                    return events;
+                }
+                Step::WriteLocale => {
+                    events.push(Event::WriteLocale);
+                    self.advance();
+                }
+                Step::Kill { process } => {
+                    let running = crate::processes::find_by_name(process);
+                    let errors: Vec<String> = running
+                        .iter()
+                        .filter_map(|p| crate::processes::kill(p.pid).err())
+                        .collect();
+                    events.push(Event::Status(if running.is_empty() {
+                        format!("{} is not running", process)
+                    } else if errors.is_empty() {
+                        format!("Closed {} ({} process(es))", process, running.len())
+                    } else {
+                        format!("Failed to close {}: {}", process, errors.join("; "))
+                    }));
+                    self.advance();
+                }
+                Step::Close { process, timeout } => {
+                    let running = crate::processes::find_by_name(process);
+                    let remaining = match self.remaining {
+                        Some(r) => r.saturating_sub(std::mem::take(&mut elapsed)),
+                        None => {
+                            for p in &running {
+                                crate::processes::request_close(p.pid);
+                            }
+                            *timeout
+                        }
+                    };
+                    if running.is_empty() {
+                        events.push(Event::Status(format!("Closed {}", process)));
+                        self.advance();
+                        continue;
+                    }
+                    if remaining.is_zero() {
+                        let errors: Vec<String> = running
+                            .iter()
+                            .filter_map(|p| crate::processes::kill(p.pid).err())
+                            .collect();
+                        events.push(Event::Status(if errors.is_empty() {
+                            format!("{} did not close in time; terminated it", process)
+                        } else {
+                            format!("Failed to close {}: {}", process, errors.join("; "))
+                        }));
+                        self.advance();
+                        continue;
+                    }
+                    self.remaining = Some(remaining);
+                    let secs = ceil_secs(remaining);
+                    if self.last_shown_secs != Some(secs) {
+                        self.last_shown_secs = Some(secs);
+                        events.push(Event::Countdown(format!("Closing {}: {}s", process, secs)));
+                    }
+                    return events;
+                }
+                Step::CheckLatency {
+                    host,
+                    port,
+                    threshold,
+                    abort,
+                } => {
+                    let Some(rx) = &self.latency_rx else {
+                        let (h, p) = (host.clone(), *port);
+                        self.latency_rx = Some(crate::repaint::spawn(move || {
+                            crate::hosts::measure_latency(&h, p)
+                        }));
+                        events.push(Event::Status(format!("Checking latency to {}...", host)));
+                        return events;
+                    };
+                    let problem = match rx.try_recv() {
+                        Err(TryRecvError::Empty) => return events,
+                        Ok(Ok(latency)) if latency <= *threshold => {
+                            events.push(Event::Status(format!(
+                                "Latency to {}: {} ms",
+                                host,
+                                latency.as_millis()
+                            )));
+                            self.advance();
+                            continue;
+                        }
+                        Ok(Ok(latency)) => format!(
+                            "Latency to {} is {} ms (limit {} ms)",
+                            host,
+                            latency.as_millis(),
+                            threshold.as_millis()
+                        ),
+                        Ok(Err(e)) => format!("{} is unreachable: {}", host, e),
+                        Err(TryRecvError::Disconnected) => {
+                            format!("Latency check of {} failed", host)
+                        }
+                    };
+                    if *abort {
+                        events.push(Event::Failed {
+                            message: format!("{}; launch aborted", problem),
+                            recovery: None,
+                        });
+                        self.finished = true;
+                        return events;
+                    }
+                    events.push(Event::Warning(format!("{}; launching anyway", problem)));
+                    self.advance();
+                }
+                Step::Hook {
+                    label,
+                    command,
+                    critical,
+                } => {
+                    let Some(rx) = &self.hook_rx else {
+                        let command = command.clone();
+                        let (running, keep_running) = std::sync::mpsc::channel();
+                        self.hook_running = Some(running);
+                        self.hook_rx = Some(crate::repaint::spawn(move || {
+                            let timeout = crate::limits::get().hook_timeout();
+                            launch::run_hook(&command, timeout, &keep_running)
+                        }));
+                        events.push(Event::Status(format!("Running {}...", label)));
+                        return events;
+                    };
+                    let problem = match rx.try_recv() {
+                        Err(TryRecvError::Empty) => return events,
+                        Ok(Ok(output)) => {
+                            if !output.is_empty() {
+                                events.push(Event::Output(format!(
+                                    "{}: {}",
+                                    label,
+                                    truncate(&output)
+                                )));
+                            }
+                            events.push(Event::Status(format!("{} done", label)));
+                            self.advance();
+                            continue;
+                        }
+                        Ok(Err(e)) => format!("{} failed: {}", label, truncate(&e)),
+                        Err(TryRecvError::Disconnected) => format!("{} failed", label),
+                    };
+                    if *critical {
+                        events.push(Event::Failed {
+                            message: format!("{}; run aborted", problem),
+                            recovery: None,
+                        });
+                        self.finished = true;
+                        return events;
+                    }
+                    events.push(Event::Warning(problem));
+                    self.advance();
+                }
+                Step::Confirm { message } => {
+                    if std::mem::take(&mut self.confirmed) {
+                        self.advance();
+                        continue;
+                    }
+                    if self.last_shown_secs.is_none() {
+                        // Announce the prompt once; last_shown_secs is reset when the step ends
+                        self.last_shown_secs = Some(0);
+                        events.push(Event::Status(message.clone()));
+                    }
+                    return events;
+                }
+            }
+        }
+
+        self.finished = true;
+        events.push(Event::Finished);
+        events
+    }
+
+    /// How long the UI may sleep before the next `tick()` has something to report.
+    pub fn next_tick_in(&self) -> Option<Duration> {
+        if self.finished || self.paused {
+            return None;
+        }
+        match self.steps.get(self.index) {
+            Some(Step::WaitForIdle { .. }) => return Some(IDLE_POLL_INTERVAL),
+            Some(Step::WaitForProcess { .. } | Step::Close { .. }) => {
+                return Some(PROCESS_POLL_INTERVAL);
+            }
+            // Their threads wake the window when done
+            Some(Step::CheckLatency { .. } | Step::Hook { .. }) => return None,
+            Some(Step::Launch { .. }) if self.spawn_rx.is_some() => return None,
+            Some(Step::Launch { .. }) if self.launched.is_some() => {
+                return Some(LAUNCH_POLL_INTERVAL);
+            }
+            _ => {}
+        }
+        let remaining = self.remaining?;
+        let frac = Duration::from_nanos((remaining.as_nanos() % 1_000_000_000) as u64);
+        Some(if frac.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            frac
+        })
+    }
+
+    /// Countdown of the current `Wait` or `WaitForProcess` step: its text, the fraction
+    /// elapsed (0 to 1).
+    pub fn wait_progress(&self) -> Option<(String, f32)> {
+        let remaining = self.remaining.filter(|_| !self.finished)?;
+        let text = match self.steps.get(self.index)? {
+            Step::Wait { label, .. } => format!("{}: {}s", label, ceil_secs(remaining)),
+            Step::WaitForProcess { label, .. } => {
+                format!("{} (giving up in {}s)", label, ceil_secs(remaining))
+            }
+            _ => return None,
+        };
+        let text = if self.paused {
+            format!("{} (paused)", text)
+        } else {
+            text
+        };
+        Some((text, self.step_progress().unwrap_or(0.0)))
+    }
+
+    /// Every step's title and state, in order.
+    pub fn timeline(&self) -> Vec<(String, StepState)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let state = if i < self.index {
+                    StepState::Done
+                } else if i > self.index {
+                    StepState::Pending
+                } else if self.finished {
+                    StepState::Failed
+                } else {
+                    StepState::Current(self.step_progress())
+                };
+                (step.title(), state)
+            })
+            .collect()
+    }
+
+    /// Fraction done of the current step, for those with a known length: waits, a launched
+    /// program being watched, a close running out its timeout.
+    fn step_progress(&self) -> Option<f32> {
+        let remaining = self.remaining?;
+        let total = match self.steps.get(self.index)? {
+            Step::Wait { duration, .. } => *duration,
+            Step::WaitForProcess { timeout, .. } | Step::Close { timeout, .. } => *timeout,
+            Step::Launch { .. } => LAUNCH_CHECK,
+            _ => return None,
+        };
+        let total = (total + self.extended).max(remaining);
+        Some(if total.is_zero() {
+            1.0
+        } else {
+            1.0 - remaining.as_secs_f32() / total.as_secs_f32()
+        })
+    }
+
+    /// Time left of the current countdown (a wait, a process wait's timeout, a close), for
+    /// showing outside the window.
+    pub fn countdown(&self) -> Option<Duration> {
+        match self.steps.get(self.index)? {
+            Step::Wait { .. } | Step::WaitForProcess { .. } | Step::Close { .. }
+                if !self.finished =>
+            {
+                self.remaining
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of the program the step after the current one launches, if any.
+    pub fn next_launch(&self) -> Option<&str> {
+        match self.steps.get(self.index + 1) {
+            Some(Step::Launch { name, .. }) => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Whether the current step is a wait (`extend` and `skip` only affect waits).
+    pub fn is_waiting(&self) -> bool {
+        !self.finished
+            && matches!(
+                self.steps.get(self.index),
+                Some(Step::Wait { .. } | Step::WaitForIdle { .. } | Step::WaitForProcess { .. })
+            )
+    }
+
+    /// Prompt of the current step if it is waiting for the user to press Continue.
+    pub fn confirmation_prompt(&self) -> Option<&str> {
+        match self.steps.get(self.index) {
+            Some(Step::Confirm { message }) if !self.finished => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Let a pending confirmation step continue on the next tick.
+    pub fn confirm(&mut self) {
+        if self.confirmation_prompt().is_some() {
+            self.confirmed = true;
+        }
+    }
+
+    /// Hold the sequence before its next step (a running wait stops counting down) until
+    /// unpaused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Add time to the current countdown.
+    pub fn extend(&mut self, by: Duration) {
+        if let Some(r) = self.remaining.as_mut() {
+            *r += by;
+            self.extended += by;
+        }
+    }
+
+    /// The step the sequence stopped at; after a failure, the one that failed.
+    pub fn current_step_mut(&mut self) -> Option<&mut Step> {
+        self.steps.get_mut(self.index)
+    }
+
+    /// Run the step that failed again on the next tick and carry on from there.
+    pub fn retry(&mut self) {
+        self.finished = false;
+        self.last_tick = None;
+        self.remaining = None;
+        self.last_shown_secs = None;
+        self.launched = None;
+        self.spawn_rx = None;
+    }
+
+    /// End the current wait immediately; the next step runs on the following tick.
+    pub fn skip(&mut self) {
+        if self.is_waiting() {
+            self.skip_requested = true;
+        }
+    }
+
+    fn advance(&mut self) {
+        self.index += 1;
+        self.remaining = None;
+        self.extended = Duration::ZERO;
+        self.last_shown_secs = None;
+        self.skip_requested = false;
+        self.confirmed = false;
+        self.latency_rx = None;
+        self.hook_rx = None;
+        self.hook_running = None;
+        self.launched = None;
+        self.spawn_rx = None;
+    }
+}
+
+/// `text` cut to `MAX_HOOK_OUTPUT` characters.
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_HOOK_OUTPUT) {
+        Some((i, _)) => format!("{}…", &text[..i]),
+        None => text.to_string(),
+    }
+}
+
+fn ceil_secs(d: Duration) -> u64 {
+    d.as_secs() + u64::from(d.subsec_nanos() > 0)
+}
+
+// Elevated launches fail right away off Windows, on their background thread
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elevated_launch_does_not_block_the_tick() {
+        let mut sequence = Sequence::new(vec![Step::Launch {
+            name: "Test".into(),
+            path: "test.exe".into(),
+            args: Vec::new(),
+            options: LaunchOptions {
+                elevate: true,
+                ..Default::default()
+            },
+            skip_if_running: false,
+        }]);
+        let events = sequence.tick(Instant::now());
+        assert!(matches!(events.as_slice(), [Event::Status(_)]));
+        assert_eq!(sequence.next_tick_in(), None);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let events = loop {
+            let events = sequence.tick(Instant::now());
+            if !events.is_empty() || Instant::now() > deadline {
+                break events;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert!(
+            matches!(events.as_slice(), [Event::Failed { message, .. }] if message.contains("administrator"))
+        );
+    }
+}
@@ -0,0 +1,411 @@
+//! Server folders: a folder linked to a profile that holds that server's own `Config.wtf`,
+//! `realmlist.wtf` and `AddOns` folder, swapped into the live installation when the profile
+//! is applied.
+//!
+//! The first time a server folder goes live, the installation's own files are kept: its
+//! Config.wtf and realmlist.wtf files in a zip below the settings directory, its
+//! `Interface/AddOns` renamed to `AddOns.entitan-original`. `restore_original` puts them back.
+//! A folder's addons are linked into place where the OS allows a directory symlink and copied
+//! otherwise. The live Config.wtf and realmlist.wtf are copies, so before another folder (or
+//! the original files) goes live they are saved back into the folder that is live, keeping
+//! the in-game changes made while it was.
+
+use crate::backup;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = "Config.wtf";
+const REALMLIST_FILE: &str = "realmlist.wtf";
+const ADDONS_DIR: &str = "AddOns";
+const ORIGINAL_ADDONS_DIR: &str = "AddOns.entitan-original";
+// Written into copied AddOns folders; a folder with it may be deleted on the next switch
+const COPY_MARKER: &str = ".entitan-server";
+
+/// Check that `folder` holds at least one of the files a server folder swaps in.
+pub fn check(folder: &Path) -> Result<(), String> {
+    if !folder.is_dir() {
+        return Err(format!("{} is not a folder", folder.display()));
+    }
+    if folder.join(CONFIG_FILE).is_file()
+        || folder.join(REALMLIST_FILE).is_file()
+        || folder.join(ADDONS_DIR).is_dir()
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} has none of {}, {} or an {} folder",
+            folder.display(),
+            CONFIG_FILE,
+            REALMLIST_FILE,
+            ADDONS_DIR
+        ))
+    }
+}
+
+/// Swap the files of `folder` into the installation of the Config.wtf `config`. Returns what
+/// was swapped in, for the status line.
+pub fn activate(folder: &Path, config: &Path) -> Result<Vec<String>, String> {
+    check(folder)?;
+    let game = game_dir(config)?;
+    keep_original(config, &game)?;
+    save_back(config, &game)?;
+    let mut swapped = Vec::new();
+    let source = folder.join(CONFIG_FILE);
+    if source.is_file() {
+        let bytes = fs::read(&source).map_err(|e| format!("{}: {}", source.display(), e))?;
+        write_live(config, &bytes)?;
+        swapped.push(CONFIG_FILE.to_string());
+    }
+    let source = folder.join(REALMLIST_FILE);
+    if source.is_file() {
+        let bytes = fs::read(&source).map_err(|e| format!("{}: {}", source.display(), e))?;
+        let targets = realmlists(&game);
+        for target in &targets {
+            write_live(target, &bytes)?;
+        }
+        swapped.push(if targets.len() == 1 {
+            REALMLIST_FILE.to_string()
+        } else {
+            format!("{} {} files", targets.len(), REALMLIST_FILE)
+        });
+    }
+    let source = folder.join(ADDONS_DIR);
+    if source.is_dir() {
+        let live = game.join("Interface").join(ADDONS_DIR);
+        let how = swap_addons(&source, &live)?;
+        swapped.push(format!("{} ({})", ADDONS_DIR, how));
+    }
+    let marker = active_marker(config).ok_or("cannot determine settings directory")?;
+    fs::write(&marker, folder.display().to_string())
+        .map_err(|e| format!("{}: {}", marker.display(), e))?;
+    Ok(swapped)
+}
+
+/// Whether a server folder has replaced files of the installation of `config`.
+pub fn is_active(config: &Path) -> bool {
+    originals_zip(config).is_some_and(|zip| zip.is_file())
+}
+
+/// Put back the installation's own files kept by `activate`. Returns the file count
+/// (the AddOns folder counting as one).
+pub fn restore_original(config: &Path) -> Result<usize, String> {
+    let game = game_dir(config)?;
+    let zip = originals_zip(config).ok_or("cannot determine settings directory")?;
+    let files = backup::read_zip(&zip)?;
+    save_back(config, &game)?;
+    for (rel, data) in &files {
+        write_live(&game.join(rel), data)?;
+    }
+    let mut restored = files.len();
+    let live = game.join("Interface").join(ADDONS_DIR);
+    let original = live.with_file_name(ORIGINAL_ADDONS_DIR);
+    if original.is_dir() {
+        remove_swapped_addons(&live)?;
+        fs::rename(&original, &live).map_err(|e| format!("{}: {}", live.display(), e))?;
+        restored += 1;
+    } else {
+        remove_swapped_addons(&live)?;
+    }
+    fs::remove_file(&zip).map_err(|e| format!("{}: {}", zip.display(), e))?;
+    if let Some(marker) = active_marker(config) {
+        let _ = fs::remove_file(marker);
+    }
+    Ok(restored)
+}
+
+/// Write a live installation file, keeping its read-only flag.
+fn write_live(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let readonly = path.metadata().is_ok_and(|m| m.permissions().readonly());
+    crate::with_writable(path, readonly, || {
+        crate::wtf::write_atomic(path, bytes).map_err(|e| e.to_string())
+    })
+    .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Copy the live Config.wtf and realmlist.wtf back into the server folder that is live, for
+/// the files that folder provides.
+fn save_back(config: &Path, game: &Path) -> Result<(), String> {
+    let Some(folder) = active_marker(config)
+        .and_then(|marker| fs::read_to_string(marker).ok())
+        .map(|s| PathBuf::from(s.trim()))
+        .filter(|folder| folder.is_dir())
+    else {
+        return Ok(());
+    };
+    let live_realmlist = realmlists(game).into_iter().find(|p| p.is_file());
+    let pairs = [
+        (Some(config.to_path_buf()), CONFIG_FILE),
+        (live_realmlist, REALMLIST_FILE),
+    ];
+    for (live, name) in pairs {
+        let target = folder.join(name);
+        if let Some(live) = live
+            && live.is_file()
+            && target.is_file()
+        {
+            let bytes = fs::read(&live).map_err(|e| format!("{}: {}", live.display(), e))?;
+            crate::wtf::write_atomic(&target, &bytes)
+                .map_err(|e| format!("{}: {}", target.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// The installation folder: the parent of the `WTF` folder holding `config`.
+fn game_dir(config: &Path) -> Result<PathBuf, String> {
+    config
+        .parent()
+        .and_then(Path::parent)
+        .filter(|d| !d.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("{} is not inside a WTF folder", config.display()))
+}
+
+/// The realmlist.wtf files of the installation: the client reads it from the game folder
+/// (older clients) or from `Data/<locale>`. When there are none yet, the game folder's.
+fn realmlists(game: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let root = game.join(REALMLIST_FILE);
+    if root.is_file() {
+        files.push(root.clone());
+    }
+    if let Ok(entries) = fs::read_dir(game.join("Data")) {
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path().join(REALMLIST_FILE))
+            .filter(|p| p.is_file())
+            .collect();
+        paths.sort();
+        files.extend(paths);
+    }
+    if files.is_empty() {
+        files.push(root);
+    }
+    files
+}
+
+/// Zip holding the installation's own Config.wtf and realmlist.wtf files while a server
+/// folder is live: one per WTF directory, like the AddOns.txt copies.
+fn originals_zip(config: &Path) -> Option<PathBuf> {
+    let wtf_dir = config.parent()?.to_string_lossy().to_lowercase();
+    let key = format!("{:08x}", crc32fast::hash(wtf_dir.as_bytes()));
+    Some(
+        crate::settings_dir()?
+            .join("servers")
+            .join(format!("{}.zip", key)),
+    )
+}
+
+/// File naming the server folder that is live in the installation of `config`, next to its
+/// `originals_zip`.
+fn active_marker(config: &Path) -> Option<PathBuf> {
+    originals_zip(config).map(|zip| zip.with_extension("folder"))
+}
+
+/// Save the installation's own files, unless a server folder already replaced them.
+fn keep_original(config: &Path, game: &Path) -> Result<(), String> {
+    let zip = originals_zip(config).ok_or("cannot determine settings directory")?;
+    if zip.is_file() {
+        return Ok(());
+    }
+    let mut names = Vec::new();
+    for path in std::iter::once(config.to_path_buf()).chain(realmlists(game)) {
+        if path.is_file()
+            && let Ok(rel) = path.strip_prefix(game)
+        {
+            let parts: Vec<String> = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            names.push(parts.join("/"));
+        }
+    }
+    let files = names
+        .into_iter()
+        .map(|name| backup::read_file(game, name))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(parent) = zip.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    backup::write_zip(&zip, &files)?;
+    Ok(())
+}
+
+/// Replace the live AddOns folder with `source`. The installation's own folder is renamed
+/// the first time; one put there by an earlier switch is removed. Returns "linked" or
+/// "copied".
+fn swap_addons(source: &Path, live: &Path) -> Result<&'static str, String> {
+    remove_swapped_addons(live)?;
+    if live.exists() {
+        let original = live.with_file_name(ORIGINAL_ADDONS_DIR);
+        if original.exists() {
+            return Err(format!(
+                "both {} and {} exist; move one of them away first",
+                live.display(),
+                original.display()
+            ));
+        }
+        fs::rename(live, &original).map_err(|e| format!("{}: {}", live.display(), e))?;
+    } else if let Some(parent) = live.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    if link_dir(source, live).is_ok() {
+        return Ok("linked");
+    }
+    copy_dir(source, live).map_err(|e| format!("{}: {}", live.display(), e))?;
+    fs::write(live.join(COPY_MARKER), source.display().to_string())
+        .map_err(|e| format!("{}: {}", live.display(), e))?;
+    Ok("copied")
+}
+
+/// Remove the live AddOns folder if an earlier switch put it there (a link, or a copy
+/// with the marker file); the installation's own folder is left alone.
+fn remove_swapped_addons(live: &Path) -> Result<(), String> {
+    let Ok(meta) = fs::symlink_metadata(live) else {
+        return Ok(());
+    };
+    let result = if meta.file_type().is_symlink() {
+        // Directory links are files on Unix and directories on Windows
+        fs::remove_file(live).or_else(|_| fs::remove_dir(live))
+    } else if live.join(COPY_MARKER).is_file() {
+        fs::remove_dir_all(live)
+    } else {
+        return Ok(());
+    };
+    result.map_err(|e| format!("{}: {}", live.display(), e))
+}
+
+#[cfg(unix)]
+fn link_dir(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+/// Needs Developer Mode or administrator rights; without them the folder is copied.
+#[cfg(windows)]
+fn link_dir(source: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(source, link)
+}
+
+fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            copy_dir(&path, &target.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, target.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        crate::location::use_temp_dir();
+        let dir =
+            std::env::temp_dir().join(format!("entitan-servers-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn server_folder(root: &Path, name: &str, realm: &str) -> PathBuf {
+        let folder = root.join(name);
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(
+            folder.join(CONFIG_FILE),
+            format!("SET realmName \"{}\"\n", name),
+        )
+        .unwrap();
+        fs::write(
+            folder.join(REALMLIST_FILE),
+            format!("set realmlist {}\n", realm),
+        )
+        .unwrap();
+        folder
+    }
+
+    #[test]
+    fn switching_saves_live_files_back_into_the_live_folder() {
+        let root = temp_dir("switch");
+        let game = root.join("Game");
+        fs::create_dir_all(game.join("WTF")).unwrap();
+        let config = game.join("WTF").join(CONFIG_FILE);
+        fs::write(&config, "SET realmName \"original\"\n").unwrap();
+        fs::write(
+            game.join(REALMLIST_FILE),
+            "set realmlist original.example.org\n",
+        )
+        .unwrap();
+        let a = server_folder(&root, "A", "a.example.org");
+        let b = server_folder(&root, "B", "b.example.org");
+
+        activate(&a, &config).unwrap();
+        assert!(is_active(&config));
+        assert_eq!(
+            fs::read(&config).unwrap(),
+            fs::read(a.join(CONFIG_FILE)).unwrap()
+        );
+        // The client saves an in-game change while A is live
+        fs::write(&config, "SET realmName \"A\"\nSET gxWindow \"1\"\n").unwrap();
+
+        activate(&b, &config).unwrap();
+        assert_eq!(
+            fs::read_to_string(a.join(CONFIG_FILE)).unwrap(),
+            "SET realmName \"A\"\nSET gxWindow \"1\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(game.join(REALMLIST_FILE)).unwrap(),
+            "set realmlist b.example.org\n"
+        );
+
+        activate(&a, &config).unwrap();
+        assert_eq!(
+            fs::read_to_string(&config).unwrap(),
+            "SET realmName \"A\"\nSET gxWindow \"1\"\n"
+        );
+
+        fs::write(&config, "SET realmName \"A\"\nSET gxWindow \"0\"\n").unwrap();
+        assert_eq!(restore_original(&config).unwrap(), 2);
+        assert!(!is_active(&config));
+        assert_eq!(
+            fs::read_to_string(&config).unwrap(),
+            "SET realmName \"original\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(game.join(REALMLIST_FILE)).unwrap(),
+            "set realmlist original.example.org\n"
+        );
+        assert_eq!(
+            fs::read_to_string(a.join(CONFIG_FILE)).unwrap(),
+            "SET realmName \"A\"\nSET gxWindow \"0\"\n"
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn read_only_config_stays_read_only() {
+        let root = temp_dir("readonly");
+        let game = root.join("Game");
+        fs::create_dir_all(game.join("WTF")).unwrap();
+        let config = game.join("WTF").join(CONFIG_FILE);
+        fs::write(&config, "SET realmName \"original\"\n").unwrap();
+        crate::wtf::set_readonly(&config, true).unwrap();
+        let a = server_folder(&root, "A", "a.example.org");
+
+        activate(&a, &config).unwrap();
+        assert!(config.metadata().unwrap().permissions().readonly());
+        assert_eq!(
+            fs::read(&config).unwrap(),
+            fs::read(a.join(CONFIG_FILE)).unwrap()
+        );
+        restore_original(&config).unwrap();
+        assert!(config.metadata().unwrap().permissions().readonly());
+        crate::wtf::set_readonly(&config, false).unwrap();
+        let _ = fs::remove_dir_all(&root);
+    }
+}
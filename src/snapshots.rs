@@ -0,0 +1,126 @@
+//! Named Config.wtf snapshots ("profiles") stored under the settings directory.
+//!
+//! Each snapshot is a verbatim copy of Config.wtf saved as `snapshots/<name>.wtf`, with the
+//! user's free-text notes for it in `snapshots/<name>.txt` and the server folder linked to it
+//! (see `servers`) in `snapshots/<name>.folder`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the snapshot files, next to `settings.json`.
+pub fn snapshots_dir() -> Option<PathBuf> {
+    crate::settings_dir().map(|d| d.join("snapshots"))
+}
+
+/// Names of all saved snapshots, sorted case-insensitively.
+pub fn list() -> Vec<String> {
+    let Some(dir) = snapshots_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| crate::is_file_with_ext(p, "wtf"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    names.sort_by_key(|n| n.to_lowercase());
+    names
+}
+
+/// Save a copy of `config` under the given snapshot name, replacing any existing one.
+pub fn save(name: &str, config: &Path) -> Result<(), String> {
+    let path = snapshot_path(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::copy(config, &path).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Overwrite `config` with the contents of the named snapshot.
+pub fn apply(name: &str, config: &Path) -> Result<(), String> {
+    if !config.is_file() {
+        return Err("Config.wtf path does not exist or is not a file".into());
+    }
+    let bytes = fs::read(snapshot_path(name)?).map_err(|e| e.to_string())?;
+    crate::wtf::write_atomic(config, &bytes).map_err(|e| e.to_string())
+}
+
+/// Delete the named snapshot and its notes.
+pub fn delete(name: &str) -> Result<(), String> {
+    let path = snapshot_path(name)?;
+    let _ = fs::remove_file(path.with_extension("txt"));
+    let _ = fs::remove_file(path.with_extension("folder"));
+    fs::remove_file(path).map_err(|e| e.to_string())
+}
+
+/// Notes of the named snapshot (empty if it has none).
+pub fn load_notes(name: &str) -> String {
+    snapshot_path(name)
+        .and_then(|p| fs::read_to_string(p.with_extension("txt")).map_err(|e| e.to_string()))
+        .unwrap_or_default()
+}
+
+/// Store the notes of the named snapshot; empty notes remove the file.
+pub fn save_notes(name: &str, notes: &str) -> Result<(), String> {
+    let path = snapshot_path(name)?.with_extension("txt");
+    if notes.trim().is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    }
+    fs::write(path, notes).map_err(|e| e.to_string())
+}
+
+/// Server folder linked to the named snapshot, if any.
+pub fn load_folder(name: &str) -> Option<PathBuf> {
+    let text = fs::read_to_string(snapshot_path(name).ok()?.with_extension("folder")).ok()?;
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(match crate::settings_dir() {
+        Some(dir) => dir.join(text),
+        None => PathBuf::from(text),
+    })
+}
+
+/// Link `folder` to the named snapshot; None unlinks it. A folder inside the settings
+/// directory is stored relative to it, so the link survives moving or syncing that directory.
+pub fn save_folder(name: &str, folder: Option<&Path>) -> Result<(), String> {
+    let path = snapshot_path(name)?.with_extension("folder");
+    let Some(folder) = folder else {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    };
+    let stored = crate::settings_dir()
+        .and_then(|dir| folder.strip_prefix(dir).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| folder.to_path_buf());
+    fs::write(path, stored.display().to_string()).map_err(|e| e.to_string())
+}
+
+fn snapshot_path(name: &str) -> Result<PathBuf, String> {
+    let file = sanitize_name(name);
+    if file.is_empty() {
+        return Err("Snapshot name is empty".into());
+    }
+    let dir = snapshots_dir().ok_or("cannot determine settings directory")?;
+    Ok(dir.join(format!("{}.wtf", file)))
+}
+
+/// Strip characters that aren't valid in file names so the name maps to a single file.
+fn sanitize_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .trim_matches(|c: char| c == '.' || c.is_whitespace())
+        .to_string()
+}
@@ -0,0 +1,53 @@
+//! The window's tabs. Each tab's rows are drawn by a module of its own below this one, so a
+//! new feature goes into the tab it belongs to instead of growing `update`.
+
+mod launch;
+mod locale;
+mod log;
+mod paths;
+
+use crate::tr;
+use eframe::egui;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tab {
+    Paths,
+    /// The locales in Config.wtf and the wanted ones; shown at startup.
+    #[default]
+    Locale,
+    Launch,
+    Log,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 4] = [Tab::Paths, Tab::Locale, Tab::Launch, Tab::Log];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Paths => "Paths",
+            Self::Locale => "Locale & Config",
+            Self::Launch => "Launch Sequence",
+            Self::Log => "Log/History",
+        }
+    }
+}
+
+/// Column widths shared by the rows of every tab, so labels and fields line up.
+#[derive(Clone, Copy)]
+pub struct Widths {
+    pub label_w: f32,
+    pub btn_w: f32,
+    pub text_w: f32,
+    // Path fields leave room for their recent-paths drop-down and padlock
+    pub path_w: f32,
+    pub right_pad: f32,
+}
+
+/// The row of tab buttons.
+pub fn bar(ui: &mut egui::Ui, tab: &mut Tab) {
+    ui.horizontal(|ui| {
+        for t in Tab::ALL {
+            ui.selectable_value(tab, t, tr(t.label()));
+        }
+    });
+}
@@ -0,0 +1,347 @@
+//! "Launch Sequence" tab: what happens around starting Battle.net and WoW, the checks
+//! before it and the guild broadcast after it.
+
+use super::Widths;
+use crate::*;
+
+impl EntitanApp {
+    pub(crate) fn launch_tab(&mut self, ui: &mut egui::Ui, widths: &Widths) {
+        let Widths {
+            label_w, text_w, ..
+        } = *widths;
+        ui.add_enabled_ui(!self.viewer(), |ui| {
+            // Shell commands around the run (VPN, mounting a drive, backup scripts)
+            for (label, command, critical, hint) in [
+                (
+                    tr("Before run:"),
+                    &mut self.pre_launch_hook,
+                    &mut self.pre_launch_hook_critical,
+                    "Command run before the sequence starts, e.g. connecting a VPN",
+                ),
+                (
+                    tr("After run:"),
+                    &mut self.post_launch_hook,
+                    &mut self.post_launch_hook_critical,
+                    "Command run after the sequence finished, e.g. a backup script",
+                ),
+            ] {
+                ui.horizontal(|ui| {
+                    let label = ui.add_sized([label_w, 24.0], egui::Label::new(label));
+                    ui.add_sized(
+                        [text_w, 24.0],
+                        egui::TextEdit::singleline(command).hint_text(tr("(none)")),
+                    )
+                    .labelled_by(label.id)
+                    .on_hover_text(format!(
+                        "{}; its output goes to the event log",
+                        hint
+                    ));
+                    ui.checkbox(critical, tr("Critical"))
+                        .on_hover_text("Abort the run if the command fails");
+                });
+            }
+
+            ui.add_space(6.0);
+
+            // Waits of the run sequence; slow machines need longer, fast ones less
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Delays:")));
+                let label = ui.label(tr("Before WoW"));
+                ui.add(
+                    egui::DragValue::new(&mut self.wow_launch_delay_secs)
+                        .range(0..=600)
+                        .suffix(" s"),
+                )
+                .labelled_by(label.id)
+                .on_hover_text("Wait after starting Battle.net before WoW is launched");
+                let label = ui.label(tr("Before Battle.net relaunch"));
+                ui.add(
+                    egui::DragValue::new(&mut self.relaunch_delay_secs)
+                        .range(0..=600)
+                        .suffix(" s"),
+                )
+                .labelled_by(label.id)
+                .on_hover_text("Wait after launching WoW before Battle.net is started again");
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(""));
+                ui.checkbox(&mut self.process_aware_waits, tr("Start the delays once Battle.net / WoW are running"))
+                    .on_hover_text(format!(
+                        "Wait until the launched process actually exists (up to {} s) before counting down, instead of relying on the timers alone",
+                        limits::get().process_wait_timeout_secs
+                    ));
+                ui.add_enabled(
+                    self.process_aware_waits,
+                    egui::Checkbox::new(&mut self.wait_for_login_window, tr("and Battle.net shows its window")),
+                );
+            });
+
+            // Optional idle wait before the final (focus-stealing) Battle.net relaunch
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Relaunch:")));
+                ui.checkbox(&mut self.close_battle_net_after_wow, tr("Close Battle.net instead"))
+                    .on_hover_text(format!(
+                        "Once WoW is running, ask Battle.net to close (and end it if it hasn't after {} s) rather than launching it again",
+                        limits::get().close_timeout_secs
+                    ));
+                let relaunch = !self.close_battle_net_after_wow;
+                let idle = ui.add_enabled(
+                    relaunch,
+                    egui::Checkbox::new(&mut self.relaunch_wait_for_idle, tr("Wait until idle for")),
+                )
+                .on_hover_text(
+                    "Delay the final Battle.net launch until there has been no keyboard or mouse input for this long",
+                );
+                ui.add_enabled(
+                    relaunch && self.relaunch_wait_for_idle,
+                    egui::DragValue::new(&mut self.relaunch_idle_secs)
+                        .range(1..=300)
+                        .suffix(" s"),
+                )
+                .labelled_by(idle.id);
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Duplicates:")));
+                ui.checkbox(
+                    &mut self.skip_running_programs,
+                    tr("Don't start Battle.net or WoW again if already running"),
+                )
+                .on_hover_text("Skip those launch steps instead of opening a second copy (the final Battle.net relaunch still happens)");
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Before WoW:")));
+                ui.checkbox(
+                    &mut self.write_locale_before_wow,
+                    tr("Write the preferred locale to Config.wtf right before starting WoW"),
+                )
+                .on_hover_text("Battle.net may rewrite Config.wtf while the run waits; this puts the preferred locale back just in time");
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Login:")));
+                ui.checkbox(
+                    &mut self.confirm_login,
+                    tr("Pause after starting Battle.net until I press Continue"),
+                )
+                .on_hover_text("Useful when Battle.net needs a manual (2FA) login before WoW may start");
+            });
+
+            ui.horizontal(|ui| {
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Clock:")));
+                let check = ui
+                    .checkbox(&mut self.check_clock_drift, tr("Check against"))
+                    .on_hover_text(
+                        "At startup, compare the system clock with this NTP server and warn if it is off by more than the threshold",
+                    );
+                ui.add_enabled(
+                    self.check_clock_drift,
+                    egui::TextEdit::singleline(&mut self.ntp_server).desired_width(110.0),
+                )
+                .labelled_by(check.id);
+                ui.add_enabled(
+                    self.check_clock_drift,
+                    egui::DragValue::new(&mut self.clock_drift_threshold_secs)
+                        .range(1..=3600)
+                        .prefix("± ")
+                        .suffix(" s"),
+                )
+                .labelled_by(label.id);
+                if ui
+                    .add_enabled(
+                        self.check_clock_drift && self.clock_rx.is_none(),
+                        egui::Button::new(tr("Check now")),
+                    )
+                    .clicked()
+                {
+                    self.clock_rx = Some(clock::check_async(self.ntp_server.clone()));
+                }
+                match &self.clock_offset {
+                    Some(Ok(offset)) => {
+                        let (text, color) = theme::verdict(
+                            ui,
+                            offset.abs() <= self.clock_drift_threshold_secs as f64,
+                            format!("off by {:+.1} s", -offset),
+                        );
+                        ui.colored_label(color, text);
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(theme::warn(ui), tr("check failed"))
+                            .on_hover_text(e);
+                    }
+                    None => {}
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Realm:")));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.realm_host)
+                        .desired_width(200.0)
+                        .hint_text(self.realm_list.as_deref().unwrap_or("realmList host")),
+                )
+                .labelled_by(label.id)
+                .on_hover_text("Realm hostname (and :port); leave empty to use realmList from Config.wtf");
+                if ui
+                    .add_enabled(self.hosts_rx.is_none(), egui::Button::new(tr("Check hosts/DNS")))
+                    .on_hover_text(
+                        "Resolve the realm hostname, compare it with the hosts file and check that the server answers",
+                    )
+                    .clicked()
+                {
+                    self.check_realm_host();
+                }
+                if self.hosts_rx.is_some() {
+                    busy_indicator(ui, self.reduce_motion());
+                }
+            });
+            self.hosts_results(ui, label_w);
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Latency:")));
+                let check = ui
+                    .checkbox(&mut self.latency_check, tr("Check the realm before launching; limit"))
+                    .on_hover_text(
+                        "Time a connection to the realm first, so a down server or a slow VPN is noticed before the whole sequence runs",
+                    );
+                ui.add_enabled(
+                    self.latency_check,
+                    egui::DragValue::new(&mut self.latency_threshold_ms)
+                        .range(10..=5000)
+                        .suffix(" ms"),
+                )
+                .labelled_by(check.id);
+                ui.add_enabled(
+                    self.latency_check,
+                    egui::Checkbox::new(&mut self.latency_abort, tr("Abort the launch")),
+                )
+                .on_hover_text("Otherwise only warn and launch anyway");
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Elevation:")));
+                ui.checkbox(
+                    &mut self.de_elevate_children,
+                    tr("Start games without admin rights"),
+                )
+                .on_hover_text(
+                    "If enTitan runs as administrator, launch Battle.net and WoW as the normal user so drag-and-drop and addon updaters keep working",
+                );
+                ui.checkbox(&mut self.battle_net_elevated, tr("Run Battle.net as administrator"));
+                ui.checkbox(&mut self.wow_elevated, tr("Run WoW as administrator"))
+                    .on_hover_text(
+                        "For installs under Program Files where starting normally fails with a permission error; asks via the UAC prompt on each launch",
+                    );
+                if launch::is_elevated() {
+                    ui.colored_label(
+                        theme::warn(ui),
+                        tr("(enTitan is running elevated)"),
+                    );
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Environment:")));
+                ui.checkbox(&mut self.sanitize_environment, tr("Remove"))
+                    .on_hover_text(
+                        "Start programs without these inherited environment variables; some overlays and injectors misbehave when they are present",
+                    );
+                ui.add_enabled(
+                    self.sanitize_environment,
+                    egui::TextEdit::singleline(&mut self.environment_blocklist)
+                        .desired_width(text_w - 80.0),
+                )
+                .labelled_by(label.id)
+                .on_hover_text("Variable names separated by spaces; PREFIX_* matches every variable starting with PREFIX_. Not applied when running as administrator");
+                if ui
+                    .add_enabled(self.sanitize_environment, egui::Button::new(tr("Default")))
+                    .clicked()
+                {
+                    self.environment_blocklist = launch::DEFAULT_ENV_REMOVE.to_string();
+                }
+            });
+
+            // Scheduling of the launched client; helps the old client on older CPUs
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("WoW CPU:")));
+                let label = ui.label(tr("Priority"));
+                egui::ComboBox::from_id_salt("wow_priority")
+                    .selected_text(self.wow_priority.label())
+                    .show_ui(ui, |ui| {
+                        for priority in launch::Priority::ALL {
+                            ui.selectable_value(
+                                &mut self.wow_priority,
+                                priority,
+                                priority.label(),
+                            );
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id);
+                let label = ui.label(tr("Cores"));
+                let cpus = std::thread::available_parallelism()
+                    .map_or(1, |n| n.get())
+                    .min(64);
+                let selected = if self.wow_affinity == 0 {
+                    tr("All").to_string()
+                } else {
+                    format!("{} of {}", self.wow_affinity.count_ones(), cpus)
+                };
+                egui::ComboBox::from_id_salt("wow_affinity")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        if ui.button(tr("All cores")).clicked() {
+                            self.wow_affinity = 0;
+                        }
+                        for cpu in 0..cpus {
+                            let bit = 1u64 << cpu;
+                            let mut on = self.wow_affinity == 0 || self.wow_affinity & bit != 0;
+                            if ui.checkbox(&mut on, format!("CPU {}", cpu)).changed() {
+                                let all = if cpus == 64 { u64::MAX } else { (1 << cpus) - 1 };
+                                let mask = if self.wow_affinity == 0 { all } else { self.wow_affinity };
+                                let mask = if on { mask | bit } else { mask & !bit };
+                                // An empty set would keep WoW from running at all
+                                if mask != 0 {
+                                    self.wow_affinity = if mask == all { 0 } else { mask };
+                                }
+                            }
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id)
+                    .on_hover_text("Applied right after WoW starts (Windows only); a failure is logged, not fatal");
+            });
+
+            // Unattended run at a time of day, e.g. just before the raid reset
+            self.schedule_controls(ui, label_w);
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Firewall:")));
+                let button = ui
+                    .add_enabled(
+                        self.firewall_rx.is_none(),
+                        egui::Button::new(tr("Allow Battle.net and WoW…")),
+                    )
+                    .on_hover_text(
+                        "Create Windows Defender Firewall rules for both executables (asks for administrator permission)",
+                    );
+                if button.clicked() {
+                    self.create_firewall_rules();
+                    self.refocus(&button);
+                }
+                if self.firewall_hint {
+                    ui.colored_label(
+                        theme::warn(ui),
+                        tr("WoW closed right after launching"),
+                    );
+                }
+            });
+        });
+
+        ui.separator();
+        self.sequence_panel(ui);
+        self.broadcast_panel(ui);
+    }
+}
@@ -0,0 +1,302 @@
+//! "Locale & Config" tab: the locales Config.wtf holds and the ones wanted, keeping them
+//! that way, and the saved profiles, presets and account configs.
+
+use super::Widths;
+use crate::*;
+
+impl EntitanApp {
+    pub(crate) fn locale_tab(&mut self, ui: &mut egui::Ui, widths: &Widths) {
+        let Widths {
+            label_w,
+            btn_w,
+            text_w,
+            right_pad,
+            ..
+        } = *widths;
+        let viewer = self.viewer();
+        // audioLocale row (aligned and colored; value left-aligned to textfield column)
+        ui.horizontal(|ui| {
+            let label = ui.add_sized([label_w, 24.0], egui::Label::new("audioLocale:"));
+            let a = self
+                .audio_locale
+                .as_deref()
+                .map_or_else(|| tr("(not available)").into(), locales::label);
+            let matches = self
+                .audio_locale
+                .as_deref()
+                .is_some_and(|v| v.eq_ignore_ascii_case(&self.preferred_audio_locale));
+            let (a, a_color) = theme::verdict(ui, matches, a);
+            // A label rather than painted text, so screen readers announce it with its name
+            ui.add_space(4.0);
+            ui.colored_label(a_color, a).labelled_by(label.id);
+        });
+
+        // textLocale row (aligned and colored; value left-aligned to textfield column)
+        ui.horizontal(|ui| {
+            let label = ui.add_sized([label_w, 24.0], egui::Label::new("textLocale:"));
+            let t = self
+                .text_locale
+                .as_deref()
+                .map_or_else(|| tr("(not available)").into(), locales::label);
+            let matches = self
+                .text_locale
+                .as_deref()
+                .is_some_and(|v| v.eq_ignore_ascii_case(&self.preferred_text_locale));
+            let (t, t_color) = theme::verdict(ui, matches, t);
+            // A label rather than painted text, so screen readers announce it with its name
+            ui.add_space(4.0);
+            ui.colored_label(t_color, t).labelled_by(label.id);
+        });
+
+        // Watcher health: whether on-disk edits of Config.wtf are picked up automatically
+        ui.horizontal(|ui| {
+            ui.add_sized([label_w, 24.0], egui::Label::new(tr("Live reload:")));
+            if self.watched_path.is_some() {
+                ui.colored_label(theme::good(ui), tr("✔ active"));
+            } else {
+                ui.colored_label(theme::bad(ui), tr("✖ inactive"))
+                    .on_hover_text("The WTF folder is not being watched; retrying periodically");
+            }
+        });
+
+        // The locale a running client actually uses: a -locale argument beats Config.wtf
+        if let Some(ref game) = self.running_game {
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Running game:")));
+                match (&game.locale_arg, &self.text_locale) {
+                    (Some(arg), Some(cfg)) if !arg.eq_ignore_ascii_case(cfg) => {
+                        ui.colored_label(
+                            theme::warn(ui),
+                            format!(
+                                "WoW (pid {}) was started with -locale {}, overriding textLocale {}",
+                                game.pid, arg, cfg
+                            ),
+                        );
+                    }
+                    (Some(arg), _) => {
+                        ui.label(format!(
+                            "WoW (pid {}) was started with -locale {}",
+                            game.pid, arg
+                        ));
+                    }
+                    (None, _) => {
+                        ui.label(format!(
+                            "WoW (pid {}) uses the Config.wtf locale",
+                            game.pid
+                        ));
+                    }
+                }
+            });
+        }
+
+        if let Some(size) = self.large_config_size {
+            ui.colored_label(
+                theme::warn(ui),
+                format!(
+                    "Large file mode: Config.wtf is {:.1} MiB, so only its locale lines are read and edited (not undoable)",
+                    size as f64 / (1024.0 * 1024.0)
+                ),
+            );
+        }
+
+        // Lint findings, so a broken Config.wtf doesn't just show up as "not available"
+        if !self.config_findings.is_empty() {
+            egui::CollapsingHeader::new(format!(
+                "Config.wtf problems ({})",
+                self.config_findings.len()
+            ))
+            .id_salt("config_findings")
+            .show(ui, |ui| {
+                for finding in &self.config_findings {
+                    ui.colored_label(theme::warn(ui), finding);
+                }
+            });
+        }
+
+        // Free-text notes of the active profile ("PTR wipes Tuesdays")
+        if let Some(profile) = self.active_profile.clone() {
+            ui.horizontal(|ui| {
+                let label = ui
+                    .add_sized([label_w, 24.0], egui::Label::new(tr("Notes:")))
+                    .on_hover_text(format!("Notes of profile \"{}\"", profile));
+                let response = ui
+                    .add_sized(
+                        [text_w, 48.0],
+                        egui::TextEdit::multiline(&mut self.profile_notes)
+                            .hint_text(format!("Notes for \"{}\"", profile))
+                            .interactive(!viewer),
+                    )
+                    .labelled_by(label.id);
+                if response.changed()
+                    && let Err(e) = snapshots::save_notes(&profile, &self.profile_notes)
+                {
+                    self.status = Some(format!("Error saving notes: {}", e));
+                }
+            });
+        }
+
+        ui.separator();
+        ui.add_space(6.0);
+
+        // Everything below edits the setup
+        ui.add_enabled_ui(!self.viewer(), |ui| {
+            // Preferred audio locale row (aligned)
+            ui.horizontal(|ui| {
+                // reuse label_w, btn_w, text_w from above
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Preferred Audio:")));
+                locale_combo(ui, "preferred_audio", &mut self.preferred_audio_locale, text_w)
+                    .labelled_by(label.id);
+                if ui
+                    .add_sized([btn_w, 24.0], egui::Button::new(tr("Update")))
+                    .on_hover_text(input::Action::UpdateLocale.hint(
+                        ui.ctx(),
+                        "Write both preferred locales to Config.wtf",
+                    ))
+                    .confirmed(self.confirm_writes)
+                {
+                    self.update_clicked();
+                }
+                // reserve space for a potential second button so alignment matches WoW row
+                ui.add_sized([btn_w, 24.0], egui::Label::new(""));
+                ui.add_sized([right_pad, 24.0], egui::Label::new(""));
+            });
+
+            ui.add_space(6.0);
+
+            // Preferred text locale row (aligned)
+            ui.horizontal(|ui| {
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Preferred Text:")));
+                locale_combo(ui, "preferred_text", &mut self.preferred_text_locale, text_w)
+                    .labelled_by(label.id);
+            });
+
+            ui.add_space(6.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Enforce:")));
+                if ui
+                    .checkbox(&mut self.enforce_locale, tr("Keep Config.wtf on the preferred locales"))
+                    .on_hover_text(
+                        "Whenever Config.wtf changes on disk and its locales differ from the preferred ones, rewrite them automatically",
+                    )
+                    .changed()
+                {
+                    if self.enforce_locale {
+                        self.schedule_enforcement();
+                    } else {
+                        self.enforce_due = None;
+                    }
+                }
+                if self.enforce_count > 0 {
+                    ui.label(format!("Reset by Battle.net {} time(s)", self.enforce_count));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new("Battle.net:"));
+                ui.checkbox(
+                    &mut self.sync_battle_net_config,
+                    tr("Also update Battle.net.config"),
+                )
+                .on_hover_text(
+                    "Write the preferred locale into Battle.net's own per-game language settings so it stops resetting Config.wtf. Close Battle.net first; it rewrites the file while running.",
+                );
+                for entry in &self.bnet_locales {
+                    let preferred = if entry.audio {
+                        &self.preferred_audio_locale
+                    } else {
+                        &self.preferred_text_locale
+                    };
+                    let (text, color) =
+                        theme::verdict(ui, entry.value.eq_ignore_ascii_case(preferred), &entry.value);
+                    ui.colored_label(color, text)
+                        .on_hover_text(entry.name());
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Addons:")));
+                if ui
+                    .checkbox(
+                        &mut self.protect_addons,
+                        tr("Save AddOns.txt before writing the locale"),
+                    )
+                    .on_hover_text(
+                        "WoW may disable every addon after a locale switch; keep a copy of each character's addon list and offer to restore it if that happens",
+                    )
+                    .changed()
+                    && !self.protect_addons
+                {
+                    self.blanked_addons.clear();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Screenshots:")));
+                ui.checkbox(
+                    &mut self.organize_screenshots,
+                    tr("Sort new screenshots into per-locale folders"),
+                )
+                .on_hover_text(
+                    "Move screenshots WoW saves next to Wow.exe into Screenshots/<textLocale>",
+                );
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Verify:")));
+                ui.checkbox(
+                    &mut self.verify_locale,
+                    tr("Check the locale the client loaded when WoW exits"),
+                )
+                .on_hover_text(
+                    "Read the client's cache and logs instead of trusting Config.wtf, and suggest fixes on a mismatch",
+                );
+                if ui
+                    .button(tr("Check now"))
+                    .on_hover_text("Check the files of the last session")
+                    .clicked()
+                {
+                    let locale_arg = self
+                        .running_game
+                        .as_ref()
+                        .and_then(|g| g.locale_arg.clone());
+                    self.verify_loaded_locale(None, locale_arg.as_deref());
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Export:")));
+                let button = ui
+                    .button(tr("Locale report (JSON)"))
+                    .on_hover_text(
+                        "Locale CVars from Config.wtf and the locale packs installed next to Wow.exe, for translation projects",
+                    );
+                if button.clicked() {
+                    self.export_locale_report();
+                    self.refocus(&button);
+                }
+                let button = ui
+                    .button(tr("Cvars (JSON/TOML)"))
+                    .on_hover_text("Save every cvar in Config.wtf as a JSON or TOML file");
+                if button.clicked() {
+                    self.export_cvars();
+                    self.refocus(&button);
+                }
+                let button = ui
+                    .button(tr("Import cvars…"))
+                    .on_hover_text(
+                        "Merge a JSON or TOML cvar file into Config.wtf; other cvars are kept",
+                    );
+                if button.confirmed(self.confirm_writes) {
+                    self.import_cvars();
+                    self.refocus(&button);
+                }
+            });
+        });
+
+        ui.separator();
+        self.profiles_panel(ui);
+        self.presets_panel(ui);
+        self.accounts_panel(ui);
+    }
+}
@@ -0,0 +1,42 @@
+//! "Log/History" tab: undo of Config.wtf changes and the event log.
+
+use super::Widths;
+use crate::*;
+
+impl EntitanApp {
+    pub(crate) fn log_tab(&mut self, ui: &mut egui::Ui, widths: &Widths) {
+        let label_w = widths.label_w;
+        ui.add_enabled_ui(!self.viewer(), |ui| {
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("History:")));
+                let undo = self.history.undo_label().map(str::to_string);
+                let redo = self.history.redo_label().map(str::to_string);
+                let undo_btn = ui
+                    .add_enabled(undo.is_some(), egui::Button::new(tr("Undo")))
+                    .on_hover_text(format!("Undo: {}", undo.as_deref().unwrap_or("nothing")));
+                let redo_btn = ui
+                    .add_enabled(redo.is_some(), egui::Button::new(tr("Redo")))
+                    .on_hover_text(format!("Redo: {}", redo.as_deref().unwrap_or("nothing")));
+                let result = if undo_btn.confirmed(self.confirm_writes) {
+                    Some(self.step_history(false).map(|l| format!("Undid: {}", l)))
+                } else if redo_btn.confirmed(self.confirm_writes) {
+                    Some(self.step_history(true).map(|l| format!("Redid: {}", l)))
+                } else {
+                    None
+                };
+                if let Some(result) = result {
+                    self.status = Some(result.unwrap_or_else(|e| format!("Error: {}", e)));
+                    // Force a refresh of cached values
+                    self.last_config_path = None;
+                }
+                ui.checkbox(&mut self.confirm_writes, tr("Hold to confirm writes"))
+                    .on_hover_text(
+                        "Buttons that change Config.wtf or the hosts file only act after being held down for a moment; automatic writes (Enforce, run sequence) are not affected",
+                    );
+            });
+        });
+
+        ui.separator();
+        self.event_log_panel(ui);
+    }
+}
@@ -0,0 +1,364 @@
+//! "Paths" tab: the programs and Config.wtf enTitan works with, and where its own settings
+//! live.
+
+use super::Widths;
+use crate::*;
+
+impl EntitanApp {
+    pub(crate) fn paths_tab(&mut self, ui: &mut egui::Ui, widths: &Widths) {
+        let Widths {
+            label_w,
+            btn_w,
+            text_w,
+            path_w,
+            right_pad,
+        } = *widths;
+        ui.add_enabled_ui(!self.viewer(), |ui| {
+            // Battle.net row (aligned)
+            ui.horizontal(|ui| {
+                // reuse label_w, btn_w, text_w from above
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new("Battle.net"));
+                ui.add_sized(
+                    [path_w, 24.0],
+                    egui::TextEdit::singleline(&mut self.battle_net_path)
+                        .id(PathField::BattleNet.id())
+                        .interactive(!self.battle_net_locked),
+                )
+                .labelled_by(label.id);
+                if let Some(path) = recent_paths_combo(
+                    ui,
+                    PathField::BattleNet,
+                    self.recent_paths.list(PathField::BattleNet),
+                    self.path(PathField::BattleNet),
+                    !self.battle_net_locked,
+                ) {
+                    self.set_path(PathField::BattleNet, path);
+                    self.status = Some("Selected (unsaved)".into());
+                }
+                lock_toggle(ui, &mut self.battle_net_locked);
+                if ui
+                    .add_enabled_ui(!self.battle_net_locked, |ui| {
+                        ui.add_sized([btn_w, 24.0], egui::Button::new(tr("Browse")))
+                    })
+                    .inner
+                    .clicked()
+                {
+                    // Back to the field once the dialog has closed
+                    self.pending_focus = Some(PathField::BattleNet.id());
+                    let mut dialog = FileDialog::new();
+                    if !self.battle_net_path.is_empty()
+                        && let Some(parent) = Path::new(&self.battle_net_path).parent()
+                    {
+                        dialog = dialog.set_directory(parent);
+                    }
+                    if let Some(file) = dialog.add_filter("exe", &["exe"]).pick_file() {
+                        if is_file_with_ext(&file, "exe") {
+                            self.set_path(PathField::BattleNet, file.display().to_string());
+                            self.status = Some("Selected (unsaved)".into());
+                        } else {
+                            self.status = Some("Selected file is not an .exe".into());
+                        }
+                    }
+                }
+                if ui
+                    .add_sized([btn_w, 24.0], egui::Button::new(tr("Test")))
+                    .on_hover_text(TEST_LAUNCH_HINT)
+                    .clicked()
+                {
+                    self.test_launch(PathField::BattleNet);
+                }
+                ui.add_sized([right_pad, 24.0], egui::Label::new(""));
+            });
+
+            ui.add_space(6.0);
+
+            // Config.wtf row (aligned)
+            ui.horizontal(|ui| {
+                // reuse label_w, btn_w, text_w from above
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new("Config.wtf:"));
+                ui.add_sized(
+                    [path_w, 24.0],
+                    egui::TextEdit::singleline(&mut self.config_wtf_path)
+                        .id(PathField::ConfigWtf.id())
+                        .interactive(!self.config_wtf_locked),
+                )
+                .labelled_by(label.id);
+                if let Some(path) = recent_paths_combo(
+                    ui,
+                    PathField::ConfigWtf,
+                    self.recent_paths.list(PathField::ConfigWtf),
+                    self.path(PathField::ConfigWtf),
+                    !self.config_wtf_locked,
+                ) {
+                    self.set_path(PathField::ConfigWtf, path);
+                    self.status = Some("Selected (unsaved)".into());
+                    self.update_locales();
+                }
+                lock_toggle(ui, &mut self.config_wtf_locked);
+                if ui
+                    .add_enabled_ui(!self.config_wtf_locked, |ui| {
+                        ui.add_sized([btn_w, 24.0], egui::Button::new(tr("Browse")))
+                    })
+                    .inner
+                    .clicked()
+                {
+                    // Back to the field once the dialog has closed
+                    self.pending_focus = Some(PathField::ConfigWtf.id());
+                    let mut dialog = FileDialog::new();
+                    if !self.config_wtf_path.is_empty()
+                        && let Some(parent) = Path::new(&self.config_wtf_path).parent()
+                    {
+                        dialog = dialog.set_directory(parent);
+                    }
+                    if let Some(file) = dialog.add_filter("wtf", &["wtf"]).pick_file() {
+                        if is_file_with_ext(&file, "wtf") {
+                            self.set_path(PathField::ConfigWtf, file.display().to_string());
+                            self.status = Some("Selected (unsaved)".into());
+                            // refresh cached locale values immediately
+                            self.update_locales();
+                        } else {
+                            self.status = Some("Selected file is not a .wtf file".into());
+                        }
+                    }
+                }
+                // reserve space for a second button so buttons align across rows
+                ui.add_sized([btn_w, 24.0], egui::Label::new(""));
+                ui.add_sized([right_pad, 24.0], egui::Label::new(""));
+            });
+
+            // Read-only Config.wtf: writes are refused unless the user opts into clearing the flag
+            if self.config_readonly {
+                ui.horizontal(|ui| {
+                    ui.add_sized([label_w, 24.0], egui::Label::new(""));
+                    ui.colored_label(
+                        theme::warn(ui),
+                        tr("Config.wtf is read-only"),
+                    );
+                    if ui
+                        .button(tr("Clear flag, update, restore"))
+                        .on_hover_text(
+                            "Temporarily clear the read-only attribute, write the preferred locales and set it again",
+                        )
+                        .confirmed(self.confirm_writes)
+                    {
+                        match self.update_config_file_locales(true) {
+                            Ok(()) => {
+                                self.status =
+                                    Some("Config.wtf updated (read-only flag restored)".into())
+                            }
+                            Err(e) => {
+                                self.status = Some(format!("Error updating config: {}", e))
+                            }
+                        }
+                    }
+                });
+            }
+
+            ui.add_space(6.0);
+
+            // WoW Executable row (aligned)
+            ui.horizontal(|ui| {
+                let label = ui.add_sized([label_w, 24.0], egui::Label::new(tr("WoW Executable:")));
+                ui.add_sized(
+                    [path_w, 24.0],
+                    egui::TextEdit::singleline(&mut self.wow_executable_path)
+                        .id(PathField::Wow.id())
+                        .interactive(!self.wow_locked),
+                )
+                .labelled_by(label.id);
+                if let Some(path) = recent_paths_combo(
+                    ui,
+                    PathField::Wow,
+                    self.recent_paths.list(PathField::Wow),
+                    self.path(PathField::Wow),
+                    !self.wow_locked,
+                ) {
+                    self.set_path(PathField::Wow, path);
+                    self.status = Some("Selected (unsaved)".into());
+                }
+                lock_toggle(ui, &mut self.wow_locked);
+                if ui
+                    .add_enabled_ui(!self.wow_locked, |ui| {
+                        ui.add_sized([btn_w, 24.0], egui::Button::new(tr("Browse")))
+                    })
+                    .inner
+                    .clicked()
+                {
+                    // Back to the field once the dialog has closed
+                    self.pending_focus = Some(PathField::Wow.id());
+                    let mut dialog = FileDialog::new();
+                    if !self.wow_executable_path.is_empty()
+                        && let Some(parent) = Path::new(&self.wow_executable_path).parent()
+                    {
+                        dialog = dialog.set_directory(parent);
+                    }
+                    if let Some(file) = dialog.add_filter("exe", &["exe"]).pick_file() {
+                        if is_file_with_ext(&file, "exe") {
+                            self.set_path(PathField::Wow, file.display().to_string());
+                            self.status = Some("Selected (unsaved)".into());
+                        } else {
+                            self.status = Some("Selected file is not an .exe".into());
+                        }
+                    }
+                }
+                if ui
+                    .add_sized([btn_w, 24.0], egui::Button::new(tr("Test")))
+                    .on_hover_text(TEST_LAUNCH_HINT)
+                    .clicked()
+                {
+                    self.test_launch(PathField::Wow);
+                }
+                ui.add_sized([right_pad, 24.0], egui::Label::new(""));
+            });
+
+            ui.add_space(6.0);
+
+            // Command-line flags for both executables
+            ui.horizontal(|ui| {
+                // Each field is announced as "Arguments: Battle.net" / "Arguments: WoW"
+                let row = ui.add_sized([label_w, 24.0], egui::Label::new(tr("Arguments:")));
+                let label = ui.label("Battle.net");
+                ui.add_sized(
+                    [(text_w - 120.0) / 2.0, 24.0],
+                    egui::TextEdit::singleline(&mut self.battle_net_args)
+                        .hint_text("-launcherlogin"),
+                )
+                .labelled_by(row.id)
+                .labelled_by(label.id);
+                let label = ui.label("WoW");
+                ui.add_sized(
+                    [(text_w - 120.0) / 2.0, 24.0],
+                    egui::TextEdit::singleline(&mut self.wow_args).hint_text("-console"),
+                )
+                .labelled_by(row.id)
+                .labelled_by(label.id)
+                .on_hover_text("Separate arguments with spaces; use \"double quotes\" around values containing spaces");
+            });
+
+            ui.add_space(6.0);
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Backup:")));
+                let button = ui
+                    .button(tr("Backup enTitan…"))
+                    .on_hover_text(
+                        "Save settings, profiles (Config.wtf snapshots and notes) and the run history in one zip file",
+                    );
+                if button.clicked() {
+                    self.backup_app();
+                    self.refocus(&button);
+                }
+                let button = ui
+                    .button(tr("Restore…"))
+                    .on_hover_text("Replace the current setup with the contents of a backup");
+                if button.clicked() {
+                    self.restore_backup();
+                    self.refocus(&button);
+                }
+                let button = ui
+                    .button(tr("Import other launcher…"))
+                    .on_hover_text(
+                        "Take over paths, arguments, realm and locale from another launcher's .ini file",
+                    );
+                if button.clicked() {
+                    self.import_legacy();
+                    self.refocus(&button);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Share settings:")));
+                let button = ui
+                    .button(tr("Export settings…"))
+                    .on_hover_text(
+                        "Save settings and profiles in one file for another PC or a guildmate (no run history, window position, hooks, elevation or broadcast webhook)",
+                    );
+                if button.clicked() {
+                    self.export_bundle();
+                    self.refocus(&button);
+                }
+                let button = ui.button(tr("Import settings…")).on_hover_text(
+                    "Take over the settings of an exported file and add its profiles",
+                );
+                if button.clicked() {
+                    self.import_bundle();
+                    self.refocus(&button);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Settings folder:")));
+                let dir = settings_dir()
+                    .map(|d| d.display().to_string())
+                    .unwrap_or_else(|| "(unknown)".into());
+                let source = location::source();
+                if source == location::Source::Default && location::default_is_exe_dir() {
+                    ui.weak(dir).on_hover_text(
+                        "Next to enTitan, because the user folder (APPDATA or HOME) is not set",
+                    );
+                } else {
+                    ui.weak(dir);
+                }
+                let hint = format!("Set by the {} environment variable", location::ENV_VAR);
+                let button = ui
+                    .add_enabled(
+                        source != location::Source::Environment,
+                        egui::Button::new(tr("Change…")),
+                    )
+                    .on_hover_text(
+                        "Keep settings, profiles and backups in another folder, e.g. a synced cloud folder",
+                    )
+                    .on_disabled_hover_text(&hint);
+                if button.clicked() {
+                    self.move_settings_dir(None);
+                    self.refocus(&button);
+                }
+                let button = ui
+                    .add_enabled(
+                        source == location::Source::Chosen,
+                        egui::Button::new(tr("Use default")),
+                    )
+                    .on_hover_text("Move the settings back to the standard folder")
+                    .on_disabled_hover_text(if source == location::Source::Environment {
+                        hint.as_str()
+                    } else {
+                        "Already using the standard folder"
+                    });
+                if button.clicked() {
+                    self.move_settings_dir(location::default_dir());
+                    self.refocus(&button);
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Viewer mode:")));
+                let response = ui
+                    .checkbox(&mut self.viewer_mode, tr("Read-only"))
+                    .on_hover_text(
+                        "Show locales, paths and checks without writing, launching or saving anything, e.g. while a helper looks at this PC (also: start with --viewer)",
+                    );
+                // Saved now: nothing is saved once it is on
+                if response.changed() && self.viewer_mode {
+                    self.status = Some(match self.save_now() {
+                        Ok(()) => "Viewer mode on".into(),
+                        Err(e) => format!("Error saving settings: {}", e),
+                    });
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_sized([label_w, 24.0], egui::Label::new(tr("Start over:")));
+                let button = ui.button(tr("Reset to defaults…")).on_hover_text(
+                    "Clear all settings and the saved window position (profiles and run history are kept)",
+                );
+                if button.clicked() {
+                    self.reset_to_defaults(ui.ctx());
+                    self.refocus(&button);
+                }
+            });
+        });
+
+        ui.separator();
+        self.appearance_panel(ui);
+    }
+}
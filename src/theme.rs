@@ -0,0 +1,98 @@
+//! Light/dark theme choice and the status colors, which need different shades to stay
+//! readable on either background.
+//!
+//! In high-contrast mode the status colors come from a palette that stays apart for
+//! colorblind eyes (blue/orange instead of green/red), and text is drawn at full contrast.
+
+use eframe::egui::{self, Color32};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Follow the light/dark setting of the OS.
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Dark, Theme::Light];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::System => "Follow Windows",
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+        }
+    }
+
+    pub fn preference(self) -> egui::ThemePreference {
+        match self {
+            Self::System => egui::ThemePreference::System,
+            Self::Dark => egui::ThemePreference::Dark,
+            Self::Light => egui::ThemePreference::Light,
+        }
+    }
+}
+
+/// Use the high-contrast palette from the next color on.
+pub fn set_high_contrast(on: bool) {
+    HIGH_CONTRAST.store(on, Ordering::Relaxed);
+}
+
+pub fn high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+
+/// Text color overriding the theme's in high-contrast mode.
+pub fn text_override(dark_mode: bool) -> Option<Color32> {
+    high_contrast().then_some(if dark_mode {
+        Color32::WHITE
+    } else {
+        Color32::BLACK
+    })
+}
+
+/// Color of success and of values that match what is wanted.
+pub fn good(ui: &egui::Ui) -> Color32 {
+    match (high_contrast(), ui.visuals().dark_mode) {
+        (false, true) => Color32::from_rgb(90, 200, 90),
+        (false, false) => Color32::from_rgb(0, 130, 0),
+        (true, true) => Color32::from_rgb(86, 180, 233),
+        (true, false) => Color32::from_rgb(0, 80, 160),
+    }
+}
+
+/// Color of warnings.
+pub fn warn(ui: &egui::Ui) -> Color32 {
+    match (high_contrast(), ui.visuals().dark_mode) {
+        (false, true) => Color32::from_rgb(230, 160, 50),
+        (false, false) => Color32::from_rgb(170, 90, 0),
+        (true, true) => Color32::from_rgb(240, 228, 66),
+        (true, false) => Color32::from_rgb(120, 90, 0),
+    }
+}
+
+/// Color of errors and of values that differ from what is wanted.
+pub fn bad(ui: &egui::Ui) -> Color32 {
+    match (high_contrast(), ui.visuals().dark_mode) {
+        (false, true) => Color32::from_rgb(255, 100, 100),
+        (false, false) => Color32::from_rgb(190, 0, 0),
+        (true, true) => Color32::from_rgb(255, 150, 40),
+        (true, false) => Color32::from_rgb(180, 60, 0),
+    }
+}
+
+/// `text` marked ✔ or ✖ and its color, for a value that does or doesn't match what is
+/// wanted; the mark tells the two apart without relying on the color.
+pub fn verdict(ui: &egui::Ui, ok: bool, text: impl std::fmt::Display) -> (String, Color32) {
+    if ok {
+        (format!("✔ {}", text), good(ui))
+    } else {
+        (format!("✖ {}", text), bad(ui))
+    }
+}
@@ -0,0 +1,42 @@
+//! The run timeline: the steps of a run side by side, each marked done, running, failed or
+//! pending, with a bar showing how far along it is.
+
+use crate::sequence::StepState;
+use crate::theme;
+use eframe::egui;
+
+// Width of one step's bar; titles longer than this wrap
+const STEP_WIDTH: f32 = 110.0;
+
+/// Draw `steps` (from `Sequence::timeline`) as a row that wraps onto more lines when narrow.
+pub fn show(ui: &mut egui::Ui, steps: &[(String, StepState)]) {
+    ui.horizontal_wrapped(|ui| {
+        for (i, (title, state)) in steps.iter().enumerate() {
+            if i > 0 {
+                ui.weak("›");
+            }
+            ui.vertical(|ui| {
+                ui.set_width(STEP_WIDTH);
+                let (mark, color, done) = match *state {
+                    StepState::Done => ("✔", theme::good(ui), 1.0),
+                    StepState::Current(progress) => (
+                        "▶",
+                        ui.visuals().strong_text_color(),
+                        progress.unwrap_or(0.0),
+                    ),
+                    StepState::Failed => ("✖", theme::bad(ui), 0.0),
+                    StepState::Pending => ("○", ui.visuals().weak_text_color(), 0.0),
+                };
+                ui.colored_label(color, format!("{} {}", mark, title));
+                let bar = egui::ProgressBar::new(done)
+                    .desired_width(STEP_WIDTH)
+                    .desired_height(4.0);
+                let bar = match *state {
+                    StepState::Done => bar.fill(theme::good(ui)),
+                    _ => bar,
+                };
+                ui.add(bar);
+            });
+        }
+    });
+}
@@ -0,0 +1,148 @@
+//! Headless `entitan validate --manifest pack.toml` for packagers.
+//!
+//! The manifest describes an install relative to its own directory:
+//!
+//! ```toml
+//! launcher = "Battle.net/Battle.net.exe"
+//! wow_executable = "World of Warcraft/Wow.exe"
+//! config = "World of Warcraft/WTF/Config.wtf"
+//! locale = "enUS"                               # expected audioLocale/textLocale
+//! required = ["World of Warcraft/Data/common.MPQ"]
+//! ```
+//!
+//! Every check is printed; the exit code is non-zero if any of them failed.
+
+use crate::wtf::WtfDocument;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+#[derive(serde::Deserialize)]
+struct Manifest {
+    launcher: Option<String>,
+    wow_executable: Option<String>,
+    config: Option<String>,
+    locale: Option<String>,
+    #[serde(default)]
+    required: Vec<String>,
+}
+
+/// Run the subcommand with the arguments following `validate`; returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let manifest_path = match args {
+        [flag, path] if flag == "--manifest" => PathBuf::from(path),
+        _ => {
+            eprintln!("usage: entitan validate --manifest <pack.toml>");
+            return EXIT_USAGE;
+        }
+    };
+    let manifest: Manifest = match fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| toml::from_str(&s).map_err(|e| e.to_string()))
+    {
+        Ok(m) => m,
+        Err(e) => {
+            eprintln!("Failed to read manifest {}: {}", manifest_path.display(), e);
+            return EXIT_USAGE;
+        }
+    };
+    let base = manifest_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let results = check(&manifest, &base);
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    for result in &results {
+        match result {
+            Ok(msg) => println!("ok   {}", msg),
+            Err(msg) => println!("FAIL {}", msg),
+        }
+    }
+    println!("{} check(s), {} failed", results.len(), failed);
+    if failed > 0 { EXIT_FAILED } else { 0 }
+}
+
+/// Print into the console enTitan was started from. The exe is built for the GUI subsystem,
+/// so Windows gives it no console of its own and `println!` output would be lost; output
+/// redirected to a file or pipe already has a handle and is left alone.
+#[cfg(windows)]
+pub fn attach_console() {
+    use windows_sys::Win32::System::Console::{
+        ATTACH_PARENT_PROCESS, AttachConsole, GetStdHandle, STD_OUTPUT_HANDLE,
+    };
+    if unsafe { GetStdHandle(STD_OUTPUT_HANDLE) }.is_null() {
+        // Fails when there is no parent console (started from Explorer); nothing to print to then
+        unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+    }
+}
+
+#[cfg(not(windows))]
+pub fn attach_console() {}
+
+fn check(manifest: &Manifest, base: &Path) -> Vec<Result<String, String>> {
+    let mut results = Vec::new();
+    for (label, path, ext) in [
+        ("launcher", &manifest.launcher, "exe"),
+        ("wow_executable", &manifest.wow_executable, "exe"),
+        ("config", &manifest.config, "wtf"),
+    ] {
+        if let Some(path) = path {
+            let full = base.join(path);
+            results.push(if crate::is_file_with_ext(&full, ext) {
+                Ok(format!("{}: {}", label, full.display()))
+            } else {
+                Err(format!(
+                    "{}: {} is not an existing .{} file",
+                    label,
+                    full.display(),
+                    ext
+                ))
+            });
+        }
+    }
+    for path in &manifest.required {
+        let full = base.join(path);
+        results.push(if full.exists() {
+            Ok(format!("required: {}", full.display()))
+        } else {
+            Err(format!("required: {} is missing", full.display()))
+        });
+    }
+    if let (Some(locale), Some(config)) = (&manifest.locale, &manifest.config) {
+        results.extend(check_locale(&base.join(config), locale));
+    }
+    results
+}
+
+fn check_locale(config: &Path, expected: &str) -> Vec<Result<String, String>> {
+    let doc = match config.metadata() {
+        Ok(meta) if meta.len() > crate::limits::get().max_config_bytes => {
+            Err("file is too large".to_string())
+        }
+        Ok(_) => fs::read(config)
+            .map_err(|e| e.to_string())
+            .and_then(|b| WtfDocument::parse(&b)),
+        Err(e) => Err(e.to_string()),
+    };
+    let doc = match doc {
+        Ok(doc) => doc,
+        Err(e) => {
+            return vec![Err(format!(
+                "locale: cannot read {}: {}",
+                config.display(),
+                e
+            ))];
+        }
+    };
+    ["audioLocale", "textLocale"]
+        .into_iter()
+        .map(|key| match doc.get(key) {
+            Some(v) if v.eq_ignore_ascii_case(expected) => Ok(format!("{} is {}", key, v)),
+            Some(v) => Err(format!("{} is {}, expected {}", key, v, expected)),
+            None => Err(format!("{} is not set, expected {}", key, expected)),
+        })
+        .collect()
+}
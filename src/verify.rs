@@ -0,0 +1,191 @@
+//! "Verify locale": which locale the client actually ran in, read from what it left next to
+//! Wow.exe rather than trusting Config.wtf. The client keeps its cache per locale in
+//! `Cache/WDB/<locale>`, and its logs under `Logs` may name the locale.
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Only the end of a log is searched; the locale is named near the latest session start
+const LOG_TAIL_BYTES: u64 = 256 * 1024;
+// The combat log can grow to hundreds of MB and never names the locale
+const COMBAT_LOG_PREFIX: &str = "wowcombatlog";
+
+/// A locale the client used, where that was seen and when.
+pub struct Evidence {
+    pub locale: &'static str,
+    pub source: String,
+    pub modified: SystemTime,
+}
+
+/// The most recent evidence of the locale the client ran in, written at or after `since`
+/// if given.
+pub fn detect(wow_exe: &Path, since: Option<SystemTime>) -> Option<Evidence> {
+    let dir = wow_exe.parent()?;
+    let mut found: Vec<Evidence> = Vec::new();
+    found.extend(from_cache(dir));
+    found.extend(from_logs(dir));
+    found
+        .into_iter()
+        .filter(|e| since.is_none_or(|since| e.modified >= since))
+        .max_by_key(|e| e.modified)
+}
+
+/// Likely reasons the client ran in another locale than `preferred`, most specific first;
+/// `synced_bnet` and `enforced` tell which of enTitan's own remedies are already on.
+pub fn suggestions(
+    wow_exe: &Path,
+    preferred: &str,
+    locale_arg: Option<&str>,
+    synced_bnet: bool,
+    enforced: bool,
+) -> Vec<String> {
+    let mut tips = Vec::new();
+    if let Some(arg) = locale_arg
+        && !arg.eq_ignore_ascii_case(preferred)
+    {
+        tips.push(format!(
+            "WoW was started with -locale {}, which overrides Config.wtf; remove it from the arguments",
+            arg
+        ));
+    }
+    if let Some(dir) = wow_exe.parent()
+        && !dir.join("Data").join(preferred).is_dir()
+    {
+        tips.push(format!(
+            "Data/{} is missing: the {} language files aren't installed, so the client falls back to another locale",
+            preferred, preferred
+        ));
+    }
+    if !synced_bnet {
+        tips.push(
+            "Turn on \"Also update Battle.net.config\" so Battle.net stops resetting the locale"
+                .into(),
+        );
+    }
+    if !enforced {
+        tips.push("Turn on Enforce to rewrite Config.wtf whenever it is reset".into());
+    }
+    tips
+}
+
+/// The locale folder of `Cache/WDB` written to last.
+fn from_cache(dir: &Path) -> Option<Evidence> {
+    let wdb = dir.join("Cache").join("WDB");
+    fs::read_dir(&wdb)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let locale = crate::locales::canonical(name.to_str()?)?;
+            let path = entry.path();
+            Some(Evidence {
+                locale,
+                modified: newest(&path)?,
+                source: format!("Cache/WDB/{}", locale),
+            })
+        })
+        .max_by_key(|e| e.modified)
+}
+
+/// The last known locale code named in the newest log file (other than the combat log).
+fn from_logs(dir: &Path) -> Option<Evidence> {
+    let logs: Vec<PathBuf> = fs::read_dir(dir.join("Logs"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| crate::is_file_with_ext(p, "log") || crate::is_file_with_ext(p, "txt"))
+        .filter(|p| {
+            !p.file_name().is_some_and(|n| {
+                n.to_string_lossy()
+                    .to_lowercase()
+                    .starts_with(COMBAT_LOG_PREFIX)
+            })
+        })
+        .collect();
+    let (path, modified) = logs
+        .into_iter()
+        .filter_map(|p| Some((p.metadata().ok()?.modified().ok()?, p)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(modified, p)| (p, modified))?;
+    let tail = read_tail(&path).ok()?;
+    let locale = last_locale(&String::from_utf8_lossy(&tail))?;
+    Some(Evidence {
+        locale,
+        modified,
+        source: format!(
+            "Logs/{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ),
+    })
+}
+
+/// The last `LOG_TAIL_BYTES` of the file at `path`.
+fn read_tail(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL_BYTES)))?;
+    let mut tail = Vec::new();
+    file.take(LOG_TAIL_BYTES).read_to_end(&mut tail)?;
+    Ok(tail)
+}
+
+/// The last known locale code in `text` that stands as a word of its own.
+fn last_locale(text: &str) -> Option<&'static str> {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .rev()
+        .find_map(|word| {
+            crate::locales::KNOWN_LOCALES
+                .iter()
+                .find(|(code, ..)| *code == word)
+                .map(|(code, ..)| *code)
+        })
+}
+
+/// Latest modification time of `dir` or any file directly inside it.
+fn newest(dir: &Path) -> Option<SystemTime> {
+    let own = dir.metadata().ok()?.modified().ok();
+    let files = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|e| e.metadata().ok()?.modified().ok());
+    own.into_iter().chain(files).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_must_be_a_whole_word() {
+        assert_eq!(last_locale("Locale: enUS\nswitched to deDE."), Some("deDE"));
+        assert_eq!(
+            last_locale("textLocale=frFR path=C:\\enUSx\\deDEfoo"),
+            Some("frFR")
+        );
+        assert_eq!(last_locale("[esMX] loaded"), Some("esMX"));
+        assert_eq!(last_locale("xenUS deDEy enus"), None);
+        assert_eq!(last_locale(""), None);
+    }
+
+    #[test]
+    fn logs_skip_the_combat_log_and_read_only_the_tail() {
+        let dir = std::env::temp_dir().join(format!("entitan-verify-{}", std::process::id()));
+        let logs = dir.join("Logs");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&logs).unwrap();
+        let client = logs.join("Client.log");
+        let mut text = "Locale deDE\n".to_string();
+        text.push_str(&"x".repeat(LOG_TAIL_BYTES as usize));
+        text.push_str("\nLocale frFR\n");
+        fs::write(&client, &text).unwrap();
+        assert_eq!(read_tail(&client).unwrap().len() as u64, LOG_TAIL_BYTES);
+        // Written last, but never taken as the newest log
+        fs::write(logs.join("WoWCombatLog-101626_203000.txt"), "enUS\n").unwrap();
+        let found = from_logs(&dir).unwrap();
+        assert_eq!(found.locale, "frFR");
+        assert_eq!(found.source, "Logs/Client.log");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,299 @@
+//! Minimal JSON webhook client: POSTs a rendered template to a user-configured URL.
+//!
+//! Windows uses WinHTTP (system proxy and TLS); elsewhere the request goes through `curl`.
+
+use std::sync::mpsc::Receiver;
+
+/// POST `body` as JSON to `url` on a background thread; the receiver yields the outcome.
+pub fn post_json_async(url: String, body: String) -> Receiver<Result<(), String>> {
+    crate::repaint::spawn(move || post_json(&url, &body))
+}
+
+/// POST `body` as JSON to `url`, failing unless the server answers with a 2xx status.
+pub fn post_json(url: &str, body: &str) -> Result<(), String> {
+    let status = send(&parse_url(url)?, body)?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("server answered HTTP {}", status))
+    }
+}
+
+/// Replace `{name}` placeholders in a JSON template; values are escaped for use inside
+/// JSON strings.
+pub fn render_template(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("{{{}}}", name), &json_escape(value));
+    }
+    out
+}
+
+struct Url {
+    secure: bool,
+    // Without the brackets of an IPv6 literal
+    host: String,
+    port: u16,
+    // Path including any query string, always starting with '/'
+    path: String,
+}
+
+fn parse_url(url: &str) -> Result<Url, String> {
+    let url = url.trim();
+    let (secure, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        return Err("URL must start with http:// or https://".into());
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    // An IPv6 literal is bracketed so its colons aren't taken for the port separator
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| format!("unclosed '[' in {:?}", authority))?;
+            match after {
+                "" => (host, None),
+                _ => match after.strip_prefix(':') {
+                    Some(port) => (host, Some(port)),
+                    None => return Err(format!("unexpected {:?} after the host", after)),
+                },
+            }
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = match port {
+        Some(port) => port
+            .parse()
+            .map_err(|_| format!("invalid port {:?}", port))?,
+        None => {
+            if secure {
+                443
+            } else {
+                80
+            }
+        }
+    };
+    if host.is_empty() {
+        return Err("URL has no host".into());
+    }
+    Ok(Url {
+        secure,
+        host: host.to_string(),
+        port,
+        path: path.to_string(),
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn send(url: &Url, body: &str) -> Result<u16, String> {
+    use crate::launch::wide;
+    use windows_sys::Win32::Networking::WinHttp::{
+        WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, WINHTTP_FLAG_SECURE, WINHTTP_QUERY_FLAG_NUMBER,
+        WINHTTP_QUERY_STATUS_CODE, WinHttpCloseHandle, WinHttpConnect, WinHttpOpen,
+        WinHttpOpenRequest, WinHttpQueryHeaders, WinHttpReceiveResponse, WinHttpSendRequest,
+        WinHttpSetTimeouts,
+    };
+
+    /// Closes the wrapped WinHTTP handle when dropped.
+    struct Handle(*mut core::ffi::c_void);
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            unsafe { WinHttpCloseHandle(self.0) };
+        }
+    }
+    let check = |handle: *mut core::ffi::c_void| {
+        if handle.is_null() {
+            Err(std::io::Error::last_os_error().to_string())
+        } else {
+            Ok(Handle(handle))
+        }
+    };
+
+    let agent = wide("enTitan");
+    let session = check(unsafe {
+        WinHttpOpen(
+            agent.as_ptr(),
+            WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY,
+            std::ptr::null(),
+            std::ptr::null(),
+            0,
+        )
+    })?;
+    unsafe { WinHttpSetTimeouts(session.0, 10_000, 10_000, 10_000, 10_000) };
+    let host = wide(&url.host);
+    let connection = check(unsafe { WinHttpConnect(session.0, host.as_ptr(), url.port, 0) })?;
+    let (verb, path) = (wide("POST"), wide(&url.path));
+    let flags = if url.secure { WINHTTP_FLAG_SECURE } else { 0 };
+    let request = check(unsafe {
+        WinHttpOpenRequest(
+            connection.0,
+            verb.as_ptr(),
+            path.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            std::ptr::null(),
+            flags,
+        )
+    })?;
+    let headers = wide("Content-Type: application/json\r\n");
+    let body = body.as_bytes();
+    let sent = unsafe {
+        WinHttpSendRequest(
+            request.0,
+            headers.as_ptr(),
+            (headers.len() - 1) as u32,
+            body.as_ptr() as *const core::ffi::c_void,
+            body.len() as u32,
+            body.len() as u32,
+            0,
+        )
+    };
+    if sent == 0 || unsafe { WinHttpReceiveResponse(request.0, std::ptr::null_mut()) } == 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    let mut status = 0u32;
+    let mut len = std::mem::size_of::<u32>() as u32;
+    let ok = unsafe {
+        WinHttpQueryHeaders(
+            request.0,
+            WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+            std::ptr::null(),
+            &mut status as *mut u32 as *mut core::ffi::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(status as u16)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn send(url: &Url, body: &str) -> Result<u16, String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let scheme = if url.secure { "https" } else { "http" };
+    let host = if url.host.contains(':') {
+        format!("[{}]", url.host)
+    } else {
+        url.host.clone()
+    };
+    let full = format!("{}://{}:{}{}", scheme, host, url.port, url.path);
+    let mut child = Command::new("curl")
+        .args([
+            "-sS",
+            "-o",
+            "/dev/null",
+            "-w",
+            "%{http_code}",
+            "--max-time",
+            "30",
+        ])
+        .args(["-X", "POST", "-H", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", &full])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| "unexpected curl output".to_string())
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_url_defaults_port_and_path() {
+        let url = parse_url("http://example.com").unwrap();
+        assert!(!url.secure);
+        assert_eq!(url.host, "example.com");
+        assert_eq!(url.port, 80);
+        assert_eq!(url.path, "/");
+        let url = parse_url(" https://example.com/hook?x=1 ").unwrap();
+        assert!(url.secure);
+        assert_eq!(url.port, 443);
+        assert_eq!(url.path, "/hook?x=1");
+    }
+
+    #[test]
+    fn parse_url_reads_explicit_port() {
+        let url = parse_url("https://example.com:8443/a/b").unwrap();
+        assert_eq!(url.host, "example.com");
+        assert_eq!(url.port, 8443);
+        assert_eq!(url.path, "/a/b");
+    }
+
+    #[test]
+    fn parse_url_handles_ipv6_literals() {
+        let url = parse_url("http://[::1]:8080/hook").unwrap();
+        assert_eq!(url.host, "::1");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.path, "/hook");
+        let url = parse_url("https://[fe80::1]").unwrap();
+        assert_eq!(url.host, "fe80::1");
+        assert_eq!(url.port, 443);
+        assert_eq!(url.path, "/");
+    }
+
+    #[test]
+    fn parse_url_rejects_bad_urls() {
+        for url in [
+            "example.com",
+            "ftp://example.com",
+            "http://",
+            "http:///path",
+            "http://:8080/",
+            "http://example.com:port/",
+            "http://example.com:70000/",
+            "http://example.com:/",
+            "http://[::1/",
+            "http://[::1]x/",
+            "http://[]/",
+        ] {
+            assert!(parse_url(url).is_err(), "{:?}", url);
+        }
+    }
+
+    #[test]
+    fn render_template_escapes_values() {
+        let rendered = render_template(
+            r#"{"text": "{name} says {msg}", "n": "{missing}"}"#,
+            &[("name", "Bob \"B\""), ("msg", "line1\nline2\\")],
+        );
+        assert_eq!(
+            rendered,
+            r#"{"text": "Bob \"B\" says line1\nline2\\", "n": "{missing}"}"#
+        );
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "Bob \"B\" says line1\nline2\\");
+    }
+}
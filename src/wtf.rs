@@ -0,0 +1,623 @@
+//! Config.wtf reading and writing.
+//!
+//! `WtfDocument` keeps the file's encoding (including any BOM) and every line's original
+//! terminator, so rewriting a value only changes the bytes of the lines that were edited.
+//! Within an edited line only the value itself is replaced: indentation, spacing and any
+//! trailing text stay as they were. Blank lines and comments are never touched, and new
+//! entries go before any trailing blank lines so the end of the file keeps its shape.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Default largest Config.wtf we read or edit (`limits.maxConfigBytes`). Real configs with
+/// many cvars stay well below this; anything bigger is most likely not a Config.wtf at all.
+pub const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Text encoding detected from the file's BOM (or its absence).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Bytes that aren't valid UTF-8; mapped 1:1 onto U+0000..U+00FF so they round-trip.
+    Latin1,
+}
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+// Trailing blank lines held back by `stream_set` before they are written out regardless
+const MAX_BLANK_RUN: usize = 64 * 1024;
+
+struct Line {
+    text: String,
+    // "\r\n", "\n", "\r" or "" for a final line without terminator
+    ending: &'static str,
+}
+
+pub struct WtfDocument {
+    encoding: Encoding,
+    lines: Vec<Line>,
+}
+
+/// A problem reported by `WtfDocument::lint`.
+pub struct Finding {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl WtfDocument {
+    /// Decode raw file contents, remembering the encoding and line endings.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let (encoding, text) = if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+            let text = std::str::from_utf8(rest).map_err(|e| format!("invalid UTF-8: {}", e))?;
+            (Encoding::Utf8Bom, text.to_string())
+        } else if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+            (Encoding::Utf16Le, decode_utf16(rest, u16::from_le_bytes)?)
+        } else if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+            (Encoding::Utf16Be, decode_utf16(rest, u16::from_be_bytes)?)
+        } else if let Ok(text) = std::str::from_utf8(bytes) {
+            (Encoding::Utf8, text.to_string())
+        } else {
+            (Encoding::Latin1, bytes.iter().map(|&b| b as char).collect())
+        };
+        Ok(Self {
+            encoding,
+            lines: split_lines(&text),
+        })
+    }
+
+    /// Encode the document back into bytes using the original encoding and BOM.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut text = String::new();
+        for line in &self.lines {
+            text.push_str(&line.text);
+            text.push_str(line.ending);
+        }
+        match self.encoding {
+            Encoding::Utf8 => text.into_bytes(),
+            Encoding::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+            Encoding::Utf16Le => {
+                let mut out = UTF16LE_BOM.to_vec();
+                out.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+                out
+            }
+            Encoding::Utf16Be => {
+                let mut out = UTF16BE_BOM.to_vec();
+                out.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+                out
+            }
+            // Characters outside Latin-1 can only come from our own edits; replace them
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+                .collect(),
+        }
+    }
+
+    /// Value of the last `SET <key> "<value>"` line for `key` (case-insensitive).
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.lines
+            .iter()
+            .rev()
+            .filter_map(|l| parse_set(&l.text))
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.to_string())
+    }
+
+    /// Every `SET` entry in file order as (key, value); later duplicates are included.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.lines
+            .iter()
+            .filter_map(|l| parse_set(&l.text))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// Set `key` to `value`, editing every existing SET line for it in place (only the
+    /// value changes) or appending a new line if the key isn't present. Lines that already
+    /// hold `value` are left byte for byte as they are.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let mut found = false;
+        for line in self.lines.iter_mut() {
+            let Some((k, v)) = parse_set(&line.text) else {
+                continue;
+            };
+            if !k.eq_ignore_ascii_case(key) {
+                continue;
+            }
+            found = true;
+            if v == value {
+                continue;
+            }
+            if let Some(text) = replace_value(&line.text, value) {
+                line.text = text;
+            }
+        }
+        if !found {
+            self.append(format!("SET {} \"{}\"", key, value));
+        }
+    }
+
+    /// Check every line for things WoW would misread or ignore: lines that aren't
+    /// `SET <key> "<value>"`, unclosed quotes, keys set more than once and locale values that
+    /// aren't known WoW locales.
+    pub fn lint(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            let number = index + 1;
+            let mut report = |message: String| {
+                findings.push(Finding {
+                    line: number,
+                    message,
+                })
+            };
+            let text = line.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = parse_set(text) else {
+                report(match text.strip_prefix("SET") {
+                    Some(rest) if rest.is_empty() || rest.starts_with(char::is_whitespace) => {
+                        "SET without a value".into()
+                    }
+                    _ => format!("not a SET line: {:?}", text),
+                });
+                continue;
+            };
+            // parse_set succeeded, so the line is "SET", whitespace, the key and the raw value
+            let raw_value = text["SET".len()..].trim_start()[key.len()..].trim();
+            if let Some(quoted) = raw_value.strip_prefix('"') {
+                match quoted.find('"') {
+                    None => report(format!("unclosed quote in value of {}", key)),
+                    Some(end) if !quoted[end + 1..].trim().is_empty() => {
+                        report(format!("unexpected text after the value of {}", key))
+                    }
+                    Some(_) => {}
+                }
+            } else {
+                report(format!("value of {} is not quoted", key));
+            }
+            if value.chars().any(char::is_control) {
+                report(format!("value of {} contains control characters", key));
+            }
+            if (key.eq_ignore_ascii_case("audioLocale") || key.eq_ignore_ascii_case("textLocale"))
+                && crate::locales::canonical(value).is_none()
+            {
+                report(format!("{} {:?} is not a known WoW locale", key, value));
+            }
+            match first_seen.get(&key.to_ascii_lowercase()) {
+                Some(first) => report(format!(
+                    "duplicate SET {} (first set on line {}; the last one wins)",
+                    key, first
+                )),
+                None => {
+                    first_seen.insert(key.to_ascii_lowercase(), number);
+                }
+            }
+        }
+        findings
+    }
+
+    /// Add a line after the last non-blank one, keeping the file's trailing structure:
+    /// trailing blank lines stay at the end and the new line ends like the line before it.
+    /// An unterminated last line gets the file's usual line ending and the new last line
+    /// stays unterminated instead.
+    fn append(&mut self, text: String) {
+        let usual = self.usual_ending();
+        let at = self
+            .lines
+            .iter()
+            .rposition(|l| !l.text.trim_ascii().is_empty())
+            .map_or(0, |i| i + 1);
+        let ending = match at.checked_sub(1).map(|i| &mut self.lines[i]) {
+            Some(previous) if previous.ending.is_empty() => {
+                previous.ending = usual;
+                ""
+            }
+            Some(previous) => previous.ending,
+            None => usual,
+        };
+        self.lines.insert(at, Line { text, ending });
+    }
+
+    /// Most common line terminator in the file ("\n" for files without any).
+    fn usual_ending(&self) -> &'static str {
+        let count = |e: &str| self.lines.iter().filter(|l| l.ending == e).count();
+        ["\r\n", "\n", "\r"]
+            .into_iter()
+            .max_by_key(|e| count(e))
+            .filter(|e| count(e) > 0)
+            .unwrap_or("\n")
+    }
+}
+
+/// Replace `path` with `bytes` atomically: write and fsync a temp file in the same
+/// directory, then rename it over the original so a crash never leaves a truncated file.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::other("path has no file name"))?;
+    let tmp = dir.join(format!(".{}.entitan-tmp", name.to_string_lossy()));
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Large-file mode: the last values of `keys` in `path`, found by scanning it line by line so
+/// memory stays bounded for configs bloated far beyond the size limit. Only byte-oriented
+/// encodings (UTF-8, Latin-1) are supported.
+pub fn scan_values(path: &Path, keys: &[&str]) -> std::io::Result<Vec<Option<String>>> {
+    let mut values = vec![None; keys.len()];
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut line = Vec::new();
+    let mut first = true;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let text = line_text(&line, std::mem::take(&mut first))?;
+        if let Some((k, v)) = parse_set(&text)
+            && let Some(i) = keys.iter().position(|key| k.eq_ignore_ascii_case(key))
+        {
+            values[i] = Some(v.to_string());
+        }
+        line.clear();
+    }
+    Ok(values)
+}
+
+/// Large-file mode counterpart of `WtfDocument::set`: stream `path` into a temp file,
+/// replacing the quoted value of every SET line for the given keys and adding keys that
+/// weren't present before any trailing blank lines, then rename it over the original. Other
+/// lines are copied byte for byte.
+pub fn stream_set(path: &Path, cvars: &[(&str, &str)]) -> std::io::Result<()> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::other("path has no file name"))?;
+    let tmp = path.with_file_name(format!(".{}.entitan-tmp", name.to_string_lossy()));
+    let result = (|| {
+        let mut reader = BufReader::new(fs::File::open(path)?);
+        let mut writer = BufWriter::new(fs::File::create(&tmp)?);
+        let mut found = vec![false; cvars.len()];
+        let mut line = Vec::new();
+        let mut first = true;
+        let mut ending: &[u8] = b"\n";
+        // Terminator of the last non-blank line, None if it has none
+        let mut last_ending: Option<&[u8]> = Some(b"\n");
+        // Blank lines not yet followed by a non-blank one; new keys go before them
+        let mut blank_run = Vec::new();
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            if std::mem::take(&mut first) {
+                line_text(&line, true)?;
+                if line.ends_with(b"\r\n") {
+                    ending = b"\r\n";
+                }
+                // For files with nothing but blank lines
+                last_ending = Some(ending);
+            }
+            if line.iter().all(u8::is_ascii_whitespace) {
+                blank_run.extend_from_slice(&line);
+                // Keep memory bounded for files bloated with blank lines
+                if blank_run.len() > MAX_BLANK_RUN {
+                    writer.write_all(&blank_run)?;
+                    last_ending = terminator(&blank_run);
+                    blank_run.clear();
+                }
+                line.clear();
+                continue;
+            }
+            writer.write_all(&blank_run)?;
+            blank_run.clear();
+            last_ending = terminator(&line);
+            let edited = std::str::from_utf8(&line).ok().and_then(|text| {
+                let body = text.trim_start_matches('\u{feff}');
+                let (key, current) = parse_set(body)?;
+                let i = cvars
+                    .iter()
+                    .position(|(k, _)| k.eq_ignore_ascii_case(key))?;
+                found[i] = true;
+                if current == cvars[i].1 {
+                    return None;
+                }
+                let bom = &text[..text.len() - body.len()];
+                Some(format!("{}{}", bom, replace_value(body, cvars[i].1)?))
+            });
+            match edited {
+                Some(text) => writer.write_all(text.as_bytes())?,
+                None => writer.write_all(&line)?,
+            }
+            line.clear();
+        }
+        // Like `WtfDocument::append`: new lines end like the last line, or the last new
+        // line stays unterminated like the last line was
+        for ((key, value), found) in cvars.iter().zip(found) {
+            if found {
+                continue;
+            }
+            match last_ending {
+                Some(last) => {
+                    write!(writer, "SET {} \"{}\"", key, value)?;
+                    writer.write_all(last)?;
+                }
+                None => {
+                    writer.write_all(ending)?;
+                    write!(writer, "SET {} \"{}\"", key, value)?;
+                }
+            }
+        }
+        writer.write_all(&blank_run)?;
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Line terminator at the end of a raw line (LF or CRLF), None for an unterminated line.
+fn terminator(line: &[u8]) -> Option<&'static [u8]> {
+    if line.ends_with(b"\r\n") {
+        Some(b"\r\n")
+    } else if line.ends_with(b"\n") {
+        Some(b"\n")
+    } else {
+        None
+    }
+}
+
+/// Decode one raw line for large-file mode, rejecting UTF-16 files (BOM on the first line).
+fn line_text(line: &[u8], first: bool) -> std::io::Result<String> {
+    if first && (line.starts_with(UTF16LE_BOM) || line.starts_with(UTF16BE_BOM)) {
+        return Err(std::io::Error::other(
+            "large UTF-16 files are not supported",
+        ));
+    }
+    let line = if first {
+        line.strip_prefix(UTF8_BOM).unwrap_or(line)
+    } else {
+        line
+    };
+    Ok(match std::str::from_utf8(line) {
+        Ok(text) => text.to_string(),
+        Err(_) => line.iter().map(|&b| b as char).collect(),
+    })
+}
+
+/// Set or clear the read-only attribute of `path`.
+pub fn set_readonly(path: &Path, readonly: bool) -> std::io::Result<()> {
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_readonly(readonly);
+    fs::set_permissions(path, perms)
+}
+
+/// Split `SET <key> <value>` into key and (unquoted) value.
+fn parse_set(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix("SET")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let key_end = rest.find(char::is_whitespace)?;
+    let key = &rest[..key_end];
+    let value = rest[key_end..].trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.find('"').map(|end| &v[..end]))
+        .unwrap_or(value);
+    Some((key, value))
+}
+
+/// `line` (a SET line) with its value replaced by `value`. A quoted value only has the text
+/// between its quotes replaced; an unquoted or unclosed one is replaced by a quoted value.
+/// Everything before and after the value is kept byte for byte.
+fn replace_value(line: &str, value: &str) -> Option<String> {
+    let skip_ws = |from: usize| from + (line[from..].len() - line[from..].trim_start().len());
+    let after_set = skip_ws(0) + "SET".len();
+    let key_start = skip_ws(after_set);
+    let key_end = key_start + line[key_start..].find(char::is_whitespace)?;
+    let value_start = skip_ws(key_end);
+    let rest = &line[value_start..];
+    if let Some(len) = rest.strip_prefix('"').and_then(|r| r.find('"')) {
+        let (start, end) = (value_start + 1, value_start + 1 + len);
+        return Some(format!("{}{}{}", &line[..start], value, &line[end..]));
+    }
+    let end = if rest.starts_with('"') {
+        // Unclosed quote: the value runs to the end of the line
+        value_start + rest.trim_end().len()
+    } else {
+        value_start + rest.find(char::is_whitespace).unwrap_or(rest.len())
+    };
+    Some(format!(
+        "{}\"{}\"{}",
+        &line[..value_start],
+        value,
+        &line[end..]
+    ))
+}
+
+fn split_lines(text: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let Some(pos) = rest.find(['\r', '\n']) else {
+            lines.push(Line {
+                text: rest.to_string(),
+                ending: "",
+            });
+            break;
+        };
+        let ending = if rest[pos..].starts_with("\r\n") {
+            "\r\n"
+        } else if rest[pos..].starts_with('\r') {
+            "\r"
+        } else {
+            "\n"
+        };
+        lines.push(Line {
+            text: rest[..pos].to_string(),
+            ending,
+        });
+        rest = &rest[pos + ending.len()..];
+    }
+    lines
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err("truncated UTF-16 data".into());
+    }
+    let units = bytes.chunks_exact(2).map(|c| from_bytes([c[0], c[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|e| format!("invalid UTF-16: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Shapes of Config.wtf seen in the wild: line endings, BOMs, trailing blank lines and
+    // whitespace, a missing final newline, odd spacing and comments
+    const SAMPLES: &[&[u8]] = &[
+        b"",
+        b"\n",
+        b"\r\n\r\n",
+        b"SET textLocale \"enUS\"",
+        b"SET textLocale \"enUS\"\n",
+        b"SET textLocale \"enUS\"\r\n",
+        b"SET textLocale \"enUS\"\r",
+        b"SET textLocale \"enUS\"\n\n\n",
+        b"SET textLocale \"enUS\"\r\n\r\n",
+        b"SET textLocale \"enUS\"\n   \n\t",
+        b"SET textLocale \"enUS\"   \nSET audioLocale \"enUS\"\t\r\n",
+        b"  SET   textLocale   \"enUS\"  # comment\n\nSET gxApi \"D3D11\"\r\n",
+        b"SET textLocale enUS\nSET audioLocale \"enUS\n",
+        b"\xEF\xBB\xBFSET textLocale \"enUS\"\r\n\r\n",
+        b"SET realmName \"Caf\xE9\"\n",
+        b"\xFF\xFES\0E\0T\0 \0a\0 \0\"\0\x31\0\"\0\r\0\n\0\r\0\n\0",
+    ];
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("entitan-wtf-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn parse_and_encode_is_byte_identical() {
+        for sample in SAMPLES {
+            let doc = WtfDocument::parse(sample).unwrap();
+            assert_eq!(
+                doc.to_bytes(),
+                *sample,
+                "{:?}",
+                String::from_utf8_lossy(sample)
+            );
+        }
+    }
+
+    #[test]
+    fn setting_current_values_is_byte_identical() {
+        for sample in SAMPLES {
+            let mut doc = WtfDocument::parse(sample).unwrap();
+            for (key, value) in doc.entries() {
+                doc.set(&key, &value);
+            }
+            assert_eq!(
+                doc.to_bytes(),
+                *sample,
+                "{:?}",
+                String::from_utf8_lossy(sample)
+            );
+        }
+    }
+
+    #[test]
+    fn set_only_changes_the_value() {
+        let mut doc = WtfDocument::parse(b"  SET   textLocale   \"enUS\"  # x\r\n\r\n").unwrap();
+        doc.set("textLocale", "deDE");
+        assert_eq!(
+            doc.to_bytes(),
+            b"  SET   textLocale   \"deDE\"  # x\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn new_entries_go_before_trailing_blank_lines() {
+        let cases: &[(&[u8], &[u8])] = &[
+            (b"SET a \"1\"", b"SET a \"1\"\nSET b \"2\""),
+            (b"SET a \"1\"\n", b"SET a \"1\"\nSET b \"2\"\n"),
+            (
+                b"SET a \"1\"\r\n\r\n",
+                b"SET a \"1\"\r\nSET b \"2\"\r\n\r\n",
+            ),
+            (
+                b"SET a \"1\"\n\n  \n\t",
+                b"SET a \"1\"\nSET b \"2\"\n\n  \n\t",
+            ),
+            (b"\n\n", b"SET b \"2\"\n\n\n"),
+            (b"", b"SET b \"2\"\n"),
+        ];
+        for (before, after) in cases {
+            let mut doc = WtfDocument::parse(before).unwrap();
+            doc.set("b", "2");
+            assert_eq!(
+                doc.to_bytes(),
+                *after,
+                "{:?}",
+                String::from_utf8_lossy(before)
+            );
+        }
+    }
+
+    #[test]
+    fn stream_set_matches_document_set() {
+        for (i, sample) in SAMPLES.iter().enumerate() {
+            // Large-file mode doesn't handle UTF-16 and only splits lines at LF
+            if sample.starts_with(UTF16LE_BOM) || sample.ends_with(b"\"\r") {
+                continue;
+            }
+            let path = temp_path(&format!("stream-{}", i));
+            for cvars in [&[][..], &[("textLocale", "enUS")][..], &[("b", "2")][..]] {
+                fs::write(&path, sample).unwrap();
+                stream_set(&path, cvars).unwrap();
+                let mut doc = WtfDocument::parse(sample).unwrap();
+                for (key, value) in cvars {
+                    doc.set(key, value);
+                }
+                assert_eq!(
+                    fs::read(&path).unwrap(),
+                    doc.to_bytes(),
+                    "{:?} with {:?}",
+                    String::from_utf8_lossy(sample),
+                    cvars
+                );
+            }
+            let _ = fs::remove_file(&path);
+        }
+    }
+}